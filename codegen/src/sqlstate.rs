@@ -10,13 +10,21 @@ pub fn build(path: &Path) {
     let mut file = BufWriter::new(File::create(path.join("error/sqlstate.rs")).unwrap());
 
     let codes = parse_codes();
+    let classes = parse_classes();
 
     make_type(&mut file);
     make_consts(&codes, &mut file);
+    make_class(&classes, &mut file);
     make_map(&codes, &mut file);
+    make_name_map(&codes, &mut file);
 }
 
-fn parse_codes() -> LinkedHashMap<String, Vec<String>> {
+struct Code {
+    names: Vec<String>,
+    condition: Option<String>,
+}
+
+fn parse_codes() -> LinkedHashMap<String, Code> {
     let mut codes = LinkedHashMap::new();
 
     for line in ERRCODES_TXT.lines() {
@@ -28,13 +36,70 @@ fn parse_codes() -> LinkedHashMap<String, Vec<String>> {
         let code = it.next().unwrap().to_owned();
         it.next();
         let name = it.next().unwrap().replace("ERRCODE_", "");
+        // only one of the entries for a code has a condition name
+        let condition = it.next();
 
-        codes.entry(code).or_insert_with(Vec::new).push(name);
+        let code = codes.entry(code).or_insert_with(|| Code {
+            names: vec![],
+            condition: None,
+        });
+        code.names.push(name);
+        if let Some(condition) = condition {
+            code.condition = Some(condition.to_owned());
+        }
     }
 
     codes
 }
 
+struct Class {
+    variant: String,
+    description: String,
+}
+
+fn parse_classes() -> LinkedHashMap<String, Class> {
+    let mut classes = LinkedHashMap::new();
+
+    for line in ERRCODES_TXT.lines() {
+        if !line.starts_with("Section: Class ") {
+            continue;
+        }
+
+        let line = &line["Section: Class ".len()..];
+        let mut it = line.splitn(2, " - ");
+        let code = it.next().unwrap().to_owned();
+        let description = it.next().unwrap();
+        // leave out comments like "(SQL/MED)"
+        let description = match description.find(" (") {
+            Some(idx) => &description[..idx],
+            None => description,
+        };
+
+        let class = Class {
+            variant: class_variant(description),
+            description: description.to_owned(),
+        };
+        classes.insert(code, class);
+    }
+
+    classes
+}
+
+fn class_variant(description: &str) -> String {
+    let mut variant = String::new();
+
+    for word in description
+        .split(|c| c == ' ' || c == '/')
+        .filter(|w| !w.is_empty())
+    {
+        let mut chars = word.chars();
+        variant.extend(chars.next().unwrap().to_uppercase());
+        variant.extend(chars.flat_map(|c| c.to_lowercase()));
+    }
+
+    variant
+}
+
 fn make_type(file: &mut BufWriter<File>) {
     write!(
         file,
@@ -59,13 +124,24 @@ impl SqlState {{
     pub fn code(&self) -> &str {{
         &self.0
     }}
+
+    /// Returns the class of the error code, or `None` if it is not in a known class.
+    pub fn class(&self) -> Option<SqlStateClass> {{
+        self.0.get(..2).and_then(SqlStateClass::from_code)
+    }}
+
+    /// Returns the condition name of the error code, such as `unique_violation`, or `None` if
+    /// the code is not known.
+    pub fn name(&self) -> Option<&'static str> {{
+        condition_name(&self.0)
+    }}
 "
     ).unwrap();
 }
 
-fn make_consts(codes: &LinkedHashMap<String, Vec<String>>, file: &mut BufWriter<File>) {
-    for (code, names) in codes {
-        for name in names {
+fn make_consts(codes: &LinkedHashMap<String, Code>, file: &mut BufWriter<File>) {
+    for (code, code_info) in codes {
+        for name in &code_info.names {
             write!(
                 file,
                 r#"
@@ -81,7 +157,102 @@ fn make_consts(codes: &LinkedHashMap<String, Vec<String>>, file: &mut BufWriter<
     write!(file, "}}").unwrap();
 }
 
-fn make_map(codes: &LinkedHashMap<String, Vec<String>>, file: &mut BufWriter<File>) {
+fn make_class(classes: &LinkedHashMap<String, Class>, file: &mut BufWriter<File>) {
+    write!(
+        file,
+        "
+
+/// The class of a SQLSTATE error code, identified by its first two characters.
+#[derive(PartialEq, Eq, Copy, Clone, Debug, Hash)]
+pub enum SqlStateClass {{"
+    ).unwrap();
+
+    for (code, class) in classes {
+        write!(
+            file,
+            "
+    /// {code} - {description}
+    {variant},
+",
+            code = code,
+            description = class.description,
+            variant = class.variant,
+        ).unwrap();
+    }
+
+    write!(
+        file,
+        "
+    #[doc(hidden)]
+    __NonExhaustive,
+}}
+
+impl SqlStateClass {{
+    /// Creates a `SqlStateClass` from its two character code.
+    pub fn from_code(s: &str) -> Option<SqlStateClass> {{
+        match s {{
+"
+    ).unwrap();
+
+    for (code, class) in classes {
+        write!(
+            file,
+            "            \"{}\" => Some(SqlStateClass::{}),\n",
+            code, class.variant
+        ).unwrap();
+    }
+
+    write!(
+        file,
+        "            _ => None,
+        }}
+    }}
+
+    /// Returns the two character code of the class.
+    pub fn code(&self) -> &'static str {{
+        match *self {{
+"
+    ).unwrap();
+
+    for (code, class) in classes {
+        write!(
+            file,
+            "            SqlStateClass::{} => \"{}\",\n",
+            class.variant, code
+        ).unwrap();
+    }
+
+    write!(
+        file,
+        "            SqlStateClass::__NonExhaustive => unreachable!(),
+        }}
+    }}
+
+    /// Returns the description of the class, such as `Integrity Constraint Violation`.
+    pub fn description(&self) -> &'static str {{
+        match *self {{
+"
+    ).unwrap();
+
+    for class in classes.values() {
+        write!(
+            file,
+            "            SqlStateClass::{} => \"{}\",\n",
+            class.variant, class.description
+        ).unwrap();
+    }
+
+    write!(
+        file,
+        "            SqlStateClass::__NonExhaustive => unreachable!(),
+        }}
+    }}
+}}
+"
+    ).unwrap();
+}
+
+fn make_map(codes: &LinkedHashMap<String, Code>, file: &mut BufWriter<File>) {
     write!(
         file,
         "
@@ -89,9 +260,36 @@ fn make_map(codes: &LinkedHashMap<String, Vec<String>>, file: &mut BufWriter<Fil
 static SQLSTATE_MAP: phf::Map<&'static str, SqlState> = "
     ).unwrap();
     let mut builder = phf_codegen::Map::new();
-    for (code, names) in codes {
-        builder.entry(&**code, &format!("SqlState::{}", &names[0]));
+    for (code, code_info) in codes {
+        builder.entry(&**code, &format!("SqlState::{}", &code_info.names[0]));
     }
     builder.build(file).unwrap();
     write!(file, ";\n").unwrap();
 }
+
+fn make_name_map(codes: &LinkedHashMap<String, Code>, file: &mut BufWriter<File>) {
+    write!(
+        file,
+        "
+fn condition_name(code: &str) -> Option<&'static str> {{
+    match code {{
+"
+    ).unwrap();
+
+    for (code, code_info) in codes {
+        write!(
+            file,
+            "        \"{}\" => Some(\"{}\"),\n",
+            code,
+            code_info.condition.as_ref().unwrap()
+        ).unwrap();
+    }
+
+    write!(
+        file,
+        "        _ => None,
+    }}
+}}
+"
+    ).unwrap();
+}
@@ -64,6 +64,8 @@ port = 5433
 ssl = on
 ssl_cert_file = 'server.crt'
 ssl_key_file = 'server.key'
+wal_level = logical
+max_prepared_transactions = 10
 EOCONF
 
 cat > "$PGDATA/pg_hba.conf" <<-EOCONF
@@ -86,6 +88,9 @@ host    all             postgres        0.0.0.0/0            trust
 host    all             postgres        ::0/0                trust
 # Unix socket connections:
 local   all             postgres                             trust
+# Replication connections:
+host    replication     postgres        0.0.0.0/0            trust
+host    replication     postgres        ::0/0                trust
 EOCONF
 
 psql -v ON_ERROR_STOP=1 --username "$POSTGRES_USER" <<-EOSQL
@@ -96,4 +101,5 @@ psql -v ON_ERROR_STOP=1 --username "$POSTGRES_USER" <<-EOSQL
     CREATE ROLE ssl_user LOGIN;
     CREATE EXTENSION hstore;
     CREATE EXTENSION citext;
+    CREATE EXTENSION postgis;
 EOSQL
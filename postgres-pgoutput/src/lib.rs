@@ -0,0 +1,688 @@
+//! A decoder for `pgoutput`, the logical decoding output plugin built into Postgres.
+//!
+//! `pgoutput` is used by logical replication slots created with the `pgoutput` plugin. The data of
+//! each `XLogData` message received while streaming from such a slot is decoded into a
+//! `LogicalReplicationMessage` by `LogicalReplicationMessage::parse`.
+//!
+//! The plugin requires the `proto_version` and `publication_names` options to be passed when
+//! starting replication, e.g. `("proto_version", PROTOCOL_VERSION)` and
+//! `("publication_names", "my_publication")`.
+//!
+//! Relation messages are sent before the first change to each table in a session, and the
+//! changes themselves only refer to tables by OID, so consumers need to keep track of the
+//! relations they have seen to interpret the tuples.
+#![doc(html_root_url = "https://docs.rs/postgres-pgoutput/0.1")]
+#![warn(missing_docs)]
+extern crate byteorder;
+extern crate bytes;
+extern crate memchr;
+
+use byteorder::{BigEndian, ReadBytesExt};
+use bytes::Bytes;
+use memchr::memchr;
+use std::cmp;
+use std::io::{self, Read};
+use std::str;
+
+/// The version of the `pgoutput` protocol implemented by this crate.
+pub const PROTOCOL_VERSION: &str = "1";
+
+/// A Postgres OID.
+pub type Oid = u32;
+
+/// A message produced by the `pgoutput` plugin.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LogicalReplicationMessage {
+    /// The start of a transaction.
+    Begin(Begin),
+    /// The end of a transaction.
+    Commit(Commit),
+    /// The origin of a transaction replicated from another node.
+    Origin(Origin),
+    /// The description of a table.
+    Relation(Relation),
+    /// The description of a data type.
+    Type(TypeInfo),
+    /// A row inserted into a table.
+    Insert(Insert),
+    /// A row updated in a table.
+    Update(Update),
+    /// A row deleted from a table.
+    Delete(Delete),
+    /// One or more tables truncated.
+    Truncate(Truncate),
+    #[doc(hidden)]
+    __NonExhaustive,
+}
+
+impl LogicalReplicationMessage {
+    /// Parses the data of an `XLogData` message.
+    pub fn parse(buf: &Bytes) -> io::Result<LogicalReplicationMessage> {
+        let mut buf = Buffer {
+            bytes: buf.clone(),
+            idx: 0,
+        };
+
+        let message = match buf.read_u8()? {
+            b'B' => LogicalReplicationMessage::Begin(Begin {
+                final_lsn: buf.read_u64::<BigEndian>()?,
+                timestamp: buf.read_i64::<BigEndian>()?,
+                xid: buf.read_u32::<BigEndian>()?,
+            }),
+            b'C' => LogicalReplicationMessage::Commit(Commit {
+                flags: buf.read_u8()?,
+                commit_lsn: buf.read_u64::<BigEndian>()?,
+                end_lsn: buf.read_u64::<BigEndian>()?,
+                timestamp: buf.read_i64::<BigEndian>()?,
+            }),
+            b'O' => LogicalReplicationMessage::Origin(Origin {
+                commit_lsn: buf.read_u64::<BigEndian>()?,
+                name: buf.read_string()?,
+            }),
+            b'R' => {
+                let id = buf.read_u32::<BigEndian>()?;
+                let namespace = buf.read_string()?;
+                let name = buf.read_string()?;
+                let replica_identity = match buf.read_u8()? {
+                    b'd' => ReplicaIdentity::Default,
+                    b'n' => ReplicaIdentity::Nothing,
+                    b'f' => ReplicaIdentity::Full,
+                    b'i' => ReplicaIdentity::Index,
+                    tag => {
+                        return Err(io::Error::new(
+                            io::ErrorKind::InvalidInput,
+                            format!("unknown replica identity `{}`", tag),
+                        ))
+                    }
+                };
+                let len = buf.read_u16::<BigEndian>()?;
+                let mut columns = Vec::with_capacity(len as usize);
+                for _ in 0..len {
+                    columns.push(RelationColumn {
+                        flags: buf.read_u8()?,
+                        name: buf.read_string()?,
+                        type_oid: buf.read_u32::<BigEndian>()?,
+                        type_modifier: buf.read_i32::<BigEndian>()?,
+                    });
+                }
+                LogicalReplicationMessage::Relation(Relation {
+                    id,
+                    namespace,
+                    name,
+                    replica_identity,
+                    columns,
+                })
+            }
+            b'Y' => LogicalReplicationMessage::Type(TypeInfo {
+                id: buf.read_u32::<BigEndian>()?,
+                namespace: buf.read_string()?,
+                name: buf.read_string()?,
+            }),
+            b'I' => {
+                let relation_id = buf.read_u32::<BigEndian>()?;
+                buf.expect(b'N')?;
+                let tuple = Tuple::parse(&mut buf)?;
+                LogicalReplicationMessage::Insert(Insert { relation_id, tuple })
+            }
+            b'U' => {
+                let relation_id = buf.read_u32::<BigEndian>()?;
+                let (old_tuple, key_tuple) = match buf.read_u8()? {
+                    b'O' => {
+                        let old = Tuple::parse(&mut buf)?;
+                        buf.expect(b'N')?;
+                        (Some(old), None)
+                    }
+                    b'K' => {
+                        let key = Tuple::parse(&mut buf)?;
+                        buf.expect(b'N')?;
+                        (None, Some(key))
+                    }
+                    b'N' => (None, None),
+                    tag => return Err(unknown_tuple_tag(tag)),
+                };
+                let new_tuple = Tuple::parse(&mut buf)?;
+                LogicalReplicationMessage::Update(Update {
+                    relation_id,
+                    old_tuple,
+                    key_tuple,
+                    new_tuple,
+                })
+            }
+            b'D' => {
+                let relation_id = buf.read_u32::<BigEndian>()?;
+                let (old_tuple, key_tuple) = match buf.read_u8()? {
+                    b'O' => (Some(Tuple::parse(&mut buf)?), None),
+                    b'K' => (None, Some(Tuple::parse(&mut buf)?)),
+                    tag => return Err(unknown_tuple_tag(tag)),
+                };
+                LogicalReplicationMessage::Delete(Delete {
+                    relation_id,
+                    old_tuple,
+                    key_tuple,
+                })
+            }
+            b'T' => {
+                let len = buf.read_u32::<BigEndian>()?;
+                let options = buf.read_u8()?;
+                let mut relation_ids = Vec::with_capacity(cmp::min(len, 1024) as usize);
+                for _ in 0..len {
+                    relation_ids.push(buf.read_u32::<BigEndian>()?);
+                }
+                LogicalReplicationMessage::Truncate(Truncate {
+                    options,
+                    relation_ids,
+                })
+            }
+            tag => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("unknown pgoutput message tag `{}`", tag),
+                ));
+            }
+        };
+
+        if !buf.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid message length",
+            ));
+        }
+
+        Ok(message)
+    }
+}
+
+/// The start of a transaction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Begin {
+    final_lsn: u64,
+    timestamp: i64,
+    xid: u32,
+}
+
+impl Begin {
+    /// The LSN of the transaction's commit record.
+    pub fn final_lsn(&self) -> u64 {
+        self.final_lsn
+    }
+
+    /// The commit timestamp of the transaction, as microseconds since midnight on 2000-01-01.
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    /// The transaction ID.
+    pub fn xid(&self) -> u32 {
+        self.xid
+    }
+}
+
+/// The end of a transaction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Commit {
+    flags: u8,
+    commit_lsn: u64,
+    end_lsn: u64,
+    timestamp: i64,
+}
+
+impl Commit {
+    /// Flags, currently unused.
+    pub fn flags(&self) -> u8 {
+        self.flags
+    }
+
+    /// The LSN of the commit record.
+    pub fn commit_lsn(&self) -> u64 {
+        self.commit_lsn
+    }
+
+    /// The LSN just past the end of the transaction.
+    ///
+    /// This is the position to report as flushed once the transaction has been processed.
+    pub fn end_lsn(&self) -> u64 {
+        self.end_lsn
+    }
+
+    /// The commit timestamp of the transaction, as microseconds since midnight on 2000-01-01.
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+}
+
+/// The origin of a transaction.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Origin {
+    commit_lsn: u64,
+    name: String,
+}
+
+impl Origin {
+    /// The LSN of the commit on the origin server.
+    pub fn commit_lsn(&self) -> u64 {
+        self.commit_lsn
+    }
+
+    /// The name of the origin.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// The replica identity setting of a table, which determines the columns sent for the old
+/// version of updated and deleted rows.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ReplicaIdentity {
+    /// The columns of the primary key.
+    Default,
+    /// No columns.
+    Nothing,
+    /// All columns.
+    Full,
+    /// The columns of a specific index.
+    Index,
+}
+
+/// The description of a table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Relation {
+    id: Oid,
+    namespace: String,
+    name: String,
+    replica_identity: ReplicaIdentity,
+    columns: Vec<RelationColumn>,
+}
+
+impl Relation {
+    /// The OID of the table.
+    pub fn id(&self) -> Oid {
+        self.id
+    }
+
+    /// The schema of the table.
+    ///
+    /// This is empty for tables in `pg_catalog`.
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    /// The name of the table.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The table's replica identity setting.
+    pub fn replica_identity(&self) -> ReplicaIdentity {
+        self.replica_identity
+    }
+
+    /// The table's columns.
+    pub fn columns(&self) -> &[RelationColumn] {
+        &self.columns
+    }
+}
+
+/// A column of a table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelationColumn {
+    flags: u8,
+    name: String,
+    type_oid: Oid,
+    type_modifier: i32,
+}
+
+impl RelationColumn {
+    /// Determines if the column is part of the table's replica identity key.
+    pub fn is_key(&self) -> bool {
+        self.flags & 1 != 0
+    }
+
+    /// The name of the column.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The OID of the column's type.
+    pub fn type_oid(&self) -> Oid {
+        self.type_oid
+    }
+
+    /// The type modifier of the column.
+    pub fn type_modifier(&self) -> i32 {
+        self.type_modifier
+    }
+}
+
+/// The description of a data type.
+///
+/// These are only sent for types which are not built in.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypeInfo {
+    id: Oid,
+    namespace: String,
+    name: String,
+}
+
+impl TypeInfo {
+    /// The OID of the type.
+    pub fn id(&self) -> Oid {
+        self.id
+    }
+
+    /// The schema of the type.
+    pub fn namespace(&self) -> &str {
+        &self.namespace
+    }
+
+    /// The name of the type.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// A row inserted into a table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Insert {
+    relation_id: Oid,
+    tuple: Tuple,
+}
+
+impl Insert {
+    /// The OID of the table.
+    pub fn relation_id(&self) -> Oid {
+        self.relation_id
+    }
+
+    /// The new row.
+    pub fn tuple(&self) -> &Tuple {
+        &self.tuple
+    }
+}
+
+/// A row updated in a table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Update {
+    relation_id: Oid,
+    old_tuple: Option<Tuple>,
+    key_tuple: Option<Tuple>,
+    new_tuple: Tuple,
+}
+
+impl Update {
+    /// The OID of the table.
+    pub fn relation_id(&self) -> Oid {
+        self.relation_id
+    }
+
+    /// The old version of the row, if the table's replica identity is `Full`.
+    pub fn old_tuple(&self) -> Option<&Tuple> {
+        self.old_tuple.as_ref()
+    }
+
+    /// The old values of the replica identity key columns, if any of them changed.
+    pub fn key_tuple(&self) -> Option<&Tuple> {
+        self.key_tuple.as_ref()
+    }
+
+    /// The new version of the row.
+    pub fn new_tuple(&self) -> &Tuple {
+        &self.new_tuple
+    }
+}
+
+/// A row deleted from a table.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Delete {
+    relation_id: Oid,
+    old_tuple: Option<Tuple>,
+    key_tuple: Option<Tuple>,
+}
+
+impl Delete {
+    /// The OID of the table.
+    pub fn relation_id(&self) -> Oid {
+        self.relation_id
+    }
+
+    /// The deleted row, if the table's replica identity is `Full`.
+    pub fn old_tuple(&self) -> Option<&Tuple> {
+        self.old_tuple.as_ref()
+    }
+
+    /// The values of the replica identity key columns of the deleted row.
+    pub fn key_tuple(&self) -> Option<&Tuple> {
+        self.key_tuple.as_ref()
+    }
+}
+
+/// One or more tables truncated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Truncate {
+    options: u8,
+    relation_ids: Vec<Oid>,
+}
+
+impl Truncate {
+    /// Determines if `CASCADE` was specified.
+    pub fn cascade(&self) -> bool {
+        self.options & 1 != 0
+    }
+
+    /// Determines if `RESTART IDENTITY` was specified.
+    pub fn restart_identity(&self) -> bool {
+        self.options & 2 != 0
+    }
+
+    /// The OIDs of the truncated tables.
+    pub fn relation_ids(&self) -> &[Oid] {
+        &self.relation_ids
+    }
+}
+
+/// The column values of a row.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tuple(Vec<TupleData>);
+
+impl Tuple {
+    fn parse(buf: &mut Buffer) -> io::Result<Tuple> {
+        let len = buf.read_u16::<BigEndian>()?;
+        let mut data = Vec::with_capacity(len as usize);
+        for _ in 0..len {
+            let value = match buf.read_u8()? {
+                b'n' => TupleData::Null,
+                b'u' => TupleData::UnchangedToast,
+                b't' => {
+                    let len = buf.read_u32::<BigEndian>()?;
+                    TupleData::Text(buf.read_bytes(len as usize)?)
+                }
+                tag => {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        format!("unknown tuple data tag `{}`", tag),
+                    ))
+                }
+            };
+            data.push(value);
+        }
+        Ok(Tuple(data))
+    }
+
+    /// The values of the row's columns, in the order of the relation's columns.
+    pub fn data(&self) -> &[TupleData] {
+        &self.0
+    }
+}
+
+/// The value of a column.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TupleData {
+    /// A `NULL` value.
+    Null,
+    /// A TOASTed value which was not changed, and so was not sent.
+    UnchangedToast,
+    /// A value in its text format.
+    Text(Bytes),
+}
+
+impl TupleData {
+    /// Returns the value as a string, if it is present and valid UTF-8.
+    pub fn as_str(&self) -> Option<&str> {
+        match *self {
+            TupleData::Text(ref bytes) => str::from_utf8(bytes).ok(),
+            _ => None,
+        }
+    }
+}
+
+fn unknown_tuple_tag(tag: u8) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidInput,
+        format!("unknown tuple tag `{}`", tag),
+    )
+}
+
+struct Buffer {
+    bytes: Bytes,
+    idx: usize,
+}
+
+impl Buffer {
+    fn slice(&self) -> &[u8] {
+        &self.bytes[self.idx..]
+    }
+
+    fn is_empty(&self) -> bool {
+        self.slice().is_empty()
+    }
+
+    fn expect(&mut self, tag: u8) -> io::Result<()> {
+        match self.read_u8()? {
+            t if t == tag => Ok(()),
+            t => Err(unknown_tuple_tag(t)),
+        }
+    }
+
+    fn read_bytes(&mut self, len: usize) -> io::Result<Bytes> {
+        if self.slice().len() < len {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "unexpected EOF",
+            ));
+        }
+        let bytes = self.bytes.slice(self.idx, self.idx + len);
+        self.idx += len;
+        Ok(bytes)
+    }
+
+    fn read_string(&mut self) -> io::Result<String> {
+        match memchr(0, self.slice()) {
+            Some(pos) => {
+                let s = str::from_utf8(&self.slice()[..pos])
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?
+                    .to_string();
+                self.idx += pos + 1;
+                Ok(s)
+            }
+            None => Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "unexpected EOF",
+            )),
+        }
+    }
+}
+
+impl Read for Buffer {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = {
+            let slice = self.slice();
+            let len = cmp::min(slice.len(), buf.len());
+            buf[..len].copy_from_slice(&slice[..len]);
+            len
+        };
+        self.idx += len;
+        Ok(len)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn begin() {
+        let mut buf = vec![b'B'];
+        buf.extend_from_slice(&[0, 0, 0, 0, 1, 2, 3, 4]);
+        buf.extend_from_slice(&[0, 0, 0, 0, 0, 0, 0, 10]);
+        buf.extend_from_slice(&[0, 0, 2, 0]);
+
+        match LogicalReplicationMessage::parse(&Bytes::from(buf)).unwrap() {
+            LogicalReplicationMessage::Begin(begin) => {
+                assert_eq!(begin.final_lsn(), 0x01020304);
+                assert_eq!(begin.timestamp(), 10);
+                assert_eq!(begin.xid(), 512);
+            }
+            m => panic!("unexpected message {:?}", m),
+        }
+    }
+
+    #[test]
+    fn relation_and_insert() {
+        let mut buf = vec![b'R', 0, 0, 64, 0];
+        buf.extend_from_slice(b"public\0foo\0d");
+        buf.extend_from_slice(&[0, 2]);
+        buf.extend_from_slice(b"\x01id\0\0\0\0\x17\xff\xff\xff\xff");
+        buf.extend_from_slice(b"\x00name\0\0\0\0\x19\xff\xff\xff\xff");
+
+        let relation = match LogicalReplicationMessage::parse(&Bytes::from(buf)).unwrap() {
+            LogicalReplicationMessage::Relation(relation) => relation,
+            m => panic!("unexpected message {:?}", m),
+        };
+        assert_eq!(relation.id(), 16384);
+        assert_eq!(relation.namespace(), "public");
+        assert_eq!(relation.name(), "foo");
+        assert_eq!(relation.replica_identity(), ReplicaIdentity::Default);
+        assert_eq!(relation.columns().len(), 2);
+        assert!(relation.columns()[0].is_key());
+        assert_eq!(relation.columns()[0].name(), "id");
+        assert_eq!(relation.columns()[0].type_oid(), 23);
+        assert_eq!(relation.columns()[0].type_modifier(), -1);
+        assert!(!relation.columns()[1].is_key());
+
+        let mut buf = vec![b'I', 0, 0, 64, 0, b'N', 0, 3];
+        buf.extend_from_slice(b"t\0\0\0\x011");
+        buf.extend_from_slice(b"n");
+        buf.extend_from_slice(b"u");
+
+        let insert = match LogicalReplicationMessage::parse(&Bytes::from(buf)).unwrap() {
+            LogicalReplicationMessage::Insert(insert) => insert,
+            m => panic!("unexpected message {:?}", m),
+        };
+        assert_eq!(insert.relation_id(), 16384);
+        assert_eq!(insert.tuple().data()[0].as_str(), Some("1"));
+        assert_eq!(insert.tuple().data()[1], TupleData::Null);
+        assert_eq!(insert.tuple().data()[2], TupleData::UnchangedToast);
+    }
+
+    #[test]
+    fn update_with_key() {
+        let mut buf = vec![b'U', 0, 0, 64, 0, b'K', 0, 1];
+        buf.extend_from_slice(b"t\0\0\0\x011");
+        buf.extend_from_slice(&[b'N', 0, 1]);
+        buf.extend_from_slice(b"t\0\0\0\x012");
+
+        match LogicalReplicationMessage::parse(&Bytes::from(buf)).unwrap() {
+            LogicalReplicationMessage::Update(update) => {
+                assert!(update.old_tuple().is_none());
+                assert_eq!(update.key_tuple().unwrap().data()[0].as_str(), Some("1"));
+                assert_eq!(update.new_tuple().data()[0].as_str(), Some("2"));
+            }
+            m => panic!("unexpected message {:?}", m),
+        }
+    }
+
+    #[test]
+    fn trailing_data() {
+        let buf = vec![b'Y', 0, 0, 0, 1, b'a', 0, b'b', 0, 0];
+        assert!(LogicalReplicationMessage::parse(&Bytes::from(buf)).is_err());
+    }
+}
@@ -79,3 +79,4 @@ macro_rules! from_usize {
 
 from_usize!(i16);
 from_usize!(i32);
+from_usize!(u16);
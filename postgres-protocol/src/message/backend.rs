@@ -28,6 +28,7 @@ pub enum Message {
     BindComplete,
     CloseComplete,
     CommandComplete(CommandCompleteBody),
+    CopyBothResponse(CopyBothResponseBody),
     CopyData(CopyDataBody),
     CopyDone,
     CopyInResponse(CopyInResponseBody),
@@ -134,6 +135,16 @@ impl Message {
                     storage: storage,
                 })
             }
+            b'W' => {
+                let format = buf.read_u8()?;
+                let len = buf.read_u16::<BigEndian>()?;
+                let storage = buf.read_all();
+                Message::CopyBothResponse(CopyBothResponseBody {
+                    format: format,
+                    len: len,
+                    storage: storage,
+                })
+            }
             b'I' => Message::EmptyQueryResponse,
             b'K' => {
                 let process_id = buf.read_i32::<BigEndian>()?;
@@ -231,6 +242,131 @@ impl Message {
     }
 }
 
+/// An enum representing the messages carried in `CopyData` during streaming replication.
+pub enum ReplicationMessage {
+    XLogData(XLogDataBody),
+    PrimaryKeepAlive(PrimaryKeepAliveBody),
+    #[doc(hidden)]
+    __ForExtensibility,
+}
+
+impl ReplicationMessage {
+    #[inline]
+    pub fn parse(buf: &Bytes) -> io::Result<ReplicationMessage> {
+        let mut buf = Buffer {
+            bytes: buf.clone(),
+            idx: 0,
+        };
+
+        let message = match buf.read_u8()? {
+            b'w' => {
+                let wal_start = buf.read_u64::<BigEndian>()?;
+                let wal_end = buf.read_u64::<BigEndian>()?;
+                let timestamp = buf.read_i64::<BigEndian>()?;
+                let data = buf.read_all();
+                ReplicationMessage::XLogData(XLogDataBody {
+                    wal_start: wal_start,
+                    wal_end: wal_end,
+                    timestamp: timestamp,
+                    data: data,
+                })
+            }
+            b'k' => {
+                let wal_end = buf.read_u64::<BigEndian>()?;
+                let timestamp = buf.read_i64::<BigEndian>()?;
+                let reply = buf.read_u8()?;
+                ReplicationMessage::PrimaryKeepAlive(PrimaryKeepAliveBody {
+                    wal_end: wal_end,
+                    timestamp: timestamp,
+                    reply: reply,
+                })
+            }
+            tag => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!("unknown replication message tag `{}`", tag),
+                ));
+            }
+        };
+
+        if !buf.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid message length",
+            ));
+        }
+
+        Ok(message)
+    }
+}
+
+pub struct XLogDataBody {
+    wal_start: u64,
+    wal_end: u64,
+    timestamp: i64,
+    data: Bytes,
+}
+
+impl XLogDataBody {
+    /// The starting point of the WAL data in this message.
+    #[inline]
+    pub fn wal_start(&self) -> u64 {
+        self.wal_start
+    }
+
+    /// The current end of WAL on the server.
+    #[inline]
+    pub fn wal_end(&self) -> u64 {
+        self.wal_end
+    }
+
+    /// The server's system clock at the time of transmission, as microseconds since midnight on
+    /// 2000-01-01.
+    #[inline]
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    /// The WAL data.
+    #[inline]
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Consumes the body, returning the WAL data.
+    #[inline]
+    pub fn into_bytes(self) -> Bytes {
+        self.data
+    }
+}
+
+pub struct PrimaryKeepAliveBody {
+    wal_end: u64,
+    timestamp: i64,
+    reply: u8,
+}
+
+impl PrimaryKeepAliveBody {
+    /// The current end of WAL on the server.
+    #[inline]
+    pub fn wal_end(&self) -> u64 {
+        self.wal_end
+    }
+
+    /// The server's system clock at the time of transmission, as microseconds since midnight on
+    /// 2000-01-01.
+    #[inline]
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    /// Whether the server has requested an immediate standby status update.
+    #[inline]
+    pub fn reply(&self) -> bool {
+        self.reply == 1
+    }
+}
+
 struct Buffer {
     bytes: Bytes,
     idx: usize,
@@ -381,6 +517,27 @@ impl CommandCompleteBody {
     }
 }
 
+pub struct CopyBothResponseBody {
+    storage: Bytes,
+    len: u16,
+    format: u8,
+}
+
+impl CopyBothResponseBody {
+    #[inline]
+    pub fn format(&self) -> u8 {
+        self.format
+    }
+
+    #[inline]
+    pub fn column_formats<'a>(&'a self) -> ColumnFormats<'a> {
+        ColumnFormats {
+            remaining: self.len,
+            buf: &self.storage,
+        }
+    }
+}
+
 pub struct CopyDataBody {
     storage: Bytes,
 }
@@ -316,6 +316,31 @@ where
     })
 }
 
+/// Writes a standby status update, wrapped in a `CopyData` message, for use during streaming
+/// replication.
+///
+/// The timestamp is in microseconds since midnight on 2000-01-01.
+#[inline]
+pub fn standby_status_update(
+    write_lsn: u64,
+    flush_lsn: u64,
+    apply_lsn: u64,
+    timestamp: i64,
+    reply: bool,
+    buf: &mut Vec<u8>,
+) {
+    buf.push(b'd');
+    write_body(buf, |buf| {
+        buf.push(b'r');
+        buf.write_u64::<BigEndian>(write_lsn)?;
+        buf.write_u64::<BigEndian>(flush_lsn)?;
+        buf.write_u64::<BigEndian>(apply_lsn)?;
+        buf.write_i64::<BigEndian>(timestamp)?;
+        buf.push(reply as u8);
+        Ok::<(), io::Error>(())
+    }).unwrap();
+}
+
 #[inline]
 pub fn sync(buf: &mut Vec<u8>) {
     buf.push(b'S');
@@ -364,7 +364,7 @@ pub fn date_from_sql(mut buf: &[u8]) -> Result<i32, StdBox<Error + Sync + Send>>
     Ok(v)
 }
 
-/// Serializes a `TIME` or `TIMETZ` value.
+/// Serializes a `TIME` value.
 ///
 /// The value should represent the number of microseconds since midnight.
 #[inline]
@@ -372,7 +372,7 @@ pub fn time_to_sql(v: i64, buf: &mut Vec<u8>) {
     buf.write_i64::<BigEndian>(v).unwrap();
 }
 
-/// Deserializes a `TIME` or `TIMETZ` value.
+/// Deserializes a `TIME` value.
 ///
 /// The value represents the number of microseconds since midnight.
 #[inline]
@@ -384,6 +384,248 @@ pub fn time_from_sql(mut buf: &[u8]) -> Result<i64, StdBox<Error + Sync + Send>>
     Ok(v)
 }
 
+/// Serializes a `TIMETZ` value.
+///
+/// The time should represent the number of microseconds since midnight, and the offset the
+/// number of seconds the time zone is *west* of UTC, as Postgres stores it.
+#[inline]
+pub fn timetz_to_sql(time: i64, offset: i32, buf: &mut Vec<u8>) {
+    buf.write_i64::<BigEndian>(time).unwrap();
+    buf.write_i32::<BigEndian>(offset).unwrap();
+}
+
+/// Deserializes a `TIMETZ` value.
+#[inline]
+pub fn timetz_from_sql(mut buf: &[u8]) -> Result<TimeTz, StdBox<Error + Sync + Send>> {
+    let time = buf.read_i64::<BigEndian>()?;
+    let offset = buf.read_i32::<BigEndian>()?;
+    if !buf.is_empty() {
+        return Err("invalid message length".into());
+    }
+    Ok(TimeTz {
+        time: time,
+        offset: offset,
+    })
+}
+
+/// A `TIMETZ` value.
+#[derive(Copy, Clone)]
+pub struct TimeTz {
+    time: i64,
+    offset: i32,
+}
+
+impl TimeTz {
+    /// Returns the number of microseconds since midnight.
+    #[inline]
+    pub fn time(&self) -> i64 {
+        self.time
+    }
+
+    /// Returns the number of seconds the time zone is *west* of UTC.
+    #[inline]
+    pub fn offset(&self) -> i32 {
+        self.offset
+    }
+}
+
+/// Serializes an `INTERVAL` value.
+#[inline]
+pub fn interval_to_sql(microseconds: i64, days: i32, months: i32, buf: &mut Vec<u8>) {
+    buf.write_i64::<BigEndian>(microseconds).unwrap();
+    buf.write_i32::<BigEndian>(days).unwrap();
+    buf.write_i32::<BigEndian>(months).unwrap();
+}
+
+/// Deserializes an `INTERVAL` value.
+#[inline]
+pub fn interval_from_sql(mut buf: &[u8]) -> Result<Interval, StdBox<Error + Sync + Send>> {
+    let microseconds = buf.read_i64::<BigEndian>()?;
+    let days = buf.read_i32::<BigEndian>()?;
+    let months = buf.read_i32::<BigEndian>()?;
+    if !buf.is_empty() {
+        return Err("invalid message length".into());
+    }
+    Ok(Interval {
+        microseconds: microseconds,
+        days: days,
+        months: months,
+    })
+}
+
+/// An `INTERVAL` value.
+///
+/// Postgres keeps the months, days and smaller units of an interval separate, since the length of
+/// a month or day in seconds varies.
+#[derive(Copy, Clone)]
+pub struct Interval {
+    microseconds: i64,
+    days: i32,
+    months: i32,
+}
+
+impl Interval {
+    /// Returns the microseconds component of the interval.
+    #[inline]
+    pub fn microseconds(&self) -> i64 {
+        self.microseconds
+    }
+
+    /// Returns the days component of the interval.
+    #[inline]
+    pub fn days(&self) -> i32 {
+        self.days
+    }
+
+    /// Returns the months component of the interval.
+    #[inline]
+    pub fn months(&self) -> i32 {
+        self.months
+    }
+}
+
+const NUMERIC_POS: u16 = 0x0000;
+const NUMERIC_NEG: u16 = 0x4000;
+const NUMERIC_NAN: u16 = 0xc000;
+const NUMERIC_PINF: u16 = 0xd000;
+const NUMERIC_NINF: u16 = 0xf000;
+
+/// The sign of a `NUMERIC` value, or the special value it represents.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum NumericSign {
+    /// A positive value or zero.
+    Positive,
+    /// A negative value.
+    Negative,
+    /// `NaN`.
+    NaN,
+    /// `Infinity`, supported by Postgres 14 and newer.
+    PositiveInfinity,
+    /// `-Infinity`, supported by Postgres 14 and newer.
+    NegativeInfinity,
+}
+
+/// Serializes a `NUMERIC` value.
+///
+/// The value is made up of base 10000 digits, the first of which is multiplied by
+/// `10000 ^ weight`. `scale` is the number of decimal digits displayed after the decimal point.
+/// Special values should have no digits.
+#[inline]
+pub fn numeric_to_sql(
+    sign: NumericSign,
+    weight: i16,
+    scale: u16,
+    digits: &[i16],
+    buf: &mut Vec<u8>,
+) -> Result<(), StdBox<Error + Sync + Send>> {
+    let sign = match sign {
+        NumericSign::Positive => NUMERIC_POS,
+        NumericSign::Negative => NUMERIC_NEG,
+        NumericSign::NaN => NUMERIC_NAN,
+        NumericSign::PositiveInfinity => NUMERIC_PINF,
+        NumericSign::NegativeInfinity => NUMERIC_NINF,
+    };
+
+    let ndigits = i16::from_usize(digits.len())?;
+    buf.write_i16::<BigEndian>(ndigits).unwrap();
+    buf.write_i16::<BigEndian>(weight).unwrap();
+    buf.write_u16::<BigEndian>(sign).unwrap();
+    buf.write_u16::<BigEndian>(scale).unwrap();
+    for &digit in digits {
+        buf.write_i16::<BigEndian>(digit).unwrap();
+    }
+    Ok(())
+}
+
+/// Deserializes a `NUMERIC` value.
+#[inline]
+pub fn numeric_from_sql<'a>(mut buf: &'a [u8]) -> Result<Numeric<'a>, StdBox<Error + Sync + Send>> {
+    let ndigits = buf.read_i16::<BigEndian>()?;
+    let weight = buf.read_i16::<BigEndian>()?;
+    let sign = match buf.read_u16::<BigEndian>()? {
+        NUMERIC_POS => NumericSign::Positive,
+        NUMERIC_NEG => NumericSign::Negative,
+        NUMERIC_NAN => NumericSign::NaN,
+        NUMERIC_PINF => NumericSign::PositiveInfinity,
+        NUMERIC_NINF => NumericSign::NegativeInfinity,
+        _ => return Err("invalid numeric sign".into()),
+    };
+    let scale = buf.read_u16::<BigEndian>()?;
+
+    if ndigits < 0 {
+        return Err("invalid digit count".into());
+    }
+    if buf.len() != ndigits as usize * 2 {
+        return Err("invalid message length".into());
+    }
+
+    Ok(Numeric {
+        sign: sign,
+        weight: weight,
+        scale: scale,
+        digits: buf,
+    })
+}
+
+/// A `NUMERIC` value.
+pub struct Numeric<'a> {
+    sign: NumericSign,
+    weight: i16,
+    scale: u16,
+    digits: &'a [u8],
+}
+
+impl<'a> Numeric<'a> {
+    /// Returns the sign of the value.
+    #[inline]
+    pub fn sign(&self) -> NumericSign {
+        self.sign
+    }
+
+    /// Returns the power of 10000 the first digit is multiplied by.
+    #[inline]
+    pub fn weight(&self) -> i16 {
+        self.weight
+    }
+
+    /// Returns the number of decimal digits displayed after the decimal point.
+    #[inline]
+    pub fn scale(&self) -> u16 {
+        self.scale
+    }
+
+    /// Returns an iterator over the base 10000 digits of the value, most significant first.
+    #[inline]
+    pub fn digits(&self) -> NumericDigits<'a> {
+        NumericDigits(self.digits)
+    }
+}
+
+/// An iterator over the digits of a `NUMERIC` value.
+pub struct NumericDigits<'a>(&'a [u8]);
+
+impl<'a> Iterator for NumericDigits<'a> {
+    type Item = i16;
+
+    #[inline]
+    fn next(&mut self) -> Option<i16> {
+        if self.0.is_empty() {
+            return None;
+        }
+        let digit = BigEndian::read_i16(self.0);
+        self.0 = &self.0[2..];
+        Some(digit)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.len() / 2;
+        (len, Some(len))
+    }
+}
+
+impl<'a> ExactSizeIterator for NumericDigits<'a> {}
+
 /// Serializes a `MACADDR` value.
 #[inline]
 pub fn macaddr_to_sql(v: [u8; 6], buf: &mut Vec<u8>) {
@@ -943,6 +1185,521 @@ impl<'a> FallibleIterator for PathPoints<'a> {
     }
 }
 
+/// Serializes a Postgres line.
+///
+/// The line is represented by the coefficients of its equation, `ax + by + c = 0`.
+#[inline]
+pub fn line_to_sql(a: f64, b: f64, c: f64, buf: &mut Vec<u8>) {
+    buf.write_f64::<BigEndian>(a).unwrap();
+    buf.write_f64::<BigEndian>(b).unwrap();
+    buf.write_f64::<BigEndian>(c).unwrap();
+}
+
+/// Deserializes a Postgres line.
+#[inline]
+pub fn line_from_sql(mut buf: &[u8]) -> Result<Line, StdBox<Error + Sync + Send>> {
+    let a = buf.read_f64::<BigEndian>()?;
+    let b = buf.read_f64::<BigEndian>()?;
+    let c = buf.read_f64::<BigEndian>()?;
+    if !buf.is_empty() {
+        return Err("invalid buffer size".into());
+    }
+    Ok(Line { a: a, b: b, c: c })
+}
+
+/// A Postgres line.
+#[derive(Copy, Clone)]
+pub struct Line {
+    a: f64,
+    b: f64,
+    c: f64,
+}
+
+impl Line {
+    /// Returns the `a` coefficient of the line's equation, `ax + by + c = 0`.
+    #[inline]
+    pub fn a(&self) -> f64 {
+        self.a
+    }
+
+    /// Returns the `b` coefficient of the line's equation, `ax + by + c = 0`.
+    #[inline]
+    pub fn b(&self) -> f64 {
+        self.b
+    }
+
+    /// Returns the `c` coefficient of the line's equation, `ax + by + c = 0`.
+    #[inline]
+    pub fn c(&self) -> f64 {
+        self.c
+    }
+}
+
+/// Serializes a Postgres line segment.
+#[inline]
+pub fn lseg_to_sql(x1: f64, y1: f64, x2: f64, y2: f64, buf: &mut Vec<u8>) {
+    buf.write_f64::<BigEndian>(x1).unwrap();
+    buf.write_f64::<BigEndian>(y1).unwrap();
+    buf.write_f64::<BigEndian>(x2).unwrap();
+    buf.write_f64::<BigEndian>(y2).unwrap();
+}
+
+/// Deserializes a Postgres line segment.
+#[inline]
+pub fn lseg_from_sql(mut buf: &[u8]) -> Result<LineSegment, StdBox<Error + Sync + Send>> {
+    let x1 = buf.read_f64::<BigEndian>()?;
+    let y1 = buf.read_f64::<BigEndian>()?;
+    let x2 = buf.read_f64::<BigEndian>()?;
+    let y2 = buf.read_f64::<BigEndian>()?;
+    if !buf.is_empty() {
+        return Err("invalid buffer size".into());
+    }
+    Ok(LineSegment {
+        start: Point { x: x1, y: y1 },
+        end: Point { x: x2, y: y2 },
+    })
+}
+
+/// A Postgres line segment.
+#[derive(Copy, Clone)]
+pub struct LineSegment {
+    start: Point,
+    end: Point,
+}
+
+impl LineSegment {
+    /// Returns the first end point of the segment.
+    #[inline]
+    pub fn start(&self) -> Point {
+        self.start
+    }
+
+    /// Returns the second end point of the segment.
+    #[inline]
+    pub fn end(&self) -> Point {
+        self.end
+    }
+}
+
+/// Serializes a Postgres polygon.
+#[inline]
+pub fn polygon_to_sql<I>(points: I, buf: &mut Vec<u8>) -> Result<(), StdBox<Error + Sync + Send>>
+where
+    I: IntoIterator<Item = (f64, f64)>,
+{
+    let points_idx = buf.len();
+    buf.extend_from_slice(&[0; 4]);
+
+    let mut num_points = 0;
+    for (x, y) in points {
+        num_points += 1;
+        buf.write_f64::<BigEndian>(x).unwrap();
+        buf.write_f64::<BigEndian>(y).unwrap();
+    }
+
+    let num_points = i32::from_usize(num_points)?;
+    BigEndian::write_i32(&mut buf[points_idx..], num_points);
+
+    Ok(())
+}
+
+/// Deserializes a Postgres polygon.
+#[inline]
+pub fn polygon_from_sql<'a>(mut buf: &'a [u8]) -> Result<Polygon<'a>, StdBox<Error + Sync + Send>> {
+    let points = buf.read_i32::<BigEndian>()?;
+    if points < 0 {
+        return Err("invalid point count".into());
+    }
+
+    Ok(Polygon {
+        points: points,
+        buf: buf,
+    })
+}
+
+/// A Postgres polygon.
+pub struct Polygon<'a> {
+    points: i32,
+    buf: &'a [u8],
+}
+
+impl<'a> Polygon<'a> {
+    /// Returns an iterator over the vertices of the polygon.
+    ///
+    /// Polygons are implicitly closed; the first vertex is not repeated at the end.
+    #[inline]
+    pub fn points(&self) -> PathPoints<'a> {
+        PathPoints {
+            remaining: self.points,
+            buf: self.buf,
+        }
+    }
+}
+
+/// Serializes a Postgres circle.
+#[inline]
+pub fn circle_to_sql(x: f64, y: f64, radius: f64, buf: &mut Vec<u8>) {
+    buf.write_f64::<BigEndian>(x).unwrap();
+    buf.write_f64::<BigEndian>(y).unwrap();
+    buf.write_f64::<BigEndian>(radius).unwrap();
+}
+
+/// Deserializes a Postgres circle.
+#[inline]
+pub fn circle_from_sql(mut buf: &[u8]) -> Result<Circle, StdBox<Error + Sync + Send>> {
+    let x = buf.read_f64::<BigEndian>()?;
+    let y = buf.read_f64::<BigEndian>()?;
+    let radius = buf.read_f64::<BigEndian>()?;
+    if !buf.is_empty() {
+        return Err("invalid buffer size".into());
+    }
+    Ok(Circle {
+        center: Point { x: x, y: y },
+        radius: radius,
+    })
+}
+
+/// A Postgres circle.
+#[derive(Copy, Clone)]
+pub struct Circle {
+    center: Point,
+    radius: f64,
+}
+
+impl Circle {
+    /// Returns the center of the circle.
+    #[inline]
+    pub fn center(&self) -> Point {
+        self.center
+    }
+
+    /// Returns the radius of the circle.
+    #[inline]
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+}
+
+/// Serializes a `TSVECTOR` value.
+///
+/// Each lexeme is accompanied by its positions. A position is stored in the low 14 bits and its
+/// weight in the upper 2 bits (3 for `A` down to 0 for `D`). The positions of a lexeme must be
+/// strictly increasing.
+#[inline]
+pub fn tsvector_to_sql<'a, I, J>(
+    lexemes: I,
+    buf: &mut Vec<u8>,
+) -> Result<(), StdBox<Error + Sync + Send>>
+where
+    I: IntoIterator<Item = (&'a str, J)>,
+    J: IntoIterator<Item = u16>,
+{
+    let base = buf.len();
+    buf.extend_from_slice(&[0; 4]);
+
+    let mut count = 0;
+    for (lexeme, positions) in lexemes {
+        count += 1;
+
+        write_cstr(lexeme, buf)?;
+
+        let positions_idx = buf.len();
+        buf.extend_from_slice(&[0; 2]);
+        let mut num_positions = 0;
+        for position in positions {
+            num_positions += 1;
+            buf.write_u16::<BigEndian>(position).unwrap();
+        }
+        let num_positions = u16::from_usize(num_positions)?;
+        BigEndian::write_u16(&mut buf[positions_idx..], num_positions);
+    }
+
+    let count = i32::from_usize(count)?;
+    BigEndian::write_i32(&mut buf[base..], count);
+
+    Ok(())
+}
+
+fn write_cstr(s: &str, buf: &mut Vec<u8>) -> Result<(), StdBox<Error + Sync + Send>> {
+    if s.as_bytes().contains(&0) {
+        return Err("string contains embedded null".into());
+    }
+    buf.extend_from_slice(s.as_bytes());
+    buf.push(0);
+    Ok(())
+}
+
+fn read_cstr<'a>(buf: &mut &'a [u8]) -> Result<&'a str, StdBox<Error + Sync + Send>> {
+    let end = match buf.iter().position(|&b| b == 0) {
+        Some(end) => end,
+        None => return Err("unexpected EOF".into()),
+    };
+    let s = str::from_utf8(&buf[..end])?;
+    *buf = &buf[end + 1..];
+    Ok(s)
+}
+
+/// Deserializes a `TSVECTOR` value.
+#[inline]
+pub fn tsvector_from_sql<'a>(
+    mut buf: &'a [u8],
+) -> Result<TsVectorEntries<'a>, StdBox<Error + Sync + Send>> {
+    let count = buf.read_i32::<BigEndian>()?;
+    if count < 0 {
+        return Err("invalid lexeme count".into());
+    }
+
+    Ok(TsVectorEntries {
+        remaining: count,
+        buf: buf,
+    })
+}
+
+/// A fallible iterator over the lexemes of a `TSVECTOR`.
+pub struct TsVectorEntries<'a> {
+    remaining: i32,
+    buf: &'a [u8],
+}
+
+impl<'a> FallibleIterator for TsVectorEntries<'a> {
+    type Item = (&'a str, TsVectorPositions<'a>);
+    type Error = StdBox<Error + Sync + Send>;
+
+    #[inline]
+    fn next(
+        &mut self,
+    ) -> Result<Option<(&'a str, TsVectorPositions<'a>)>, StdBox<Error + Sync + Send>> {
+        if self.remaining == 0 {
+            if !self.buf.is_empty() {
+                return Err("invalid buffer size".into());
+            }
+            return Ok(None);
+        }
+        self.remaining -= 1;
+
+        let lexeme = read_cstr(&mut self.buf)?;
+        let len = self.buf.read_u16::<BigEndian>()? as usize * 2;
+        if self.buf.len() < len {
+            return Err("invalid buffer size".into());
+        }
+        let (positions, buf) = self.buf.split_at(len);
+        self.buf = buf;
+
+        Ok(Some((lexeme, TsVectorPositions(positions))))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.remaining as usize;
+        (len, Some(len))
+    }
+}
+
+/// An iterator over the positions of a `TSVECTOR` lexeme.
+///
+/// Positions are encoded as they are by `tsvector_to_sql`.
+pub struct TsVectorPositions<'a>(&'a [u8]);
+
+impl<'a> Iterator for TsVectorPositions<'a> {
+    type Item = u16;
+
+    #[inline]
+    fn next(&mut self) -> Option<u16> {
+        if self.0.is_empty() {
+            return None;
+        }
+        let position = BigEndian::read_u16(self.0);
+        self.0 = &self.0[2..];
+        Some(position)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.0.len() / 2;
+        (len, Some(len))
+    }
+}
+
+impl<'a> ExactSizeIterator for TsVectorPositions<'a> {}
+
+const TSQUERY_VALUE: u8 = 1;
+const TSQUERY_OPERATOR: u8 = 2;
+
+const TSQUERY_NOT: u8 = 1;
+const TSQUERY_AND: u8 = 2;
+const TSQUERY_OR: u8 = 3;
+const TSQUERY_PHRASE: u8 = 4;
+
+/// An item of a `TSQUERY` value.
+///
+/// Items are stored in prefix order, with the operands of binary operators in reverse: an
+/// operator is followed by its right operand and then its left operand.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TsQueryItem<'a> {
+    /// A lexeme to match.
+    Operand {
+        /// A bitmask of the weights the lexeme matches, from `1 << 3` for `A` down to `1` for
+        /// `D`. A mask of 0 matches any weight.
+        weights: u8,
+        /// If set, the lexeme matches any lexeme it is a prefix of.
+        prefix: bool,
+        /// The lexeme.
+        value: &'a str,
+    },
+    /// The `!` operator.
+    Not,
+    /// The `&` operator.
+    And,
+    /// The `|` operator.
+    Or,
+    /// The `<N>` operator, with its distance.
+    Phrase(u16),
+}
+
+/// Serializes a `TSQUERY` value.
+#[inline]
+pub fn tsquery_to_sql<'a, I>(items: I, buf: &mut Vec<u8>) -> Result<(), StdBox<Error + Sync + Send>>
+where
+    I: IntoIterator<Item = TsQueryItem<'a>>,
+{
+    let base = buf.len();
+    buf.extend_from_slice(&[0; 4]);
+
+    let mut count = 0;
+    for item in items {
+        count += 1;
+
+        match item {
+            TsQueryItem::Operand {
+                weights,
+                prefix,
+                value,
+            } => {
+                buf.push(TSQUERY_VALUE);
+                buf.push(weights);
+                buf.push(prefix as u8);
+                write_cstr(value, buf)?;
+            }
+            TsQueryItem::Not => buf.extend_from_slice(&[TSQUERY_OPERATOR, TSQUERY_NOT]),
+            TsQueryItem::And => buf.extend_from_slice(&[TSQUERY_OPERATOR, TSQUERY_AND]),
+            TsQueryItem::Or => buf.extend_from_slice(&[TSQUERY_OPERATOR, TSQUERY_OR]),
+            TsQueryItem::Phrase(distance) => {
+                buf.extend_from_slice(&[TSQUERY_OPERATOR, TSQUERY_PHRASE]);
+                buf.write_u16::<BigEndian>(distance).unwrap();
+            }
+        }
+    }
+
+    let count = i32::from_usize(count)?;
+    BigEndian::write_i32(&mut buf[base..], count);
+
+    Ok(())
+}
+
+/// Deserializes a `TSQUERY` value.
+#[inline]
+pub fn tsquery_from_sql<'a>(
+    mut buf: &'a [u8],
+) -> Result<TsQueryItems<'a>, StdBox<Error + Sync + Send>> {
+    let count = buf.read_i32::<BigEndian>()?;
+    if count < 0 {
+        return Err("invalid item count".into());
+    }
+
+    Ok(TsQueryItems {
+        remaining: count,
+        buf: buf,
+    })
+}
+
+/// A fallible iterator over the items of a `TSQUERY`.
+pub struct TsQueryItems<'a> {
+    remaining: i32,
+    buf: &'a [u8],
+}
+
+impl<'a> FallibleIterator for TsQueryItems<'a> {
+    type Item = TsQueryItem<'a>;
+    type Error = StdBox<Error + Sync + Send>;
+
+    #[inline]
+    fn next(&mut self) -> Result<Option<TsQueryItem<'a>>, StdBox<Error + Sync + Send>> {
+        if self.remaining == 0 {
+            if !self.buf.is_empty() {
+                return Err("invalid buffer size".into());
+            }
+            return Ok(None);
+        }
+        self.remaining -= 1;
+
+        let item = match self.buf.read_u8()? {
+            TSQUERY_VALUE => {
+                let weights = self.buf.read_u8()?;
+                let prefix = self.buf.read_u8()? != 0;
+                let value = read_cstr(&mut self.buf)?;
+                TsQueryItem::Operand {
+                    weights: weights,
+                    prefix: prefix,
+                    value: value,
+                }
+            }
+            TSQUERY_OPERATOR => match self.buf.read_u8()? {
+                TSQUERY_NOT => TsQueryItem::Not,
+                TSQUERY_AND => TsQueryItem::And,
+                TSQUERY_OR => TsQueryItem::Or,
+                TSQUERY_PHRASE => TsQueryItem::Phrase(self.buf.read_u16::<BigEndian>()?),
+                _ => return Err("invalid tsquery operator".into()),
+            },
+            _ => return Err("invalid tsquery item type".into()),
+        };
+
+        Ok(Some(item))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.remaining as usize;
+        (len, Some(len))
+    }
+}
+
+const JSONPATH_VERSION: u8 = 1;
+const LTREE_VERSION: u8 = 1;
+
+/// Serializes a `JSONPATH` value.
+#[inline]
+pub fn jsonpath_to_sql(v: &str, buf: &mut Vec<u8>) {
+    buf.push(JSONPATH_VERSION);
+    buf.extend_from_slice(v.as_bytes());
+}
+
+/// Deserializes a `JSONPATH` value.
+#[inline]
+pub fn jsonpath_from_sql(buf: &[u8]) -> Result<&str, StdBox<Error + Sync + Send>> {
+    versioned_text_from_sql(JSONPATH_VERSION, buf)
+}
+
+/// Serializes an `LTREE`, `LQUERY` or `LTXTQUERY` value.
+#[inline]
+pub fn ltree_to_sql(v: &str, buf: &mut Vec<u8>) {
+    buf.push(LTREE_VERSION);
+    buf.extend_from_slice(v.as_bytes());
+}
+
+/// Deserializes an `LTREE`, `LQUERY` or `LTXTQUERY` value.
+#[inline]
+pub fn ltree_from_sql(buf: &[u8]) -> Result<&str, StdBox<Error + Sync + Send>> {
+    versioned_text_from_sql(LTREE_VERSION, buf)
+}
+
+fn versioned_text_from_sql(version: u8, buf: &[u8]) -> Result<&str, StdBox<Error + Sync + Send>> {
+    match buf.split_first() {
+        Some((&v, rest)) if v == version => Ok(str::from_utf8(rest)?),
+        Some(_) => Err("unsupported format version".into()),
+        None => Err("unexpected EOF".into()),
+    }
+}
+
 #[cfg(test)]
 mod test {
     use fallible_iterator::FallibleIterator;
@@ -1097,4 +1854,88 @@ mod test {
         assert_eq!(array.dimensions().collect::<Vec<_>>().unwrap(), dimensions);
         assert_eq!(array.values().collect::<Vec<_>>().unwrap(), values);
     }
+
+    #[test]
+    fn tsvector() {
+        let lexemes = [("fat", vec![(3 << 14) | 2, 11]), ("rat", vec![])];
+
+        let mut buf = vec![];
+        tsvector_to_sql(
+            lexemes.iter().map(|&(l, ref p)| (l, p.iter().cloned())),
+            &mut buf,
+        ).unwrap();
+        let out = tsvector_from_sql(&buf)
+            .unwrap()
+            .map(|(l, p)| (l, p.collect::<Vec<_>>()))
+            .collect::<Vec<_>>()
+            .unwrap();
+        assert_eq!(out, lexemes);
+
+        let mut buf = vec![];
+        assert!(tsvector_to_sql(Some(("a\0b", vec![])), &mut buf).is_err());
+    }
+
+    #[test]
+    fn tsquery() {
+        // 'fat':AB & !'rat':*
+        let items = [
+            TsQueryItem::And,
+            TsQueryItem::Not,
+            TsQueryItem::Operand {
+                weights: 0,
+                prefix: true,
+                value: "rat",
+            },
+            TsQueryItem::Operand {
+                weights: 0b1100,
+                prefix: false,
+                value: "fat",
+            },
+        ];
+
+        let mut buf = vec![];
+        tsquery_to_sql(items.iter().cloned(), &mut buf).unwrap();
+        assert_eq!(
+            tsquery_from_sql(&buf).unwrap().collect::<Vec<_>>().unwrap(),
+            items
+        );
+    }
+
+    #[test]
+    fn versioned_text() {
+        let mut buf = vec![];
+        jsonpath_to_sql("$.a", &mut buf);
+        assert_eq!(buf, b"\x01$.a");
+        assert_eq!(jsonpath_from_sql(&buf).unwrap(), "$.a");
+        assert!(ltree_from_sql(b"\x02a.b").is_err());
+    }
+
+    #[test]
+    fn polygon() {
+        let points = [(0., 0.), (1., 0.), (0., 1.)];
+
+        let mut buf = vec![];
+        polygon_to_sql(points.iter().cloned(), &mut buf).unwrap();
+        let out = polygon_from_sql(&buf)
+            .unwrap()
+            .points()
+            .map(|p| (p.x(), p.y()))
+            .collect::<Vec<_>>()
+            .unwrap();
+        assert_eq!(out, points);
+    }
+
+    #[test]
+    fn numeric() {
+        // -12345678.9
+        let mut buf = vec![];
+        numeric_to_sql(NumericSign::Negative, 1, 1, &[1234, 5678, 9000], &mut buf).unwrap();
+        let numeric = numeric_from_sql(&buf).unwrap();
+        assert_eq!(numeric.sign(), NumericSign::Negative);
+        assert_eq!(numeric.weight(), 1);
+        assert_eq!(numeric.scale(), 1);
+        assert_eq!(numeric.digits().collect::<Vec<_>>(), [1234, 5678, 9000]);
+
+        assert!(numeric_from_sql(&buf[..buf.len() - 1]).is_err());
+    }
 }
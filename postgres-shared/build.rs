@@ -0,0 +1,10 @@
+extern crate autocfg;
+
+fn main() {
+    let ac = autocfg::new();
+    // SystemTime::checked_add and checked_sub were added in Rust 1.34
+    ac.emit_expression_cfg(
+        "std::time::UNIX_EPOCH.checked_add(std::time::Duration::from_secs(0))",
+        "has_system_time_checked",
+    );
+}
@@ -0,0 +1,65 @@
+//! Types shared by the advisory lock APIs of both clients.
+
+/// The key identifying an advisory lock.
+///
+/// Postgres treats the single 64 bit and the pair of 32 bit key spaces as distinct, so
+/// `Key::Single(0)` and `Key::Pair(0, 0)` name different locks.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Key {
+    /// A single 64 bit key.
+    Single(i64),
+    /// A pair of 32 bit keys.
+    Pair(i32, i32),
+}
+
+impl From<i64> for Key {
+    fn from(key: i64) -> Key {
+        Key::Single(key)
+    }
+}
+
+impl From<(i32, i32)> for Key {
+    fn from((a, b): (i32, i32)) -> Key {
+        Key::Pair(a, b)
+    }
+}
+
+/// The mode of an advisory lock.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Mode {
+    /// The lock may only be held by a single session at a time.
+    Exclusive,
+    /// The lock may be held by any number of sessions at a time, but conflicts with exclusive
+    /// holders.
+    Shared,
+}
+
+#[doc(hidden)]
+pub fn query(function: &str, key: Key, mode: Mode) -> String {
+    let suffix = match mode {
+        Mode::Exclusive => "",
+        Mode::Shared => "_shared",
+    };
+    // the keys are integers, so can be inlined without escaping
+    match key {
+        Key::Single(key) => format!("SELECT pg_catalog.{}{}({})", function, suffix, key),
+        Key::Pair(a, b) => format!("SELECT pg_catalog.{}{}({}, {})", function, suffix, a, b),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn query_text() {
+        assert_eq!(
+            query("pg_advisory_lock", Key::Single(-1), Mode::Exclusive),
+            "SELECT pg_catalog.pg_advisory_lock(-1)"
+        );
+        assert_eq!(
+            query("pg_try_advisory_xact_lock", (1, 2).into(), Mode::Shared),
+            "SELECT pg_catalog.pg_try_advisory_xact_lock_shared(1, 2)"
+        );
+    }
+}
@@ -0,0 +1,397 @@
+//! Encoding and decoding of the binary `COPY` format.
+//!
+//! These types perform no IO themselves; the `postgres` and `tokio-postgres` crates wrap them in
+//! adaptors suitable for their respective `COPY` APIs.
+use byteorder::{BigEndian, ByteOrder};
+use postgres_protocol::types;
+use std::error::Error;
+use std::fmt;
+use std::i16;
+use std::io;
+use std::ops::Range;
+use std::sync::Arc;
+
+use types::{FromSql, IsNull, ToSql, Type, WrongType};
+
+const MAGIC: &'static [u8] = b"PGCOPY\n\xff\r\n\0";
+const HEADER_LEN: usize = 19;
+
+/// An encoder of rows into the binary `COPY` format.
+#[derive(Debug)]
+pub struct BinaryCopyEncoder {
+    types: Vec<Type>,
+    header_written: bool,
+}
+
+impl BinaryCopyEncoder {
+    /// Creates a new encoder for rows with the specified column types.
+    ///
+    /// Returns an error if there are more than 32767 columns, the most the format can represent.
+    pub fn new(types: &[Type]) -> io::Result<BinaryCopyEncoder> {
+        if types.len() > i16::MAX as usize {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "too many columns",
+            ));
+        }
+
+        Ok(BinaryCopyEncoder {
+            types: types.to_vec(),
+            header_written: false,
+        })
+    }
+
+    /// Returns the column types of the rows being encoded.
+    pub fn types(&self) -> &[Type] {
+        &self.types
+    }
+
+    /// Appends a row to `buf`, preceded by the format's header if this is the first call.
+    ///
+    /// The number of values must match the number of column types, and each value is converted
+    /// with `ToSql::to_sql_checked`. On error, `buf` is left unchanged.
+    pub fn encode_row(
+        &mut self,
+        row: &[&ToSql],
+        buf: &mut Vec<u8>,
+    ) -> Result<(), Box<Error + Sync + Send>> {
+        if row.len() != self.types.len() {
+            return Err(format!(
+                "expected {} values but got {}",
+                self.types.len(),
+                row.len()
+            ).into());
+        }
+
+        let start = buf.len();
+        let r = self.encode_row_inner(row, buf);
+        if r.is_err() {
+            buf.truncate(start);
+        } else {
+            self.header_written = true;
+        }
+        r
+    }
+
+    fn encode_row_inner(
+        &self,
+        row: &[&ToSql],
+        buf: &mut Vec<u8>,
+    ) -> Result<(), Box<Error + Sync + Send>> {
+        if !self.header_written {
+            write_header(buf);
+        }
+
+        types::int2_to_sql(self.types.len() as i16, buf);
+        for (value, ty) in row.iter().zip(&self.types) {
+            let base = buf.len();
+            buf.extend_from_slice(&[0; 4]);
+            let len = match value.to_sql_checked(ty, buf)? {
+                IsNull::Yes => -1,
+                IsNull::No => {
+                    let len = buf.len() - base - 4;
+                    if len > i32::max_value() as usize {
+                        return Err("value too large to transmit".into());
+                    }
+                    len as i32
+                }
+            };
+            BigEndian::write_i32(&mut buf[base..base + 4], len);
+        }
+
+        Ok(())
+    }
+
+    /// Appends the trailer marking the end of the data to `buf`.
+    ///
+    /// The header is written first if no rows were encoded.
+    pub fn finish(&mut self, buf: &mut Vec<u8>) {
+        if !self.header_written {
+            write_header(buf);
+            self.header_written = true;
+        }
+        types::int2_to_sql(-1, buf);
+    }
+}
+
+fn write_header(buf: &mut Vec<u8>) {
+    buf.extend_from_slice(MAGIC);
+    // flags
+    types::int4_to_sql(0, buf);
+    // header extension length
+    types::int4_to_sql(0, buf);
+}
+
+/// A decoder of rows in the binary `COPY` format.
+///
+/// Data is fed to the decoder with `push`, and decoded rows are retrieved with `next_row`.
+#[derive(Debug)]
+pub struct BinaryCopyDecoder {
+    types: Arc<Vec<Type>>,
+    buf: Vec<u8>,
+    pos: usize,
+    header_read: bool,
+    finished: bool,
+}
+
+impl BinaryCopyDecoder {
+    /// Creates a new decoder for rows with the specified column types.
+    pub fn new(types: &[Type]) -> BinaryCopyDecoder {
+        BinaryCopyDecoder {
+            types: Arc::new(types.to_vec()),
+            buf: vec![],
+            pos: 0,
+            header_read: false,
+            finished: false,
+        }
+    }
+
+    /// Returns the column types of the rows being decoded.
+    pub fn types(&self) -> &[Type] {
+        &self.types
+    }
+
+    /// Adds data to the decoder's buffer.
+    pub fn push(&mut self, data: &[u8]) {
+        if self.pos > 0 && self.pos >= self.buf.len() / 2 {
+            self.buf.drain(..self.pos);
+            self.pos = 0;
+        }
+        self.buf.extend_from_slice(data);
+    }
+
+    /// Determines if the trailer marking the end of the data has been decoded.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Decodes the next row from the buffered data.
+    ///
+    /// Returns `None` if more data is required or the end of the data has been reached; use
+    /// `is_finished` to distinguish the two cases.
+    pub fn next_row(&mut self) -> io::Result<Option<BinaryCopyRow>> {
+        if self.finished {
+            return Ok(None);
+        }
+
+        if !self.header_read {
+            if !self.read_header()? {
+                return Ok(None);
+            }
+            self.header_read = true;
+        }
+
+        let buf = &self.buf[self.pos..];
+        if buf.len() < 2 {
+            return Ok(None);
+        }
+
+        let count = BigEndian::read_i16(buf);
+        if count == -1 {
+            self.pos += 2;
+            self.finished = true;
+            return Ok(None);
+        }
+        if count as usize != self.types.len() {
+            return Err(invalid_data(&format!(
+                "expected {} values but got {}",
+                self.types.len(),
+                count
+            )));
+        }
+
+        let mut idx = 2;
+        let mut ranges = Vec::with_capacity(self.types.len());
+        for _ in 0..count {
+            if buf.len() < idx + 4 {
+                return Ok(None);
+            }
+            let len = BigEndian::read_i32(&buf[idx..]);
+            idx += 4;
+            if len < 0 {
+                ranges.push(None);
+                continue;
+            }
+            let len = len as usize;
+            if buf.len() < idx + len {
+                return Ok(None);
+            }
+            ranges.push(Some(idx - 2..idx - 2 + len));
+            idx += len;
+        }
+
+        let row = BinaryCopyRow {
+            types: self.types.clone(),
+            buf: buf[2..idx].to_vec(),
+            ranges: ranges,
+        };
+        self.pos += idx;
+        Ok(Some(row))
+    }
+
+    /// Returns an error if the trailer has not been decoded or unconsumed data remains.
+    ///
+    /// This should be called once the underlying source has been exhausted.
+    pub fn finish(&self) -> io::Result<()> {
+        if !self.finished {
+            return Err(io::Error::new(
+                io::ErrorKind::UnexpectedEof,
+                "unexpected end of binary copy data",
+            ));
+        }
+        if self.pos != self.buf.len() {
+            return Err(invalid_data("unexpected data after binary copy trailer"));
+        }
+        Ok(())
+    }
+
+    fn read_header(&mut self) -> io::Result<bool> {
+        let buf = &self.buf[self.pos..];
+        if buf.len() < HEADER_LEN {
+            return Ok(false);
+        }
+        if &buf[..MAGIC.len()] != MAGIC {
+            return Err(invalid_data("invalid binary copy signature"));
+        }
+        let flags = BigEndian::read_i32(&buf[MAGIC.len()..]);
+        // bit 16 indicates the presence of OIDs, which we don't support
+        if flags & (1 << 16) != 0 {
+            return Err(invalid_data("binary copy data with OIDs is not supported"));
+        }
+        let extension_len = BigEndian::read_i32(&buf[MAGIC.len() + 4..]);
+        if extension_len < 0 {
+            return Err(invalid_data("invalid binary copy header extension length"));
+        }
+        let len = HEADER_LEN + extension_len as usize;
+        if buf.len() < len {
+            return Ok(false);
+        }
+        self.pos += len;
+        Ok(true)
+    }
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// A row of data decoded from the binary `COPY` format.
+#[derive(Clone)]
+pub struct BinaryCopyRow {
+    types: Arc<Vec<Type>>,
+    buf: Vec<u8>,
+    ranges: Vec<Option<Range<usize>>>,
+}
+
+impl fmt::Debug for BinaryCopyRow {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("BinaryCopyRow")
+            .field("types", &self.types)
+            .finish()
+    }
+}
+
+impl BinaryCopyRow {
+    /// Returns the number of values in the row.
+    pub fn len(&self) -> usize {
+        self.ranges.len()
+    }
+
+    /// Determines if there are any values in the row.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the types of the values in the row.
+    pub fn types(&self) -> &[Type] {
+        &self.types
+    }
+
+    /// Retrieves the contents of a field of the row.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index is out of bounds or if the value cannot be converted to the specified
+    /// type.
+    pub fn get<'a, T>(&'a self, idx: usize) -> T
+    where
+        T: FromSql<'a>,
+    {
+        match self.try_get(idx) {
+            Ok(ok) => ok,
+            Err(err) => panic!("error retrieving column {}: {}", idx, err),
+        }
+    }
+
+    /// Retrieves the contents of a field of the row.
+    ///
+    /// Returns an error if the index is out of bounds or if the value cannot be converted to the
+    /// specified type.
+    pub fn try_get<'a, T>(&'a self, idx: usize) -> Result<T, Box<Error + Sync + Send>>
+    where
+        T: FromSql<'a>,
+    {
+        let ty = match self.types.get(idx) {
+            Some(ty) => ty,
+            None => return Err(format!("invalid column index {}", idx).into()),
+        };
+        if !T::accepts(ty) {
+            return Err(Box::new(WrongType::new(ty.clone())));
+        }
+        let raw = self.ranges[idx].clone().map(|r| &self.buf[r]);
+        T::from_sql_nullable(ty, raw)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let types = [Type::INT4, Type::TEXT];
+        let mut encoder = BinaryCopyEncoder::new(&types).unwrap();
+        let mut buf = vec![];
+        encoder.encode_row(&[&1i32, &"hello"], &mut buf).unwrap();
+        encoder.encode_row(&[&2i32, &None::<String>], &mut buf).unwrap();
+        assert!(encoder.encode_row(&[&3i32], &mut buf).is_err());
+        encoder.finish(&mut buf);
+
+        let mut decoder = BinaryCopyDecoder::new(&types);
+        let mut rows = vec![];
+        // feed the data in small pieces to exercise partial reads
+        for chunk in buf.chunks(3) {
+            decoder.push(chunk);
+            while let Some(row) = decoder.next_row().unwrap() {
+                rows.push(row);
+            }
+        }
+        decoder.finish().unwrap();
+
+        assert_eq!(rows.len(), 2);
+        assert_eq!(rows[0].get::<i32>(0), 1);
+        assert_eq!(rows[0].get::<&str>(1), "hello");
+        assert_eq!(rows[1].get::<i32>(0), 2);
+        assert_eq!(rows[1].get::<Option<String>>(1), None);
+        assert!(rows[1].try_get::<String>(1).is_err());
+        assert!(rows[1].try_get::<i32>(2).is_err());
+    }
+
+    #[test]
+    fn empty() {
+        let mut encoder = BinaryCopyEncoder::new(&[Type::INT4]).unwrap();
+        let mut buf = vec![];
+        encoder.finish(&mut buf);
+
+        let mut decoder = BinaryCopyDecoder::new(&[Type::INT4]);
+        decoder.push(&buf);
+        assert!(decoder.next_row().unwrap().is_none());
+        decoder.finish().unwrap();
+    }
+
+    #[test]
+    fn too_many_columns() {
+        assert!(BinaryCopyEncoder::new(&vec![Type::INT4; 32767]).is_ok());
+        assert!(BinaryCopyEncoder::new(&vec![Type::INT4; 32768]).is_err());
+    }
+}
@@ -0,0 +1,81 @@
+//! Types shared by the cursor APIs of both clients.
+
+/// Configuration of a cursor.
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    scroll: Option<bool>,
+    hold: bool,
+}
+
+impl Config {
+    /// Creates a new `Config` with no configuration overrides.
+    pub fn new() -> Config {
+        Config::default()
+    }
+
+    /// Sets the scrollability of the cursor.
+    ///
+    /// A scrollable cursor can be fetched from and moved backwards. By default, Postgres allows
+    /// backwards movement only if it does not require extra overhead to support.
+    pub fn scroll(&mut self, scroll: bool) -> &mut Config {
+        self.scroll = Some(scroll);
+        self
+    }
+
+    /// Returns the scrollability of the cursor, if it has been set.
+    pub fn get_scroll(&self) -> Option<bool> {
+        self.scroll
+    }
+
+    /// Sets the holdability of the cursor.
+    ///
+    /// A holdable cursor remains usable after the transaction that created it commits, and can
+    /// be declared outside of a transaction. Its results are materialized when the transaction
+    /// commits. Defaults to `false`.
+    pub fn hold(&mut self, hold: bool) -> &mut Config {
+        self.hold = hold;
+        self
+    }
+
+    /// Returns the holdability of the cursor.
+    pub fn get_hold(&self) -> bool {
+        self.hold
+    }
+
+    #[doc(hidden)]
+    pub fn declare_query(&self, name: &str, query: &str) -> String {
+        let mut s = format!("DECLARE {}", name);
+        match self.scroll {
+            Some(true) => s.push_str(" SCROLL"),
+            Some(false) => s.push_str(" NO SCROLL"),
+            None => {}
+        }
+        s.push_str(" CURSOR");
+        if self.hold {
+            s.push_str(" WITH HOLD");
+        }
+        s.push_str(" FOR ");
+        s.push_str(query);
+        s
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn declare_query() {
+        assert_eq!(
+            Config::new().declare_query("c0", "SELECT 1"),
+            "DECLARE c0 CURSOR FOR SELECT 1"
+        );
+        assert_eq!(
+            Config::new()
+                .scroll(false)
+                .hold(true)
+                .declare_query("c1", "SELECT 1"),
+            "DECLARE c1 NO SCROLL CURSOR WITH HOLD FOR SELECT 1"
+        );
+    }
+}
@@ -2,7 +2,6 @@
 
 use fallible_iterator::FallibleIterator;
 use postgres_protocol::message::backend::{ErrorFields, ErrorResponseBody};
-use std::convert::From;
 use std::error;
 use std::fmt;
 use std::io;
@@ -276,12 +275,197 @@ impl fmt::Debug for DbError {
     }
 }
 
+impl DbError {
+    /// The field contents are ERROR, FATAL, or PANIC (in an error message),
+    /// or WARNING, NOTICE, DEBUG, INFO, or LOG (in a notice message), or a
+    /// localized translation of one of these.
+    pub fn severity(&self) -> &str {
+        &self.severity
+    }
+
+    /// A parsed, nonlocalized version of `severity`. (PostgreSQL 9.6+)
+    pub fn parsed_severity(&self) -> Option<Severity> {
+        self.parsed_severity
+    }
+
+    /// The SQLSTATE code for the error.
+    pub fn code(&self) -> &SqlState {
+        &self.code
+    }
+
+    /// The primary human-readable error message.
+    ///
+    /// This should be accurate but terse (typically one line).
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    /// An optional secondary error message carrying more detail about the
+    /// problem.
+    ///
+    /// Might run to multiple lines.
+    pub fn detail(&self) -> Option<&str> {
+        self.detail.as_ref().map(|s| &**s)
+    }
+
+    /// An optional suggestion what to do about the problem.
+    ///
+    /// This is intended to differ from `detail` in that it offers advice
+    /// (potentially inappropriate) rather than hard facts. Might run to
+    /// multiple lines.
+    pub fn hint(&self) -> Option<&str> {
+        self.hint.as_ref().map(|s| &**s)
+    }
+
+    /// An optional error cursor position into either the original query string
+    /// or an internally generated query.
+    pub fn position(&self) -> Option<&ErrorPosition> {
+        self.position.as_ref()
+    }
+
+    /// An indication of the context in which the error occurred.
+    ///
+    /// Presently this includes a call stack traceback of active procedural
+    /// language functions and internally-generated queries. The trace is one
+    /// entry per line, most recent first.
+    pub fn where_(&self) -> Option<&str> {
+        self.where_.as_ref().map(|s| &**s)
+    }
+
+    /// If the error was associated with a specific database object, the name
+    /// of the schema containing that object, if any. (PostgreSQL 9.3+)
+    pub fn schema(&self) -> Option<&str> {
+        self.schema.as_ref().map(|s| &**s)
+    }
+
+    /// If the error was associated with a specific table, the name of the
+    /// table. (Refer to the schema name field for the name of the table's
+    /// schema.) (PostgreSQL 9.3+)
+    pub fn table(&self) -> Option<&str> {
+        self.table.as_ref().map(|s| &**s)
+    }
+
+    /// If the error was associated with a specific table column, the name of
+    /// the column.
+    ///
+    /// (Refer to the schema and table name fields to identify the table.)
+    /// (PostgreSQL 9.3+)
+    pub fn column(&self) -> Option<&str> {
+        self.column.as_ref().map(|s| &**s)
+    }
+
+    /// If the error was associated with a specific data type, the name of the
+    /// data type. (Refer to the schema name field for the name of the data
+    /// type's schema.) (PostgreSQL 9.3+)
+    pub fn datatype(&self) -> Option<&str> {
+        self.datatype.as_ref().map(|s| &**s)
+    }
+
+    /// If the error was associated with a specific constraint, the name of the
+    /// constraint.
+    ///
+    /// Refer to fields listed above for the associated table or domain.
+    /// (For this purpose, indexes are treated as constraints, even if they
+    /// weren't created with constraint syntax.) (PostgreSQL 9.3+)
+    pub fn constraint(&self) -> Option<&str> {
+        self.constraint.as_ref().map(|s| &**s)
+    }
+
+    /// The file name of the source-code location where the error was reported.
+    pub fn file(&self) -> Option<&str> {
+        self.file.as_ref().map(|s| &**s)
+    }
+
+    /// The line number of the source-code location where the error was
+    /// reported.
+    pub fn line(&self) -> Option<u32> {
+        self.line
+    }
+
+    /// The name of the source-code routine reporting the error.
+    pub fn routine(&self) -> Option<&str> {
+        self.routine.as_ref().map(|s| &**s)
+    }
+
+    /// Returns a value which displays the error along with the line of the query it occurred in,
+    /// with a caret marking the error position, and the detail, hint and context of the error.
+    ///
+    /// `query` should be the text of the query which caused the error. If the position is in an
+    /// internally generated query, that query is displayed instead.
+    pub fn render<'a>(&'a self, query: &'a str) -> RenderedDbError<'a> {
+        RenderedDbError {
+            error: self,
+            query: query,
+        }
+    }
+
+    /// Returns the details of the error if it is a unique constraint violation.
+    pub fn as_unique_violation(&self) -> Option<ConstraintViolation> {
+        self.as_violation(&SqlState::UNIQUE_VIOLATION)
+    }
+
+    /// Returns the details of the error if it is a foreign key constraint violation.
+    pub fn as_foreign_key_violation(&self) -> Option<ConstraintViolation> {
+        self.as_violation(&SqlState::FOREIGN_KEY_VIOLATION)
+    }
+
+    /// Returns the details of the error if it is a not-null constraint violation.
+    pub fn as_not_null_violation(&self) -> Option<ConstraintViolation> {
+        self.as_violation(&SqlState::NOT_NULL_VIOLATION)
+    }
+
+    /// Returns the details of the error if it is a check constraint violation.
+    pub fn as_check_violation(&self) -> Option<ConstraintViolation> {
+        self.as_violation(&SqlState::CHECK_VIOLATION)
+    }
+
+    fn as_violation(&self, code: &SqlState) -> Option<ConstraintViolation> {
+        if self.code != *code {
+            return None;
+        }
+
+        Some(constraint_violation(
+            self.constraint(),
+            self.schema(),
+            self.table(),
+            self.column(),
+            self.detail(),
+        ))
+    }
+}
+
 impl fmt::Display for DbError {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         write!(fmt, "{}: {}", self.severity, self.message)
     }
 }
 
+/// A `DbError` displayed along with the query it occurred in.
+///
+/// Created by `DbError::render`.
+pub struct RenderedDbError<'a> {
+    error: &'a DbError,
+    query: &'a str,
+}
+
+impl<'a> fmt::Display for RenderedDbError<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        let error = self.error;
+        write!(fmt, "{}", error)?;
+        match error.position {
+            Some(ErrorPosition::Normal(position)) => {
+                write_position(fmt, self.query, position, false)?
+            }
+            Some(ErrorPosition::Internal {
+                position,
+                ref query,
+            }) => write_position(fmt, query, position, true)?,
+            None => {}
+        }
+        write_fields(fmt, error.detail(), error.hint(), error.where_())
+    }
+}
+
 impl error::Error for DbError {
     fn description(&self) -> &str {
         &self.message
@@ -302,97 +486,325 @@ pub enum ErrorPosition {
     },
 }
 
-#[doc(hidden)]
-pub fn connect(e: Box<error::Error + Sync + Send>) -> Error {
-    Error(Box::new(ErrorKind::ConnectParams(e)))
+/// The details of a constraint violation.
+///
+/// Created by `DbError::as_unique_violation` and the similar methods of `DbError` and `Error`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConstraintViolation<'a> {
+    /// The name of the violated constraint, or of the index for unique violations.
+    pub constraint: Option<&'a str>,
+
+    /// The schema of the table the constraint belongs to.
+    pub schema: Option<&'a str>,
+
+    /// The table the constraint belongs to.
+    pub table: Option<&'a str>,
+
+    /// The columns involved in the violation, as written by Postgres.
+    ///
+    /// For unique and foreign key violations, these are parsed from the `Key (...)=(...)` part of
+    /// the error detail, which Postgres omits if the user lacks permission to read the columns.
+    /// Columns of expression indexes are expressions, and quoted identifiers keep their quotes.
+    /// For not-null violations, this is the column which was null.
+    pub columns: Vec<&'a str>,
 }
 
-#[doc(hidden)]
-pub fn tls(e: Box<error::Error + Sync + Send>) -> Error {
-    Error(Box::new(ErrorKind::Tls(e)))
+fn constraint_violation<'a>(
+    constraint: Option<&'a str>,
+    schema: Option<&'a str>,
+    table: Option<&'a str>,
+    column: Option<&'a str>,
+    detail: Option<&'a str>,
+) -> ConstraintViolation<'a> {
+    let columns = match column {
+        Some(column) => vec![column],
+        None => detail.map_or_else(Vec::new, key_columns),
+    };
+
+    ConstraintViolation {
+        constraint: constraint,
+        schema: schema,
+        table: table,
+        columns: columns,
+    }
 }
 
-#[doc(hidden)]
-pub fn db(e: DbError) -> Error {
-    Error(Box::new(ErrorKind::Db(e)))
+// Parses the columns out of a detail message like `Key (a, lower(b))=(1, x) already exists.`
+fn key_columns(detail: &str) -> Vec<&str> {
+    let rest = match detail.find("Key (") {
+        Some(i) => &detail[i + "Key (".len()..],
+        None => return vec![],
+    };
+    let end = match rest.find(")=(") {
+        Some(end) => end,
+        None => return vec![],
+    };
+    let list = &rest[..end];
+
+    // split on commas outside of parentheses and quotes
+    let mut columns = vec![];
+    let mut depth = 0;
+    let mut quoted = false;
+    let mut start = 0;
+    for (i, c) in list.char_indices() {
+        match c {
+            '"' => quoted = !quoted,
+            '(' if !quoted => depth += 1,
+            ')' if !quoted => depth -= 1,
+            ',' if !quoted && depth == 0 => {
+                columns.push(list[start..i].trim());
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    columns.push(list[start..].trim());
+    columns
 }
 
-#[doc(hidden)]
-pub fn __db(e: ErrorResponseBody) -> Error {
-    match DbError::new(&mut e.fields()) {
-        Ok(e) => Error(Box::new(ErrorKind::Db(e))),
-        Err(e) => Error(Box::new(ErrorKind::Io(e))),
+// Writes the line of `query` containing the error position, followed by a line with a caret
+// under the position. Postgres reports positions as 1-based character indices.
+fn write_position(
+    fmt: &mut fmt::Formatter,
+    query: &str,
+    position: u32,
+    internal: bool,
+) -> fmt::Result {
+    let index = (position as usize).saturating_sub(1);
+    let offset = query
+        .char_indices()
+        .nth(index)
+        .map_or(query.len(), |(offset, _)| offset);
+
+    let line_start = query[..offset].rfind('\n').map_or(0, |i| i + 1);
+    let line_end = query[offset..]
+        .find('\n')
+        .map_or(query.len(), |i| offset + i);
+    let line = query[line_start..line_end].trim_right_matches('\r');
+
+    let prefix = if internal {
+        "QUERY: ".to_string()
+    } else {
+        let line_number = query[..line_start].matches('\n').count() + 1;
+        format!("LINE {}: ", line_number)
+    };
+    write!(fmt, "\n{}{}\n", prefix, line)?;
+
+    // tabs are kept so that the caret lines up with the query however they're displayed
+    for _ in prefix.chars() {
+        fmt.write_str(" ")?;
     }
+    for c in query[line_start..offset].chars() {
+        fmt.write_str(if c == '\t' { "\t" } else { " " })?;
+    }
+    fmt.write_str("^")
 }
 
-#[doc(hidden)]
-pub fn __user<T>(e: T) -> Error
-where
-    T: Into<Box<error::Error + Sync + Send>>,
-{
-    Error(Box::new(ErrorKind::Conversion(e.into())))
+fn write_fields(
+    fmt: &mut fmt::Formatter,
+    detail: Option<&str>,
+    hint: Option<&str>,
+    where_: Option<&str>,
+) -> fmt::Result {
+    if let Some(detail) = detail {
+        write!(fmt, "\nDETAIL: {}", detail)?;
+    }
+    if let Some(hint) = hint {
+        write!(fmt, "\nHINT: {}", hint)?;
+    }
+    if let Some(where_) = where_ {
+        write!(fmt, "\nCONTEXT: {}", where_)?;
+    }
+    Ok(())
 }
 
-#[doc(hidden)]
-pub fn io(e: io::Error) -> Error {
-    Error(Box::new(ErrorKind::Io(e)))
+// The connection has been poisoned by an earlier IO error.
+#[derive(Debug)]
+struct Desynchronized;
+
+impl fmt::Display for Desynchronized {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str(error::Error::description(self))
+    }
+}
+
+impl error::Error for Desynchronized {
+    fn description(&self) -> &str {
+        "communication with the server has desynchronized due to an earlier IO error"
+    }
 }
 
 #[doc(hidden)]
-pub fn conversion(e: Box<error::Error + Sync + Send>) -> Error {
-    Error(Box::new(ErrorKind::Conversion(e)))
+pub fn __desynchronized() -> io::Error {
+    io::Error::new(io::ErrorKind::Other, Desynchronized)
 }
 
-#[derive(Debug)]
-enum ErrorKind {
-    ConnectParams(Box<error::Error + Sync + Send>),
-    Tls(Box<error::Error + Sync + Send>),
-    Db(DbError),
-    Io(io::Error),
-    Conversion(Box<error::Error + Sync + Send>),
+fn is_connection_lost_code(code: &SqlState) -> bool {
+    code.code().starts_with("08")
+        || *code == SqlState::ADMIN_SHUTDOWN
+        || *code == SqlState::CRASH_SHUTDOWN
+        || *code == SqlState::CANNOT_CONNECT_NOW
+}
+
+fn is_transient_code(code: &SqlState) -> bool {
+    is_connection_lost_code(code) || code.code().starts_with("40") || code.code().starts_with("53")
+}
+
+fn is_retryable_code(code: &SqlState) -> bool {
+    if *code == SqlState::SQLCLIENT_UNABLE_TO_ESTABLISH_SQLCONNECTION
+        || *code == SqlState::SQLSERVER_REJECTED_ESTABLISHMENT_OF_SQLCONNECTION
+        || *code == SqlState::CANNOT_CONNECT_NOW
+    {
+        // the session never started, so nothing was run
+        return true;
+    }
+
+    // the server rolled the transaction back, unless it doesn't know whether the statement
+    // completed
+    *code != SqlState::T_R_STATEMENT_COMPLETION_UNKNOWN
+        && (code.code().starts_with("40") || code.code().starts_with("53"))
+}
+
+fn is_connection_lost_io(e: &io::Error) -> bool {
+    match e.kind() {
+        io::ErrorKind::ConnectionReset
+        | io::ErrorKind::ConnectionAborted
+        | io::ErrorKind::BrokenPipe
+        | io::ErrorKind::NotConnected
+        | io::ErrorKind::UnexpectedEof => true,
+        _ => e.get_ref().map_or(false, |e| e.is::<Desynchronized>()),
+    }
+}
+
+fn is_transient_io(e: &io::Error) -> bool {
+    match e.kind() {
+        io::ErrorKind::ConnectionRefused | io::ErrorKind::TimedOut => true,
+        _ => is_connection_lost_io(e),
+    }
+}
+
+fn is_retryable_io(e: &io::Error) -> bool {
+    // a refused connection never reached the server
+    e.kind() == io::ErrorKind::ConnectionRefused
+}
+
+/// The kind of an `Error`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Kind {
+    /// An IO error communicating with the server.
+    Io,
+    /// The server sent a message which was not expected at that point.
+    UnexpectedMessage,
+    /// An error performing the TLS handshake.
+    Tls,
+    /// An error converting a value to its Postgres representation.
+    ToSql,
+    /// An error converting a value from its Postgres representation.
+    FromSql,
+    /// An error returned by the stream passed to a `COPY ... FROM STDIN` query.
+    CopyInStream,
+    /// The connection was closed.
+    Closed,
+    /// An error reported by the server.
+    Db,
+    /// An error parsing a message from the server.
+    Parse,
+    /// An error encoding a message to the server.
+    Encode,
+    /// A username was not provided.
+    MissingUser,
+    /// The server requested a password, but one was not provided.
+    MissingPassword,
+    /// The server requested an authentication method which is not supported.
+    UnsupportedAuthentication,
+    /// The connection parameters were invalid.
+    ConnectParams,
+    /// An error establishing a connection to the server.
+    Connect,
+    /// An error from a timer.
+    Timer,
+    /// An error authenticating with the server.
+    Authentication,
+    #[doc(hidden)]
+    __NonExhaustive,
+}
+
+struct ErrorInner {
+    kind: Kind,
+    cause: Option<Box<error::Error + Sync + Send>>,
 }
 
 /// An error communicating with the Postgres server.
-#[derive(Debug)]
-pub struct Error(Box<ErrorKind>);
+pub struct Error(Box<ErrorInner>);
+
+impl fmt::Debug for Error {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("Error")
+            .field("kind", &self.0.kind)
+            .field("cause", &self.0.cause)
+            .finish()
+    }
+}
 
 impl fmt::Display for Error {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         fmt.write_str(error::Error::description(self))?;
-        match *self.0 {
-            ErrorKind::ConnectParams(ref err) => write!(fmt, ": {}", err),
-            ErrorKind::Tls(ref err) => write!(fmt, ": {}", err),
-            ErrorKind::Db(ref err) => write!(fmt, ": {}", err),
-            ErrorKind::Io(ref err) => write!(fmt, ": {}", err),
-            ErrorKind::Conversion(ref err) => write!(fmt, ": {}", err),
+        if let Some(ref cause) = self.0.cause {
+            write!(fmt, ": {}", cause)?;
         }
+        Ok(())
     }
 }
 
 impl error::Error for Error {
     fn description(&self) -> &str {
-        match *self.0 {
-            ErrorKind::ConnectParams(_) => "invalid connection parameters",
-            ErrorKind::Tls(_) => "TLS handshake error",
-            ErrorKind::Db(_) => "database error",
-            ErrorKind::Io(_) => "IO error",
-            ErrorKind::Conversion(_) => "type conversion error",
+        match self.0.kind {
+            Kind::Io => "error communicating with the server",
+            Kind::UnexpectedMessage => "unexpected message from server",
+            Kind::Tls => "error performing TLS handshake",
+            Kind::ToSql => "error serializing a value",
+            Kind::FromSql => "error deserializing a value",
+            Kind::CopyInStream => "error from a copy_in stream",
+            Kind::Closed => "connection closed",
+            Kind::Db => "db error",
+            Kind::Parse => "error parsing response from server",
+            Kind::Encode => "error encoding message to server",
+            Kind::MissingUser => "username not provided",
+            Kind::MissingPassword => "password not provided",
+            Kind::UnsupportedAuthentication => "unsupported authentication method requested",
+            Kind::ConnectParams => "invalid connection parameters",
+            Kind::Connect => "error connecting to server",
+            Kind::Timer => "timer error",
+            Kind::Authentication => "authentication error",
+            Kind::__NonExhaustive => unreachable!(),
         }
     }
 
     fn cause(&self) -> Option<&error::Error> {
-        match *self.0 {
-            ErrorKind::ConnectParams(ref err) => Some(&**err),
-            ErrorKind::Tls(ref err) => Some(&**err),
-            ErrorKind::Db(ref err) => Some(err),
-            ErrorKind::Io(ref err) => Some(err),
-            ErrorKind::Conversion(ref err) => Some(&**err),
-        }
+        self.0.cause.as_ref().map(|e| &**e as &error::Error)
     }
 }
 
 impl Error {
-    /// Returns the SQLSTATE error code associated with this error if it is a DB
+    /// Returns the kind of the error.
+    pub fn kind(&self) -> Kind {
+        self.0.kind
+    }
+
+    /// Returns the error's cause.
+    ///
+    /// This is the same as `Error::cause` except that it provides extra bounds
+    /// required to be able to downcast the error.
+    pub fn cause2(&self) -> Option<&(error::Error + 'static + Sync + Send)> {
+        self.0.cause.as_ref().map(|e| &**e)
+    }
+
+    /// Consumes the error, returning its cause.
+    pub fn into_cause(self) -> Option<Box<error::Error + Sync + Send>> {
+        self.0.cause
+    }
+
+    /// Returns the SQLSTATE error code associated with the error if it is a DB
     /// error.
     pub fn code(&self) -> Option<&SqlState> {
         self.as_db().map(|e| &e.code)
@@ -400,24 +812,24 @@ impl Error {
 
     /// Returns the inner error if this is a connection parameter error.
     pub fn as_connection(&self) -> Option<&(error::Error + 'static + Sync + Send)> {
-        match *self.0 {
-            ErrorKind::ConnectParams(ref err) => Some(&**err),
+        match self.0.kind {
+            Kind::ConnectParams => self.cause2(),
             _ => None,
         }
     }
 
     /// Returns the `DbError` associated with this error if it is a DB error.
     pub fn as_db(&self) -> Option<&DbError> {
-        match *self.0 {
-            ErrorKind::Db(ref err) => Some(err),
+        match self.0.kind {
+            Kind::Db => self.cause2().and_then(|e| e.downcast_ref::<DbError>()),
             _ => None,
         }
     }
 
     /// Returns the inner error if this is a conversion error.
     pub fn as_conversion(&self) -> Option<&(error::Error + 'static + Sync + Send)> {
-        match *self.0 {
-            ErrorKind::Conversion(ref err) => Some(&**err),
+        match self.0.kind {
+            Kind::ToSql | Kind::FromSql => self.cause2(),
             _ => None,
         }
     }
@@ -425,24 +837,377 @@ impl Error {
     /// Returns the inner `io::Error` associated with this error if it is an IO
     /// error.
     pub fn as_io(&self) -> Option<&io::Error> {
-        match *self.0 {
-            ErrorKind::Io(ref err) => Some(err),
+        match self.0.kind {
+            Kind::Io | Kind::Connect => self.cause2().and_then(|e| e.downcast_ref::<io::Error>()),
             _ => None,
         }
     }
+
+    /// Returns the details of the error if it is a unique constraint violation.
+    pub fn as_unique_violation(&self) -> Option<ConstraintViolation> {
+        self.as_db().and_then(DbError::as_unique_violation)
+    }
+
+    /// Returns the details of the error if it is a foreign key constraint violation.
+    pub fn as_foreign_key_violation(&self) -> Option<ConstraintViolation> {
+        self.as_db().and_then(DbError::as_foreign_key_violation)
+    }
+
+    /// Returns the details of the error if it is a not-null constraint violation.
+    pub fn as_not_null_violation(&self) -> Option<ConstraintViolation> {
+        self.as_db().and_then(DbError::as_not_null_violation)
+    }
+
+    /// Returns the details of the error if it is a check constraint violation.
+    pub fn as_check_violation(&self) -> Option<ConstraintViolation> {
+        self.as_db().and_then(DbError::as_check_violation)
+    }
+
+    /// Determines if the connection which produced the error can no longer be used.
+    ///
+    /// This is the case for errors returned after the connection has closed, for IO errors
+    /// caused by the connection being reset or closed, for errors returned after an earlier IO
+    /// error desynchronized the connection, and for database errors in SQLSTATE class 08
+    /// (connection exception) or with codes 57P01 through 57P03 (the server is shutting down or
+    /// starting up). Connection pools should discard such connections.
+    pub fn is_connection_lost(&self) -> bool {
+        match self.0.kind {
+            Kind::Closed => true,
+            Kind::Db => self.code().map_or(false, is_connection_lost_code),
+            Kind::Io => self.as_io().map_or(false, is_connection_lost_io),
+            _ => false,
+        }
+    }
+
+    /// Determines if the error was caused by a condition which is expected to be temporary.
+    ///
+    /// In addition to the errors covered by `is_connection_lost`, this includes refused and
+    /// timed out connections and database errors in SQLSTATE classes 40 (transaction rollback,
+    /// such as serialization failures and deadlocks) and 53 (insufficient resources). The same
+    /// work may succeed if it is attempted again later, possibly on a new connection.
+    pub fn is_transient(&self) -> bool {
+        match self.0.kind {
+            Kind::Closed => true,
+            Kind::Db => self.code().map_or(false, is_transient_code),
+            Kind::Io | Kind::Connect => self.as_io().map_or(false, is_transient_io),
+            _ => false,
+        }
+    }
+
+    /// Determines if the failed operation can safely be retried.
+    ///
+    /// This is a subset of the transient errors for which the server is known not to have
+    /// applied any of the operation's work: the transaction was rolled back (SQLSTATE classes
+    /// 40 and 53, except 40003), or the connection was never established (08001, 08004, 57P03
+    /// and refused connections). A connection lost in the middle of a query is not retryable,
+    /// since the query may or may not have been committed.
+    pub fn is_retryable(&self) -> bool {
+        match self.0.kind {
+            Kind::Db => self.code().map_or(false, is_retryable_code),
+            Kind::Io | Kind::Connect => self.as_io().map_or(false, is_retryable_io),
+            _ => false,
+        }
+    }
+
+    fn new(kind: Kind, cause: Option<Box<error::Error + Sync + Send>>) -> Error {
+        Error(Box::new(ErrorInner { kind, cause }))
+    }
+
+    #[doc(hidden)]
+    pub fn closed() -> Error {
+        Error::new(Kind::Closed, None)
+    }
+
+    #[doc(hidden)]
+    pub fn unexpected_message() -> Error {
+        Error::new(Kind::UnexpectedMessage, None)
+    }
+
+    #[doc(hidden)]
+    pub fn db(error: ErrorResponseBody) -> Error {
+        match DbError::new(&mut error.fields()) {
+            Ok(e) => Error::from(e),
+            Err(e) => Error::parse(e),
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn parse(e: io::Error) -> Error {
+        Error::new(Kind::Parse, Some(Box::new(e)))
+    }
+
+    #[doc(hidden)]
+    pub fn encode(e: io::Error) -> Error {
+        Error::new(Kind::Encode, Some(Box::new(e)))
+    }
+
+    #[doc(hidden)]
+    pub fn to_sql(e: Box<error::Error + Sync + Send>) -> Error {
+        Error::new(Kind::ToSql, Some(e))
+    }
+
+    #[doc(hidden)]
+    pub fn from_sql(e: Box<error::Error + Sync + Send>) -> Error {
+        Error::new(Kind::FromSql, Some(e))
+    }
+
+    #[doc(hidden)]
+    pub fn copy_in_stream<E>(e: E) -> Error
+    where
+        E: Into<Box<error::Error + Sync + Send>>,
+    {
+        Error::new(Kind::CopyInStream, Some(e.into()))
+    }
+
+    #[doc(hidden)]
+    pub fn missing_user() -> Error {
+        Error::new(Kind::MissingUser, None)
+    }
+
+    #[doc(hidden)]
+    pub fn missing_password() -> Error {
+        Error::new(Kind::MissingPassword, None)
+    }
+
+    #[doc(hidden)]
+    pub fn unsupported_authentication() -> Error {
+        Error::new(Kind::UnsupportedAuthentication, None)
+    }
+
+    #[doc(hidden)]
+    pub fn tls(e: Box<error::Error + Sync + Send>) -> Error {
+        Error::new(Kind::Tls, Some(e))
+    }
+
+    #[doc(hidden)]
+    pub fn connect_params(e: Box<error::Error + Sync + Send>) -> Error {
+        Error::new(Kind::ConnectParams, Some(e))
+    }
+
+    #[doc(hidden)]
+    pub fn connect(e: io::Error) -> Error {
+        Error::new(Kind::Connect, Some(Box::new(e)))
+    }
+
+    #[doc(hidden)]
+    pub fn timer<E>(e: E) -> Error
+    where
+        E: error::Error + 'static + Sync + Send,
+    {
+        Error::new(Kind::Timer, Some(Box::new(e)))
+    }
+
+    #[doc(hidden)]
+    pub fn io(e: io::Error) -> Error {
+        Error::new(Kind::Io, Some(Box::new(e)))
+    }
+
+    #[doc(hidden)]
+    pub fn authentication(e: io::Error) -> Error {
+        Error::new(Kind::Authentication, Some(Box::new(e)))
+    }
+}
+
+impl From<DbError> for Error {
+    fn from(err: DbError) -> Error {
+        Error::new(Kind::Db, Some(Box::new(err)))
+    }
 }
 
 impl From<io::Error> for Error {
     fn from(err: io::Error) -> Error {
-        Error(Box::new(ErrorKind::Io(err)))
+        Error::io(err)
     }
 }
 
 impl From<Error> for io::Error {
     fn from(err: Error) -> io::Error {
-        match *err.0 {
-            ErrorKind::Io(e) => e,
-            _ => io::Error::new(io::ErrorKind::Other, err),
+        if err.kind() != Kind::Io {
+            return io::Error::new(io::ErrorKind::Other, err);
+        }
+
+        match err.into_cause().map(|e| e.downcast::<io::Error>()) {
+            Some(Ok(e)) => *e,
+            Some(Err(e)) => io::Error::new(io::ErrorKind::Other, e),
+            None => io::ErrorKind::Other.into(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn db_error(position: Option<ErrorPosition>) -> DbError {
+        DbError {
+            severity: "ERROR".to_string(),
+            parsed_severity: Some(Severity::Error),
+            code: SqlState::SYNTAX_ERROR,
+            message: "syntax error at or near \"FORM\"".to_string(),
+            detail: None,
+            hint: Some("check the query".to_string()),
+            position: position,
+            where_: None,
+            schema: None,
+            table: None,
+            column: None,
+            datatype: None,
+            constraint: None,
+            file: None,
+            line: None,
+            routine: None,
+            _p: (),
         }
     }
+
+    #[test]
+    fn render() {
+        let query = "SELECT *\n\tFORM foo";
+        let error = db_error(Some(ErrorPosition::Normal(11)));
+        assert_eq!(
+            error.render(query).to_string(),
+            "ERROR: syntax error at or near \"FORM\"\n\
+             LINE 2: \tFORM foo\n        \t^\n\
+             HINT: check the query"
+        );
+
+        // errors at the end of the input point just past it
+        let error = db_error(Some(ErrorPosition::Normal(9)));
+        assert!(error
+            .render("SELECT (")
+            .to_string()
+            .contains("LINE 1: SELECT (\n                ^"));
+
+        let error = db_error(Some(ErrorPosition::Internal {
+            position: 3,
+            query: "é FORM".to_string(),
+        }));
+        assert!(error
+            .render("SELECT f()")
+            .to_string()
+            .contains("QUERY: é FORM\n         ^"));
+    }
+
+    fn db_error_with_code(code: SqlState) -> Error {
+        let mut error = db_error(None);
+        error.code = code;
+        Error::from(error)
+    }
+
+    #[test]
+    fn classification() {
+        let error = db_error_with_code(SqlState::T_R_SERIALIZATION_FAILURE);
+        assert!(!error.is_connection_lost());
+        assert!(error.is_transient());
+        assert!(error.is_retryable());
+
+        let error = db_error_with_code(SqlState::T_R_STATEMENT_COMPLETION_UNKNOWN);
+        assert!(error.is_transient());
+        assert!(!error.is_retryable());
+
+        let error = db_error_with_code(SqlState::TOO_MANY_CONNECTIONS);
+        assert!(!error.is_connection_lost());
+        assert!(error.is_transient());
+        assert!(error.is_retryable());
+
+        let error = db_error_with_code(SqlState::ADMIN_SHUTDOWN);
+        assert!(error.is_connection_lost());
+        assert!(error.is_transient());
+        assert!(!error.is_retryable());
+
+        let error = db_error_with_code(SqlState::CANNOT_CONNECT_NOW);
+        assert!(error.is_connection_lost());
+        assert!(error.is_retryable());
+
+        let error = db_error_with_code(SqlState::TRANSACTION_RESOLUTION_UNKNOWN);
+        assert!(error.is_connection_lost());
+        assert!(!error.is_retryable());
+
+        let error = db_error_with_code(SqlState::UNIQUE_VIOLATION);
+        assert!(!error.is_connection_lost());
+        assert!(!error.is_transient());
+        assert!(!error.is_retryable());
+
+        let error = Error::from(io::Error::from(io::ErrorKind::ConnectionReset));
+        assert!(error.is_connection_lost());
+        assert!(error.is_transient());
+        assert!(!error.is_retryable());
+
+        let error = Error::from(__desynchronized());
+        assert!(error.is_connection_lost());
+        assert!(!error.is_retryable());
+
+        let error = Error::from(io::Error::from(io::ErrorKind::ConnectionRefused));
+        assert!(!error.is_connection_lost());
+        assert!(error.is_transient());
+        assert!(error.is_retryable());
+
+        let error = Error::from(io::Error::new(io::ErrorKind::Other, "boom"));
+        assert!(!error.is_connection_lost());
+        assert!(!error.is_transient());
+
+        let error = Error::from_sql("bad value".into());
+        assert!(!error.is_transient());
+    }
+
+    #[test]
+    fn kind_and_cause() {
+        let err = Error::from(io::Error::from(io::ErrorKind::BrokenPipe));
+        assert_eq!(err.kind(), Kind::Io);
+        assert!(error::Error::cause(&err).is_some());
+        let cause = err.cause2().unwrap();
+        assert!(cause.downcast_ref::<io::Error>().is_some());
+        assert_eq!(io::Error::from(err).kind(), io::ErrorKind::BrokenPipe);
+
+        let err = Error::from(db_error(None));
+        assert_eq!(err.kind(), Kind::Db);
+        assert_eq!(err.code(), Some(&SqlState::SYNTAX_ERROR));
+        assert!(err.as_io().is_none());
+        assert!(err.to_string().contains("syntax error"));
+
+        let err = Error::closed();
+        assert!(error::Error::cause(&err).is_none());
+    }
+
+    #[test]
+    fn sqlstate_class_and_name() {
+        let code = SqlState::UNIQUE_VIOLATION;
+        assert_eq!(
+            code.class(),
+            Some(SqlStateClass::IntegrityConstraintViolation)
+        );
+        assert_eq!(code.name(), Some("unique_violation"));
+
+        // the condition name is shared by all constants for a code
+        assert_eq!(
+            SqlState::ARRAY_ELEMENT_ERROR.name(),
+            Some("array_subscript_error")
+        );
+
+        let class = SqlState::T_R_DEADLOCK_DETECTED.class().unwrap();
+        assert_eq!(class, SqlStateClass::TransactionRollback);
+        assert_eq!(class.code(), "40");
+        assert_eq!(class.description(), "Transaction Rollback");
+        assert_eq!(
+            SqlStateClass::from_code("HV"),
+            Some(SqlStateClass::ForeignDataWrapperError)
+        );
+
+        let code = SqlState::from_code("ZZ001");
+        assert_eq!(code.class(), None);
+        assert_eq!(code.name(), None);
+    }
+
+    #[test]
+    fn key_columns() {
+        assert_eq!(
+            super::key_columns("Key (email)=(a@b.com) already exists."),
+            ["email"]
+        );
+        assert_eq!(
+            super::key_columns("Key (a, lower((b)::text), \"c,d\")=(1, x, y) already exists."),
+            ["a", "lower((b)::text)", "\"c,d\""]
+        );
+        assert!(super::key_columns("Failing row contains (1, null).").is_empty());
+    }
 }
@@ -20,6 +20,17 @@ impl SqlState {
         &self.0
     }
 
+    /// Returns the class of the error code, or `None` if it is not in a known class.
+    pub fn class(&self) -> Option<SqlStateClass> {
+        self.0.get(..2).and_then(SqlStateClass::from_code)
+    }
+
+    /// Returns the condition name of the error code, such as `unique_violation`, or `None` if
+    /// the code is not known.
+    pub fn name(&self) -> Option<&'static str> {
+        condition_name(&self.0)
+    }
+
     /// 00000
     pub const SUCCESSFUL_COMPLETION: SqlState = SqlState(Cow::Borrowed("00000"));
 
@@ -761,6 +772,295 @@ impl SqlState {
     /// XX002
     pub const INDEX_CORRUPTED: SqlState = SqlState(Cow::Borrowed("XX002"));
 }
+
+/// The class of a SQLSTATE error code, identified by its first two characters.
+#[derive(PartialEq, Eq, Copy, Clone, Debug, Hash)]
+pub enum SqlStateClass {
+    /// 00 - Successful Completion
+    SuccessfulCompletion,
+
+    /// 01 - Warning
+    Warning,
+
+    /// 02 - No Data
+    NoData,
+
+    /// 03 - SQL Statement Not Yet Complete
+    SqlStatementNotYetComplete,
+
+    /// 08 - Connection Exception
+    ConnectionException,
+
+    /// 09 - Triggered Action Exception
+    TriggeredActionException,
+
+    /// 0A - Feature Not Supported
+    FeatureNotSupported,
+
+    /// 0B - Invalid Transaction Initiation
+    InvalidTransactionInitiation,
+
+    /// 0F - Locator Exception
+    LocatorException,
+
+    /// 0L - Invalid Grantor
+    InvalidGrantor,
+
+    /// 0P - Invalid Role Specification
+    InvalidRoleSpecification,
+
+    /// 0Z - Diagnostics Exception
+    DiagnosticsException,
+
+    /// 20 - Case Not Found
+    CaseNotFound,
+
+    /// 21 - Cardinality Violation
+    CardinalityViolation,
+
+    /// 22 - Data Exception
+    DataException,
+
+    /// 23 - Integrity Constraint Violation
+    IntegrityConstraintViolation,
+
+    /// 24 - Invalid Cursor State
+    InvalidCursorState,
+
+    /// 25 - Invalid Transaction State
+    InvalidTransactionState,
+
+    /// 26 - Invalid SQL Statement Name
+    InvalidSqlStatementName,
+
+    /// 27 - Triggered Data Change Violation
+    TriggeredDataChangeViolation,
+
+    /// 28 - Invalid Authorization Specification
+    InvalidAuthorizationSpecification,
+
+    /// 2B - Dependent Privilege Descriptors Still Exist
+    DependentPrivilegeDescriptorsStillExist,
+
+    /// 2D - Invalid Transaction Termination
+    InvalidTransactionTermination,
+
+    /// 2F - SQL Routine Exception
+    SqlRoutineException,
+
+    /// 34 - Invalid Cursor Name
+    InvalidCursorName,
+
+    /// 38 - External Routine Exception
+    ExternalRoutineException,
+
+    /// 39 - External Routine Invocation Exception
+    ExternalRoutineInvocationException,
+
+    /// 3B - Savepoint Exception
+    SavepointException,
+
+    /// 3D - Invalid Catalog Name
+    InvalidCatalogName,
+
+    /// 3F - Invalid Schema Name
+    InvalidSchemaName,
+
+    /// 40 - Transaction Rollback
+    TransactionRollback,
+
+    /// 42 - Syntax Error or Access Rule Violation
+    SyntaxErrorOrAccessRuleViolation,
+
+    /// 44 - WITH CHECK OPTION Violation
+    WithCheckOptionViolation,
+
+    /// 53 - Insufficient Resources
+    InsufficientResources,
+
+    /// 54 - Program Limit Exceeded
+    ProgramLimitExceeded,
+
+    /// 55 - Object Not In Prerequisite State
+    ObjectNotInPrerequisiteState,
+
+    /// 57 - Operator Intervention
+    OperatorIntervention,
+
+    /// 58 - System Error
+    SystemError,
+
+    /// 72 - Snapshot Failure
+    SnapshotFailure,
+
+    /// F0 - Configuration File Error
+    ConfigurationFileError,
+
+    /// HV - Foreign Data Wrapper Error
+    ForeignDataWrapperError,
+
+    /// P0 - PL/pgSQL Error
+    PlPgsqlError,
+
+    /// XX - Internal Error
+    InternalError,
+
+    #[doc(hidden)]
+    __NonExhaustive,
+}
+
+impl SqlStateClass {
+    /// Creates a `SqlStateClass` from its two character code.
+    pub fn from_code(s: &str) -> Option<SqlStateClass> {
+        match s {
+            "00" => Some(SqlStateClass::SuccessfulCompletion),
+            "01" => Some(SqlStateClass::Warning),
+            "02" => Some(SqlStateClass::NoData),
+            "03" => Some(SqlStateClass::SqlStatementNotYetComplete),
+            "08" => Some(SqlStateClass::ConnectionException),
+            "09" => Some(SqlStateClass::TriggeredActionException),
+            "0A" => Some(SqlStateClass::FeatureNotSupported),
+            "0B" => Some(SqlStateClass::InvalidTransactionInitiation),
+            "0F" => Some(SqlStateClass::LocatorException),
+            "0L" => Some(SqlStateClass::InvalidGrantor),
+            "0P" => Some(SqlStateClass::InvalidRoleSpecification),
+            "0Z" => Some(SqlStateClass::DiagnosticsException),
+            "20" => Some(SqlStateClass::CaseNotFound),
+            "21" => Some(SqlStateClass::CardinalityViolation),
+            "22" => Some(SqlStateClass::DataException),
+            "23" => Some(SqlStateClass::IntegrityConstraintViolation),
+            "24" => Some(SqlStateClass::InvalidCursorState),
+            "25" => Some(SqlStateClass::InvalidTransactionState),
+            "26" => Some(SqlStateClass::InvalidSqlStatementName),
+            "27" => Some(SqlStateClass::TriggeredDataChangeViolation),
+            "28" => Some(SqlStateClass::InvalidAuthorizationSpecification),
+            "2B" => Some(SqlStateClass::DependentPrivilegeDescriptorsStillExist),
+            "2D" => Some(SqlStateClass::InvalidTransactionTermination),
+            "2F" => Some(SqlStateClass::SqlRoutineException),
+            "34" => Some(SqlStateClass::InvalidCursorName),
+            "38" => Some(SqlStateClass::ExternalRoutineException),
+            "39" => Some(SqlStateClass::ExternalRoutineInvocationException),
+            "3B" => Some(SqlStateClass::SavepointException),
+            "3D" => Some(SqlStateClass::InvalidCatalogName),
+            "3F" => Some(SqlStateClass::InvalidSchemaName),
+            "40" => Some(SqlStateClass::TransactionRollback),
+            "42" => Some(SqlStateClass::SyntaxErrorOrAccessRuleViolation),
+            "44" => Some(SqlStateClass::WithCheckOptionViolation),
+            "53" => Some(SqlStateClass::InsufficientResources),
+            "54" => Some(SqlStateClass::ProgramLimitExceeded),
+            "55" => Some(SqlStateClass::ObjectNotInPrerequisiteState),
+            "57" => Some(SqlStateClass::OperatorIntervention),
+            "58" => Some(SqlStateClass::SystemError),
+            "72" => Some(SqlStateClass::SnapshotFailure),
+            "F0" => Some(SqlStateClass::ConfigurationFileError),
+            "HV" => Some(SqlStateClass::ForeignDataWrapperError),
+            "P0" => Some(SqlStateClass::PlPgsqlError),
+            "XX" => Some(SqlStateClass::InternalError),
+            _ => None,
+        }
+    }
+
+    /// Returns the two character code of the class.
+    pub fn code(&self) -> &'static str {
+        match *self {
+            SqlStateClass::SuccessfulCompletion => "00",
+            SqlStateClass::Warning => "01",
+            SqlStateClass::NoData => "02",
+            SqlStateClass::SqlStatementNotYetComplete => "03",
+            SqlStateClass::ConnectionException => "08",
+            SqlStateClass::TriggeredActionException => "09",
+            SqlStateClass::FeatureNotSupported => "0A",
+            SqlStateClass::InvalidTransactionInitiation => "0B",
+            SqlStateClass::LocatorException => "0F",
+            SqlStateClass::InvalidGrantor => "0L",
+            SqlStateClass::InvalidRoleSpecification => "0P",
+            SqlStateClass::DiagnosticsException => "0Z",
+            SqlStateClass::CaseNotFound => "20",
+            SqlStateClass::CardinalityViolation => "21",
+            SqlStateClass::DataException => "22",
+            SqlStateClass::IntegrityConstraintViolation => "23",
+            SqlStateClass::InvalidCursorState => "24",
+            SqlStateClass::InvalidTransactionState => "25",
+            SqlStateClass::InvalidSqlStatementName => "26",
+            SqlStateClass::TriggeredDataChangeViolation => "27",
+            SqlStateClass::InvalidAuthorizationSpecification => "28",
+            SqlStateClass::DependentPrivilegeDescriptorsStillExist => "2B",
+            SqlStateClass::InvalidTransactionTermination => "2D",
+            SqlStateClass::SqlRoutineException => "2F",
+            SqlStateClass::InvalidCursorName => "34",
+            SqlStateClass::ExternalRoutineException => "38",
+            SqlStateClass::ExternalRoutineInvocationException => "39",
+            SqlStateClass::SavepointException => "3B",
+            SqlStateClass::InvalidCatalogName => "3D",
+            SqlStateClass::InvalidSchemaName => "3F",
+            SqlStateClass::TransactionRollback => "40",
+            SqlStateClass::SyntaxErrorOrAccessRuleViolation => "42",
+            SqlStateClass::WithCheckOptionViolation => "44",
+            SqlStateClass::InsufficientResources => "53",
+            SqlStateClass::ProgramLimitExceeded => "54",
+            SqlStateClass::ObjectNotInPrerequisiteState => "55",
+            SqlStateClass::OperatorIntervention => "57",
+            SqlStateClass::SystemError => "58",
+            SqlStateClass::SnapshotFailure => "72",
+            SqlStateClass::ConfigurationFileError => "F0",
+            SqlStateClass::ForeignDataWrapperError => "HV",
+            SqlStateClass::PlPgsqlError => "P0",
+            SqlStateClass::InternalError => "XX",
+            SqlStateClass::__NonExhaustive => unreachable!(),
+        }
+    }
+
+    /// Returns the description of the class, such as `Integrity Constraint Violation`.
+    pub fn description(&self) -> &'static str {
+        match *self {
+            SqlStateClass::SuccessfulCompletion => "Successful Completion",
+            SqlStateClass::Warning => "Warning",
+            SqlStateClass::NoData => "No Data",
+            SqlStateClass::SqlStatementNotYetComplete => "SQL Statement Not Yet Complete",
+            SqlStateClass::ConnectionException => "Connection Exception",
+            SqlStateClass::TriggeredActionException => "Triggered Action Exception",
+            SqlStateClass::FeatureNotSupported => "Feature Not Supported",
+            SqlStateClass::InvalidTransactionInitiation => "Invalid Transaction Initiation",
+            SqlStateClass::LocatorException => "Locator Exception",
+            SqlStateClass::InvalidGrantor => "Invalid Grantor",
+            SqlStateClass::InvalidRoleSpecification => "Invalid Role Specification",
+            SqlStateClass::DiagnosticsException => "Diagnostics Exception",
+            SqlStateClass::CaseNotFound => "Case Not Found",
+            SqlStateClass::CardinalityViolation => "Cardinality Violation",
+            SqlStateClass::DataException => "Data Exception",
+            SqlStateClass::IntegrityConstraintViolation => "Integrity Constraint Violation",
+            SqlStateClass::InvalidCursorState => "Invalid Cursor State",
+            SqlStateClass::InvalidTransactionState => "Invalid Transaction State",
+            SqlStateClass::InvalidSqlStatementName => "Invalid SQL Statement Name",
+            SqlStateClass::TriggeredDataChangeViolation => "Triggered Data Change Violation",
+            SqlStateClass::InvalidAuthorizationSpecification => "Invalid Authorization Specification",
+            SqlStateClass::DependentPrivilegeDescriptorsStillExist => "Dependent Privilege Descriptors Still Exist",
+            SqlStateClass::InvalidTransactionTermination => "Invalid Transaction Termination",
+            SqlStateClass::SqlRoutineException => "SQL Routine Exception",
+            SqlStateClass::InvalidCursorName => "Invalid Cursor Name",
+            SqlStateClass::ExternalRoutineException => "External Routine Exception",
+            SqlStateClass::ExternalRoutineInvocationException => "External Routine Invocation Exception",
+            SqlStateClass::SavepointException => "Savepoint Exception",
+            SqlStateClass::InvalidCatalogName => "Invalid Catalog Name",
+            SqlStateClass::InvalidSchemaName => "Invalid Schema Name",
+            SqlStateClass::TransactionRollback => "Transaction Rollback",
+            SqlStateClass::SyntaxErrorOrAccessRuleViolation => "Syntax Error or Access Rule Violation",
+            SqlStateClass::WithCheckOptionViolation => "WITH CHECK OPTION Violation",
+            SqlStateClass::InsufficientResources => "Insufficient Resources",
+            SqlStateClass::ProgramLimitExceeded => "Program Limit Exceeded",
+            SqlStateClass::ObjectNotInPrerequisiteState => "Object Not In Prerequisite State",
+            SqlStateClass::OperatorIntervention => "Operator Intervention",
+            SqlStateClass::SystemError => "System Error",
+            SqlStateClass::SnapshotFailure => "Snapshot Failure",
+            SqlStateClass::ConfigurationFileError => "Configuration File Error",
+            SqlStateClass::ForeignDataWrapperError => "Foreign Data Wrapper Error",
+            SqlStateClass::PlPgsqlError => "PL/pgSQL Error",
+            SqlStateClass::InternalError => "Internal Error",
+            SqlStateClass::__NonExhaustive => unreachable!(),
+        }
+    }
+}
+
 #[cfg_attr(rustfmt, rustfmt_skip)]
 static SQLSTATE_MAP: phf::Map<&'static str, SqlState> = ::phf::Map {
     key: 1897749892740154578,
@@ -1059,3 +1359,250 @@ static SQLSTATE_MAP: phf::Map<&'static str, SqlState> = ::phf::Map {
         ("57P03", SqlState::CANNOT_CONNECT_NOW),
     ]),
 };
+
+fn condition_name(code: &str) -> Option<&'static str> {
+    match code {
+        "00000" => Some("successful_completion"),
+        "01000" => Some("warning"),
+        "0100C" => Some("dynamic_result_sets_returned"),
+        "01008" => Some("implicit_zero_bit_padding"),
+        "01003" => Some("null_value_eliminated_in_set_function"),
+        "01007" => Some("privilege_not_granted"),
+        "01006" => Some("privilege_not_revoked"),
+        "01004" => Some("string_data_right_truncation"),
+        "01P01" => Some("deprecated_feature"),
+        "02000" => Some("no_data"),
+        "02001" => Some("no_additional_dynamic_result_sets_returned"),
+        "03000" => Some("sql_statement_not_yet_complete"),
+        "08000" => Some("connection_exception"),
+        "08003" => Some("connection_does_not_exist"),
+        "08006" => Some("connection_failure"),
+        "08001" => Some("sqlclient_unable_to_establish_sqlconnection"),
+        "08004" => Some("sqlserver_rejected_establishment_of_sqlconnection"),
+        "08007" => Some("transaction_resolution_unknown"),
+        "08P01" => Some("protocol_violation"),
+        "09000" => Some("triggered_action_exception"),
+        "0A000" => Some("feature_not_supported"),
+        "0B000" => Some("invalid_transaction_initiation"),
+        "0F000" => Some("locator_exception"),
+        "0F001" => Some("invalid_locator_specification"),
+        "0L000" => Some("invalid_grantor"),
+        "0LP01" => Some("invalid_grant_operation"),
+        "0P000" => Some("invalid_role_specification"),
+        "0Z000" => Some("diagnostics_exception"),
+        "0Z002" => Some("stacked_diagnostics_accessed_without_active_handler"),
+        "20000" => Some("case_not_found"),
+        "21000" => Some("cardinality_violation"),
+        "22000" => Some("data_exception"),
+        "2202E" => Some("array_subscript_error"),
+        "22021" => Some("character_not_in_repertoire"),
+        "22008" => Some("datetime_field_overflow"),
+        "22012" => Some("division_by_zero"),
+        "22005" => Some("error_in_assignment"),
+        "2200B" => Some("escape_character_conflict"),
+        "22022" => Some("indicator_overflow"),
+        "22015" => Some("interval_field_overflow"),
+        "2201E" => Some("invalid_argument_for_logarithm"),
+        "22014" => Some("invalid_argument_for_ntile_function"),
+        "22016" => Some("invalid_argument_for_nth_value_function"),
+        "2201F" => Some("invalid_argument_for_power_function"),
+        "2201G" => Some("invalid_argument_for_width_bucket_function"),
+        "22018" => Some("invalid_character_value_for_cast"),
+        "22007" => Some("invalid_datetime_format"),
+        "22019" => Some("invalid_escape_character"),
+        "2200D" => Some("invalid_escape_octet"),
+        "22025" => Some("invalid_escape_sequence"),
+        "22P06" => Some("nonstandard_use_of_escape_character"),
+        "22010" => Some("invalid_indicator_parameter_value"),
+        "22023" => Some("invalid_parameter_value"),
+        "2201B" => Some("invalid_regular_expression"),
+        "2201W" => Some("invalid_row_count_in_limit_clause"),
+        "2201X" => Some("invalid_row_count_in_result_offset_clause"),
+        "2202H" => Some("invalid_tablesample_argument"),
+        "2202G" => Some("invalid_tablesample_repeat"),
+        "22009" => Some("invalid_time_zone_displacement_value"),
+        "2200C" => Some("invalid_use_of_escape_character"),
+        "2200G" => Some("most_specific_type_mismatch"),
+        "22004" => Some("null_value_not_allowed"),
+        "22002" => Some("null_value_no_indicator_parameter"),
+        "22003" => Some("numeric_value_out_of_range"),
+        "2200H" => Some("sequence_generator_limit_exceeded"),
+        "22026" => Some("string_data_length_mismatch"),
+        "22001" => Some("string_data_right_truncation"),
+        "22011" => Some("substring_error"),
+        "22027" => Some("trim_error"),
+        "22024" => Some("unterminated_c_string"),
+        "2200F" => Some("zero_length_character_string"),
+        "22P01" => Some("floating_point_exception"),
+        "22P02" => Some("invalid_text_representation"),
+        "22P03" => Some("invalid_binary_representation"),
+        "22P04" => Some("bad_copy_file_format"),
+        "22P05" => Some("untranslatable_character"),
+        "2200L" => Some("not_an_xml_document"),
+        "2200M" => Some("invalid_xml_document"),
+        "2200N" => Some("invalid_xml_content"),
+        "2200S" => Some("invalid_xml_comment"),
+        "2200T" => Some("invalid_xml_processing_instruction"),
+        "23000" => Some("integrity_constraint_violation"),
+        "23001" => Some("restrict_violation"),
+        "23502" => Some("not_null_violation"),
+        "23503" => Some("foreign_key_violation"),
+        "23505" => Some("unique_violation"),
+        "23514" => Some("check_violation"),
+        "23P01" => Some("exclusion_violation"),
+        "24000" => Some("invalid_cursor_state"),
+        "25000" => Some("invalid_transaction_state"),
+        "25001" => Some("active_sql_transaction"),
+        "25002" => Some("branch_transaction_already_active"),
+        "25008" => Some("held_cursor_requires_same_isolation_level"),
+        "25003" => Some("inappropriate_access_mode_for_branch_transaction"),
+        "25004" => Some("inappropriate_isolation_level_for_branch_transaction"),
+        "25005" => Some("no_active_sql_transaction_for_branch_transaction"),
+        "25006" => Some("read_only_sql_transaction"),
+        "25007" => Some("schema_and_data_statement_mixing_not_supported"),
+        "25P01" => Some("no_active_sql_transaction"),
+        "25P02" => Some("in_failed_sql_transaction"),
+        "25P03" => Some("idle_in_transaction_session_timeout"),
+        "26000" => Some("invalid_sql_statement_name"),
+        "27000" => Some("triggered_data_change_violation"),
+        "28000" => Some("invalid_authorization_specification"),
+        "28P01" => Some("invalid_password"),
+        "2B000" => Some("dependent_privilege_descriptors_still_exist"),
+        "2BP01" => Some("dependent_objects_still_exist"),
+        "2D000" => Some("invalid_transaction_termination"),
+        "2F000" => Some("sql_routine_exception"),
+        "2F005" => Some("function_executed_no_return_statement"),
+        "2F002" => Some("modifying_sql_data_not_permitted"),
+        "2F003" => Some("prohibited_sql_statement_attempted"),
+        "2F004" => Some("reading_sql_data_not_permitted"),
+        "34000" => Some("invalid_cursor_name"),
+        "38000" => Some("external_routine_exception"),
+        "38001" => Some("containing_sql_not_permitted"),
+        "38002" => Some("modifying_sql_data_not_permitted"),
+        "38003" => Some("prohibited_sql_statement_attempted"),
+        "38004" => Some("reading_sql_data_not_permitted"),
+        "39000" => Some("external_routine_invocation_exception"),
+        "39001" => Some("invalid_sqlstate_returned"),
+        "39004" => Some("null_value_not_allowed"),
+        "39P01" => Some("trigger_protocol_violated"),
+        "39P02" => Some("srf_protocol_violated"),
+        "39P03" => Some("event_trigger_protocol_violated"),
+        "3B000" => Some("savepoint_exception"),
+        "3B001" => Some("invalid_savepoint_specification"),
+        "3D000" => Some("invalid_catalog_name"),
+        "3F000" => Some("invalid_schema_name"),
+        "40000" => Some("transaction_rollback"),
+        "40002" => Some("transaction_integrity_constraint_violation"),
+        "40001" => Some("serialization_failure"),
+        "40003" => Some("statement_completion_unknown"),
+        "40P01" => Some("deadlock_detected"),
+        "42000" => Some("syntax_error_or_access_rule_violation"),
+        "42601" => Some("syntax_error"),
+        "42501" => Some("insufficient_privilege"),
+        "42846" => Some("cannot_coerce"),
+        "42803" => Some("grouping_error"),
+        "42P20" => Some("windowing_error"),
+        "42P19" => Some("invalid_recursion"),
+        "42830" => Some("invalid_foreign_key"),
+        "42602" => Some("invalid_name"),
+        "42622" => Some("name_too_long"),
+        "42939" => Some("reserved_name"),
+        "42804" => Some("datatype_mismatch"),
+        "42P18" => Some("indeterminate_datatype"),
+        "42P21" => Some("collation_mismatch"),
+        "42P22" => Some("indeterminate_collation"),
+        "42809" => Some("wrong_object_type"),
+        "428C9" => Some("generated_always"),
+        "42703" => Some("undefined_column"),
+        "42883" => Some("undefined_function"),
+        "42P01" => Some("undefined_table"),
+        "42P02" => Some("undefined_parameter"),
+        "42704" => Some("undefined_object"),
+        "42701" => Some("duplicate_column"),
+        "42P03" => Some("duplicate_cursor"),
+        "42P04" => Some("duplicate_database"),
+        "42723" => Some("duplicate_function"),
+        "42P05" => Some("duplicate_prepared_statement"),
+        "42P06" => Some("duplicate_schema"),
+        "42P07" => Some("duplicate_table"),
+        "42712" => Some("duplicate_alias"),
+        "42710" => Some("duplicate_object"),
+        "42702" => Some("ambiguous_column"),
+        "42725" => Some("ambiguous_function"),
+        "42P08" => Some("ambiguous_parameter"),
+        "42P09" => Some("ambiguous_alias"),
+        "42P10" => Some("invalid_column_reference"),
+        "42611" => Some("invalid_column_definition"),
+        "42P11" => Some("invalid_cursor_definition"),
+        "42P12" => Some("invalid_database_definition"),
+        "42P13" => Some("invalid_function_definition"),
+        "42P14" => Some("invalid_prepared_statement_definition"),
+        "42P15" => Some("invalid_schema_definition"),
+        "42P16" => Some("invalid_table_definition"),
+        "42P17" => Some("invalid_object_definition"),
+        "44000" => Some("with_check_option_violation"),
+        "53000" => Some("insufficient_resources"),
+        "53100" => Some("disk_full"),
+        "53200" => Some("out_of_memory"),
+        "53300" => Some("too_many_connections"),
+        "53400" => Some("configuration_limit_exceeded"),
+        "54000" => Some("program_limit_exceeded"),
+        "54001" => Some("statement_too_complex"),
+        "54011" => Some("too_many_columns"),
+        "54023" => Some("too_many_arguments"),
+        "55000" => Some("object_not_in_prerequisite_state"),
+        "55006" => Some("object_in_use"),
+        "55P02" => Some("cant_change_runtime_param"),
+        "55P03" => Some("lock_not_available"),
+        "55P04" => Some("unsafe_new_enum_value_usage"),
+        "57000" => Some("operator_intervention"),
+        "57014" => Some("query_canceled"),
+        "57P01" => Some("admin_shutdown"),
+        "57P02" => Some("crash_shutdown"),
+        "57P03" => Some("cannot_connect_now"),
+        "57P04" => Some("database_dropped"),
+        "58000" => Some("system_error"),
+        "58030" => Some("io_error"),
+        "58P01" => Some("undefined_file"),
+        "58P02" => Some("duplicate_file"),
+        "72000" => Some("snapshot_too_old"),
+        "F0000" => Some("config_file_error"),
+        "F0001" => Some("lock_file_exists"),
+        "HV000" => Some("fdw_error"),
+        "HV005" => Some("fdw_column_name_not_found"),
+        "HV002" => Some("fdw_dynamic_parameter_value_needed"),
+        "HV010" => Some("fdw_function_sequence_error"),
+        "HV021" => Some("fdw_inconsistent_descriptor_information"),
+        "HV024" => Some("fdw_invalid_attribute_value"),
+        "HV007" => Some("fdw_invalid_column_name"),
+        "HV008" => Some("fdw_invalid_column_number"),
+        "HV004" => Some("fdw_invalid_data_type"),
+        "HV006" => Some("fdw_invalid_data_type_descriptors"),
+        "HV091" => Some("fdw_invalid_descriptor_field_identifier"),
+        "HV00B" => Some("fdw_invalid_handle"),
+        "HV00C" => Some("fdw_invalid_option_index"),
+        "HV00D" => Some("fdw_invalid_option_name"),
+        "HV090" => Some("fdw_invalid_string_length_or_buffer_length"),
+        "HV00A" => Some("fdw_invalid_string_format"),
+        "HV009" => Some("fdw_invalid_use_of_null_pointer"),
+        "HV014" => Some("fdw_too_many_handles"),
+        "HV001" => Some("fdw_out_of_memory"),
+        "HV00P" => Some("fdw_no_schemas"),
+        "HV00J" => Some("fdw_option_name_not_found"),
+        "HV00K" => Some("fdw_reply_handle"),
+        "HV00Q" => Some("fdw_schema_not_found"),
+        "HV00R" => Some("fdw_table_not_found"),
+        "HV00L" => Some("fdw_unable_to_create_execution"),
+        "HV00M" => Some("fdw_unable_to_create_reply"),
+        "HV00N" => Some("fdw_unable_to_establish_connection"),
+        "P0000" => Some("plpgsql_error"),
+        "P0001" => Some("raise_exception"),
+        "P0002" => Some("no_data_found"),
+        "P0003" => Some("too_many_rows"),
+        "P0004" => Some("assert_failure"),
+        "XX000" => Some("internal_error"),
+        "XX001" => Some("data_corrupted"),
+        "XX002" => Some("index_corrupted"),
+        _ => None,
+    }
+}
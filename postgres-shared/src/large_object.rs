@@ -0,0 +1,30 @@
+//! Types shared by the large object APIs of both clients.
+
+// from libpq-fs.h
+const INV_WRITE: i32 = 0x0002_0000;
+const INV_READ: i32 = 0x0004_0000;
+
+/// The access mode of an open large object.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Mode {
+    /// The large object may only be read from.
+    ///
+    /// Reads see the contents of the object as of the snapshot of the transaction that opened
+    /// it, regardless of later writes.
+    Read,
+    /// The large object may only be written to.
+    Write,
+    /// The large object may be read from and written to.
+    ReadWrite,
+}
+
+impl Mode {
+    #[doc(hidden)]
+    pub fn to_flags(self) -> i32 {
+        match self {
+            Mode::Read => INV_READ,
+            Mode::Write => INV_WRITE,
+            Mode::ReadWrite => INV_READ | INV_WRITE,
+        }
+    }
+}
@@ -1,15 +1,38 @@
 #![allow(unknown_lints)] // for clippy
 
+extern crate byteorder;
 extern crate hex;
 extern crate fallible_iterator;
 extern crate phf;
 extern crate postgres_protocol;
 
+use std::time::SystemTime;
+
+use error::DbError;
+
+pub mod advisory_lock;
+pub mod binary_copy;
+pub mod cursor;
 pub mod error;
+pub mod large_object;
 pub mod params;
 pub mod types;
 pub mod rows;
 pub mod stmt;
+pub mod text_copy;
+
+// Backslashes are only literal in standard strings when
+// standard_conforming_strings is on, so use an escape string if there are any,
+// as PQescapeLiteral does.
+#[doc(hidden)]
+pub fn quote_literal(s: &str) -> String {
+    let s = s.replace('\'', "''");
+    if s.contains('\\') {
+        format!("E'{}'", s.replace('\\', "\\\\"))
+    } else {
+        format!("'{}'", s)
+    }
+}
 
 /// Contains information necessary to cancel queries for a session.
 #[derive(Copy, Clone, Debug)]
@@ -30,3 +53,38 @@ pub struct Notification {
     /// The "payload" string passed from the notifying process.
     pub payload: String,
 }
+
+/// A transaction prepared for two-phase commit, as listed in `pg_prepared_xacts`.
+#[derive(Clone, Debug)]
+pub struct PreparedTransaction {
+    /// The numeric identifier of the transaction.
+    pub transaction: u32,
+    /// The global identifier assigned to the transaction when it was prepared.
+    pub gid: String,
+    /// The time at which the transaction was prepared.
+    pub prepared: SystemTime,
+    /// The name of the user that executed the transaction.
+    pub owner: String,
+    /// The name of the database in which the transaction was executed.
+    pub database: String,
+}
+
+/// The result of a statement along with the notices the server raised while executing it.
+#[derive(Clone, Debug)]
+pub struct WithNotices<T> {
+    /// The result of the statement.
+    pub value: T,
+    /// The notices raised by the statement, in the order they were received.
+    pub notices: Vec<DbError>,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn quote_literal_escapes() {
+        assert_eq!(quote_literal("it's"), "'it''s'");
+        assert_eq!(quote_literal("a\\b"), "E'a\\\\b'");
+    }
+}
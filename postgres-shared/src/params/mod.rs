@@ -38,6 +38,26 @@ impl User {
     }
 }
 
+/// The kind of replication connection to open.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Replication {
+    /// A physical replication connection, which can stream WAL and take base backups.
+    Physical,
+    /// A logical replication connection to the specified database, which can stream decoded
+    /// changes from a logical replication slot.
+    Logical,
+}
+
+impl Replication {
+    #[doc(hidden)]
+    pub fn startup_value(&self) -> &'static str {
+        match *self {
+            Replication::Physical => "true",
+            Replication::Logical => "database",
+        }
+    }
+}
+
 /// Information necessary to open a new connection to a Postgres server.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct ConnectParams {
@@ -48,6 +68,7 @@ pub struct ConnectParams {
     options: Vec<(String, String)>,
     connect_timeout: Option<Duration>,
     keepalive: Option<Duration>,
+    replication: Option<Replication>,
 }
 
 impl ConnectParams {
@@ -96,13 +117,21 @@ impl ConnectParams {
     pub fn keepalive(&self) -> Option<Duration> {
         self.keepalive
     }
+
+    /// The kind of replication connection to open, if any.
+    ///
+    /// Replication connections accept the replication commands (e.g. `IDENTIFY_SYSTEM`) in the
+    /// simple query protocol.
+    pub fn replication(&self) -> Option<Replication> {
+        self.replication
+    }
 }
 
 impl FromStr for ConnectParams {
     type Err = error::Error;
 
     fn from_str(s: &str) -> Result<ConnectParams, error::Error> {
-        s.into_connect_params().map_err(error::connect)
+        s.into_connect_params().map_err(error::Error::connect_params)
     }
 }
 
@@ -114,6 +143,7 @@ pub struct Builder {
     options: Vec<(String, String)>,
     connect_timeout: Option<Duration>,
     keepalive: Option<Duration>,
+    replication: Option<Replication>,
 }
 
 impl Builder {
@@ -126,6 +156,7 @@ impl Builder {
             options: vec![],
             connect_timeout: None,
             keepalive: None,
+            replication: None,
         }
     }
 
@@ -168,6 +199,12 @@ impl Builder {
         self
     }
 
+    /// Sets the kind of replication connection to open.
+    pub fn replication(&mut self, replication: Option<Replication>) -> &mut Builder {
+        self.replication = replication;
+        self
+    }
+
     /// Constructs a `ConnectParams` from the builder.
     pub fn build(&mut self, host: Host) -> ConnectParams {
         ConnectParams {
@@ -178,6 +215,7 @@ impl Builder {
             options: mem::replace(&mut self.options, vec![]),
             connect_timeout: self.connect_timeout,
             keepalive: self.keepalive,
+            replication: self.replication,
         }
     }
 }
@@ -251,6 +289,15 @@ impl IntoConnectParams for Url {
                     let keepalive = Duration::from_secs(keepalive);
                     builder.keepalive(Some(keepalive));
                 }
+                "replication" => {
+                    let replication = match &*value {
+                        "database" => Some(Replication::Logical),
+                        "true" | "on" | "yes" | "1" => Some(Replication::Physical),
+                        "false" | "off" | "no" | "0" => None,
+                        _ => return Err("invalid replication".into()),
+                    };
+                    builder.replication(replication);
+                }
                 _ => {
                     builder.option(&name, &value);
                 }
@@ -291,5 +338,22 @@ mod test {
             &[("application_name".to_string(), "foo".to_string())][..]
         );
         assert_eq!(params.connect_timeout(), Some(Duration::from_secs(10)));
+        assert_eq!(params.replication(), None);
+    }
+
+    #[test]
+    fn parse_replication() {
+        let params = "postgres://user@host/dbname?replication=database";
+        let params = params.into_connect_params().unwrap();
+        assert_eq!(params.replication(), Some(Replication::Logical));
+        assert!(params.options().is_empty());
+
+        let params = "postgres://user@host?replication=true";
+        let params = params.into_connect_params().unwrap();
+        assert_eq!(params.replication(), Some(Replication::Physical));
+
+        assert!("postgres://user@host?replication=foo"
+            .into_connect_params()
+            .is_err());
     }
 }
@@ -1,10 +1,48 @@
-use types::Type;
+use postgres_protocol::message::backend::Field;
+use std::io;
+
+use types::{Kind, Oid, Type};
+
+// the size of a varlena header, included in the type modifiers of some types
+const VARHDRSZ: i32 = 4;
+
+/// The format of a value transferred to or from the Postgres server.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Format {
+    /// A text based format.
+    Text,
+    /// A binary format.
+    Binary,
+}
+
+impl Format {
+    #[doc(hidden)]
+    pub fn from_u16(value: u16) -> Format {
+        match value {
+            0 => Format::Text,
+            _ => Format::Binary,
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn code(self) -> i16 {
+        match self {
+            Format::Text => 0,
+            Format::Binary => 1,
+        }
+    }
+}
 
 /// Information about a column of a Postgres query.
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct Column {
     name: String,
     type_: Type,
+    format: Format,
+    table_oid: Option<Oid>,
+    column_id: Option<i16>,
+    type_size: i16,
+    type_modifier: i32,
 }
 
 impl Column {
@@ -13,6 +51,11 @@ impl Column {
         Column {
             name: name,
             type_: type_,
+            format: Format::Binary,
+            table_oid: None,
+            column_id: None,
+            type_size: -1,
+            type_modifier: -1,
         }
     }
 
@@ -25,4 +68,256 @@ impl Column {
     pub fn type_(&self) -> &Type {
         &self.type_
     }
+
+    /// Returns the format in which the column's values are retrieved.
+    ///
+    /// Values are retrieved in the binary format unless the statement was configured otherwise.
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Returns the OID of the table the column's values are taken from, if they come directly
+    /// from a table column.
+    pub fn table_oid(&self) -> Option<Oid> {
+        self.table_oid
+    }
+
+    /// Returns the attribute number of the table column the column's values are taken from, if
+    /// they come directly from a table column.
+    pub fn column_id(&self) -> Option<i16> {
+        self.column_id
+    }
+
+    /// Returns the size of the column's type in bytes, as stored in `pg_type.typlen`.
+    ///
+    /// Negative values indicate variable-width types.
+    pub fn type_size(&self) -> i16 {
+        self.type_size
+    }
+
+    /// Returns the type modifier of the column, as stored in `pg_attribute.atttypmod`.
+    ///
+    /// The meaning of the modifier depends on the type; -1 indicates that there is none. The
+    /// `character_maximum_length`, `numeric_precision`, `numeric_scale` and
+    /// `datetime_precision` methods decode it for the built-in types which use it.
+    pub fn type_modifier(&self) -> i32 {
+        self.type_modifier
+    }
+
+    // type modifiers of array columns apply to their elements
+    fn base_type(&self) -> &Type {
+        match *self.type_.kind() {
+            Kind::Array(ref member) => member,
+            _ => &self.type_,
+        }
+    }
+
+    /// Returns the declared maximum length of a `CHAR(n)`, `VARCHAR(n)`, `BIT(n)` or
+    /// `VARBIT(n)` column.
+    pub fn character_maximum_length(&self) -> Option<i32> {
+        match *self.base_type() {
+            Type::BPCHAR | Type::VARCHAR if self.type_modifier >= VARHDRSZ => {
+                Some(self.type_modifier - VARHDRSZ)
+            }
+            Type::BIT | Type::VARBIT if self.type_modifier >= 0 => Some(self.type_modifier),
+            _ => None,
+        }
+    }
+
+    /// Returns the declared precision of a `NUMERIC(p, s)` column.
+    pub fn numeric_precision(&self) -> Option<i32> {
+        match *self.base_type() {
+            Type::NUMERIC if self.type_modifier >= VARHDRSZ => {
+                Some(((self.type_modifier - VARHDRSZ) >> 16) & 0xffff)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the declared scale of a `NUMERIC(p, s)` column.
+    ///
+    /// The scale may be negative on Postgres 15 and later.
+    pub fn numeric_scale(&self) -> Option<i32> {
+        match *self.base_type() {
+            Type::NUMERIC if self.type_modifier >= VARHDRSZ => {
+                // the scale is stored as an 11 bit two's complement integer
+                Some((((self.type_modifier - VARHDRSZ) & 0x7ff) ^ 0x400) - 0x400)
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns the declared fractional seconds precision of a `TIME(p)`, `TIMETZ(p)`,
+    /// `TIMESTAMP(p)`, `TIMESTAMPTZ(p)` or `INTERVAL(p)` column.
+    pub fn datetime_precision(&self) -> Option<i32> {
+        match *self.base_type() {
+            Type::TIME | Type::TIMETZ | Type::TIMESTAMP | Type::TIMESTAMPTZ
+                if self.type_modifier >= 0 =>
+            {
+                Some(self.type_modifier)
+            }
+            // the upper half of an interval's modifier holds its field restriction
+            Type::INTERVAL if self.type_modifier >= 0 => match self.type_modifier & 0xffff {
+                0xffff => None,
+                precision => Some(precision),
+            },
+            _ => None,
+        }
+    }
+}
+
+/// The description of a column before its type has been looked up.
+#[doc(hidden)]
+pub struct ColumnDescription {
+    name: String,
+    table_oid: Oid,
+    column_id: i16,
+    type_oid: Oid,
+    type_size: i16,
+    type_modifier: i32,
+}
+
+impl ColumnDescription {
+    pub fn new(field: &Field) -> ColumnDescription {
+        ColumnDescription {
+            name: field.name().to_string(),
+            table_oid: field.table_oid(),
+            column_id: field.column_id(),
+            type_oid: field.type_oid(),
+            type_size: field.type_size(),
+            type_modifier: field.type_modifier(),
+        }
+    }
+
+    pub fn type_oid(&self) -> Oid {
+        self.type_oid
+    }
+
+    pub fn into_column(self, type_: Type) -> Column {
+        Column {
+            name: self.name,
+            type_: type_,
+            format: Format::Binary,
+            table_oid: if self.table_oid == 0 {
+                None
+            } else {
+                Some(self.table_oid)
+            },
+            column_id: if self.column_id == 0 {
+                None
+            } else {
+                Some(self.column_id)
+            },
+            type_size: self.type_size,
+            type_modifier: self.type_modifier,
+        }
+    }
+}
+
+/// Returns the result format codes to send in a `Bind` message for the columns.
+#[doc(hidden)]
+pub fn result_format_codes(columns: &[Column]) -> Vec<i16> {
+    if columns.iter().all(|c| c.format == Format::Binary) {
+        // a single code applies to every column
+        vec![Format::Binary.code()]
+    } else {
+        columns.iter().map(|c| c.format.code()).collect()
+    }
+}
+
+/// Returns a copy of the columns with their result formats changed.
+///
+/// A single format applies to every column. An error is returned if more than one format is
+/// provided and the number of formats does not match the number of columns.
+#[doc(hidden)]
+pub fn with_result_formats(columns: &[Column], formats: &[Format]) -> io::Result<Vec<Column>> {
+    if formats.len() != 1 && formats.len() != columns.len() {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            format!(
+                "expected 1 or {} result formats but got {}",
+                columns.len(),
+                formats.len()
+            ),
+        ));
+    }
+
+    let columns = columns
+        .iter()
+        .enumerate()
+        .map(|(i, c)| {
+            let mut c = c.clone();
+            c.format = if formats.len() == 1 {
+                formats[0]
+            } else {
+                formats[i]
+            };
+            c
+        }).collect();
+    Ok(columns)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn result_formats() {
+        let columns = vec![
+            Column::new("a".to_string(), Type::INT4),
+            Column::new("b".to_string(), Type::TEXT),
+        ];
+        assert_eq!(result_format_codes(&columns), [1]);
+
+        let columns = with_result_formats(&columns, &[Format::Text]).unwrap();
+        assert_eq!(result_format_codes(&columns), [0, 0]);
+
+        let columns = with_result_formats(&columns, &[Format::Binary, Format::Text]).unwrap();
+        assert_eq!(result_format_codes(&columns), [1, 0]);
+
+        assert!(with_result_formats(&columns, &[]).is_err());
+        assert!(with_result_formats(&columns, &[Format::Text; 3]).is_err());
+    }
+
+    fn column(type_: Type, type_modifier: i32) -> Column {
+        let mut column = Column::new("a".to_string(), type_);
+        column.type_modifier = type_modifier;
+        column
+    }
+
+    #[test]
+    fn type_modifiers() {
+        // VARCHAR(10)
+        assert_eq!(
+            column(Type::VARCHAR, 14).character_maximum_length(),
+            Some(10)
+        );
+        assert_eq!(
+            column(Type::VARCHAR_ARRAY, 14).character_maximum_length(),
+            Some(10)
+        );
+        assert_eq!(column(Type::VARCHAR, -1).character_maximum_length(), None);
+        assert_eq!(column(Type::VARBIT, 3).character_maximum_length(), Some(3));
+
+        // NUMERIC(10, 2)
+        let numeric = column(Type::NUMERIC, ((10 << 16) | 2) + 4);
+        assert_eq!(numeric.numeric_precision(), Some(10));
+        assert_eq!(numeric.numeric_scale(), Some(2));
+        // NUMERIC(3, -2)
+        let numeric = column(Type::NUMERIC, ((3 << 16) | 0x7fe) + 4);
+        assert_eq!(numeric.numeric_scale(), Some(-2));
+        assert_eq!(column(Type::NUMERIC, -1).numeric_precision(), None);
+
+        assert_eq!(column(Type::TIMESTAMPTZ, 3).datetime_precision(), Some(3));
+        assert_eq!(column(Type::TIMESTAMPTZ, -1).datetime_precision(), None);
+        // INTERVAL DAY TO SECOND(2)
+        assert_eq!(
+            column(Type::INTERVAL, (0x0e00 << 16) | 2).datetime_precision(),
+            Some(2)
+        );
+        assert_eq!(
+            column(Type::INTERVAL, (0x7fff << 16) | 0xffff).datetime_precision(),
+            None
+        );
+    }
 }
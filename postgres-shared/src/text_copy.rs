@@ -0,0 +1,660 @@
+//! Encoding and decoding of the `text` and `csv` `COPY` formats.
+//!
+//! Values are represented as strings, with `None` corresponding to a SQL `NULL`. The options must
+//! match those used in the `COPY` statement.
+use std::io;
+use std::str;
+
+/// The format of textual `COPY` data.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TextCopyFormat {
+    /// The `text` format.
+    Text,
+    /// The `csv` format.
+    Csv,
+}
+
+/// Options controlling the textual `COPY` formats.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextCopyOptions {
+    format: TextCopyFormat,
+    delimiter: u8,
+    null: String,
+    quote: u8,
+    escape: u8,
+    header: bool,
+}
+
+impl TextCopyOptions {
+    /// Returns the default options for the `text` format.
+    ///
+    /// Columns are delimited by tabs and `NULL` is represented by `\N`.
+    pub fn text() -> TextCopyOptions {
+        TextCopyOptions {
+            format: TextCopyFormat::Text,
+            delimiter: b'\t',
+            null: "\\N".to_string(),
+            quote: b'"',
+            escape: b'"',
+            header: false,
+        }
+    }
+
+    /// Returns the default options for the `csv` format.
+    ///
+    /// Columns are delimited by commas, values are quoted with `"`, and `NULL` is represented by
+    /// an unquoted empty string.
+    pub fn csv() -> TextCopyOptions {
+        TextCopyOptions {
+            format: TextCopyFormat::Csv,
+            delimiter: b',',
+            null: String::new(),
+            quote: b'"',
+            escape: b'"',
+            header: false,
+        }
+    }
+
+    /// Returns the format of the data.
+    pub fn format(&self) -> TextCopyFormat {
+        self.format
+    }
+
+    /// Sets the character separating columns.
+    ///
+    /// It must be a single-byte character.
+    pub fn delimiter(&mut self, delimiter: u8) -> &mut TextCopyOptions {
+        self.delimiter = delimiter;
+        self
+    }
+
+    /// Returns the character separating columns.
+    pub fn get_delimiter(&self) -> u8 {
+        self.delimiter
+    }
+
+    /// Sets the string representing a `NULL` value.
+    pub fn null(&mut self, null: &str) -> &mut TextCopyOptions {
+        self.null = null.to_string();
+        self
+    }
+
+    /// Returns the string representing a `NULL` value.
+    pub fn get_null(&self) -> &str {
+        &self.null
+    }
+
+    /// Sets the quoting character.
+    ///
+    /// This only applies to the `csv` format.
+    pub fn quote(&mut self, quote: u8) -> &mut TextCopyOptions {
+        self.quote = quote;
+        self
+    }
+
+    /// Returns the quoting character.
+    pub fn get_quote(&self) -> u8 {
+        self.quote
+    }
+
+    /// Sets the character escaping quote characters inside of quoted values.
+    ///
+    /// This only applies to the `csv` format, and defaults to the quote character.
+    pub fn escape(&mut self, escape: u8) -> &mut TextCopyOptions {
+        self.escape = escape;
+        self
+    }
+
+    /// Returns the character escaping quote characters inside of quoted values.
+    pub fn get_escape(&self) -> u8 {
+        self.escape
+    }
+
+    /// Sets whether the data starts with a header line containing the column names.
+    pub fn header(&mut self, header: bool) -> &mut TextCopyOptions {
+        self.header = header;
+        self
+    }
+
+    /// Returns whether the data starts with a header line.
+    pub fn get_header(&self) -> bool {
+        self.header
+    }
+}
+
+/// An encoder of rows into a textual `COPY` format.
+#[derive(Debug)]
+pub struct TextCopyEncoder {
+    options: TextCopyOptions,
+}
+
+impl TextCopyEncoder {
+    /// Creates a new encoder with the specified options.
+    pub fn new(options: &TextCopyOptions) -> TextCopyEncoder {
+        TextCopyEncoder {
+            options: options.clone(),
+        }
+    }
+
+    /// Returns the encoder's options.
+    pub fn options(&self) -> &TextCopyOptions {
+        &self.options
+    }
+
+    /// Appends a header line containing the specified column names to `buf`.
+    ///
+    /// This should be called before any rows are encoded, and only if the header option is set.
+    pub fn encode_header(&self, names: &[&str], buf: &mut Vec<u8>) {
+        for (i, name) in names.iter().enumerate() {
+            if i != 0 {
+                buf.push(self.options.delimiter);
+            }
+            // the server discards the header line, so names don't need to be distinguished from
+            // NULL
+            match self.options.format {
+                TextCopyFormat::Text => self.encode_text(name.as_bytes(), buf),
+                TextCopyFormat::Csv => self.encode_csv(name, buf),
+            }
+        }
+        buf.push(b'\n');
+    }
+
+    /// Appends a row to `buf`.
+    ///
+    /// Returns an error if a value cannot be distinguished from `NULL`, which is the case for an
+    /// empty string in the `text` format when `NULL` is also represented by an empty string. On
+    /// error, `buf` is left unchanged.
+    pub fn encode_row<T>(&self, row: &[Option<T>], buf: &mut Vec<u8>) -> io::Result<()>
+    where
+        T: AsRef<str>,
+    {
+        let start = buf.len();
+        for (i, value) in row.iter().enumerate() {
+            if i != 0 {
+                buf.push(self.options.delimiter);
+            }
+            let r = match *value {
+                Some(ref value) => self.encode_value(value.as_ref(), buf),
+                None => {
+                    buf.extend_from_slice(self.options.null.as_bytes());
+                    Ok(())
+                }
+            };
+            if let Err(e) = r {
+                buf.truncate(start);
+                return Err(e);
+            }
+        }
+        buf.push(b'\n');
+        Ok(())
+    }
+
+    fn encode_value(&self, value: &str, buf: &mut Vec<u8>) -> io::Result<()> {
+        match self.options.format {
+            TextCopyFormat::Text => {
+                if value.is_empty() && self.options.null.is_empty() {
+                    return Err(io::Error::new(
+                        io::ErrorKind::InvalidInput,
+                        "an empty string cannot be distinguished from NULL",
+                    ));
+                }
+
+                let start = buf.len();
+                self.encode_text(value.as_bytes(), buf);
+                // the server compares the raw text against the NULL string before processing
+                // escapes, so escaping the first byte in octal keeps the value from matching it
+                if buf[start..] == *self.options.null.as_bytes() {
+                    buf.truncate(start);
+                    let first = value.as_bytes()[0];
+                    buf.extend_from_slice(format!("\\{:03o}", first).as_bytes());
+                    self.encode_text(&value.as_bytes()[1..], buf);
+                }
+            }
+            TextCopyFormat::Csv => self.encode_csv(value, buf),
+        }
+        Ok(())
+    }
+
+    fn encode_text(&self, value: &[u8], buf: &mut Vec<u8>) {
+        for &b in value {
+            match b {
+                b'\\' => buf.extend_from_slice(b"\\\\"),
+                b'\n' => buf.extend_from_slice(b"\\n"),
+                b'\r' => buf.extend_from_slice(b"\\r"),
+                b'\t' => buf.extend_from_slice(b"\\t"),
+                0x08 => buf.extend_from_slice(b"\\b"),
+                0x0b => buf.extend_from_slice(b"\\v"),
+                0x0c => buf.extend_from_slice(b"\\f"),
+                b if b == self.options.delimiter => {
+                    buf.push(b'\\');
+                    buf.push(b);
+                }
+                b => buf.push(b),
+            }
+        }
+    }
+
+    fn encode_csv(&self, value: &str, buf: &mut Vec<u8>) {
+        let quote = self.options.quote;
+        let escape = self.options.escape;
+        let delimiter = self.options.delimiter;
+
+        // the end-of-data marker and anything that could be mistaken for NULL must be quoted
+        let needs_quotes = value == self.options.null
+            || value == "\\."
+            || value
+                .bytes()
+                .any(|b| b == delimiter || b == quote || b == b'\n' || b == b'\r');
+
+        if !needs_quotes {
+            buf.extend_from_slice(value.as_bytes());
+            return;
+        }
+
+        buf.push(quote);
+        for &b in value.as_bytes() {
+            if b == quote || b == escape {
+                buf.push(escape);
+            }
+            buf.push(b);
+        }
+        buf.push(quote);
+    }
+}
+
+/// A row decoded from a textual `COPY` format.
+pub type TextCopyRow = Vec<Option<String>>;
+
+/// A decoder of rows in a textual `COPY` format.
+///
+/// Data is fed to the decoder with `push`, and decoded rows are retrieved with `next_row`. Once all
+/// data has been provided, `end_of_input` must be called so that a final row lacking a trailing
+/// newline can be decoded.
+#[derive(Debug)]
+pub struct TextCopyDecoder {
+    options: TextCopyOptions,
+    buf: Vec<u8>,
+    pos: usize,
+    header_read: bool,
+    eof: bool,
+    done: bool,
+}
+
+impl TextCopyDecoder {
+    /// Creates a new decoder with the specified options.
+    pub fn new(options: &TextCopyOptions) -> TextCopyDecoder {
+        TextCopyDecoder {
+            options: options.clone(),
+            buf: vec![],
+            pos: 0,
+            header_read: !options.header,
+            eof: false,
+            done: false,
+        }
+    }
+
+    /// Returns the decoder's options.
+    pub fn options(&self) -> &TextCopyOptions {
+        &self.options
+    }
+
+    /// Adds data to the decoder's buffer.
+    pub fn push(&mut self, data: &[u8]) {
+        if self.pos > 0 && self.pos >= self.buf.len() / 2 {
+            self.buf.drain(..self.pos);
+            self.pos = 0;
+        }
+        self.buf.extend_from_slice(data);
+    }
+
+    /// Indicates that no more data will be pushed to the decoder.
+    pub fn end_of_input(&mut self) {
+        self.eof = true;
+    }
+
+    /// Decodes the next row from the buffered data.
+    ///
+    /// Returns `None` if more data is required or the end of the data has been reached. A line
+    /// consisting of the end-of-data marker `\.` ends the data.
+    pub fn next_row(&mut self) -> io::Result<Option<TextCopyRow>> {
+        loop {
+            if self.done {
+                return Ok(None);
+            }
+
+            let (fields, len) = match self.split_row()? {
+                Some(row) => row,
+                None => return Ok(None),
+            };
+            self.pos += len;
+
+            if fields.len() == 1 {
+                if let Field::Plain(ref raw) = fields[0] {
+                    if &raw[..] == b"\\." {
+                        self.done = true;
+                        continue;
+                    }
+                }
+            }
+
+            if !self.header_read {
+                self.header_read = true;
+                continue;
+            }
+
+            let mut row = Vec::with_capacity(fields.len());
+            for field in fields {
+                row.push(self.decode_field(field)?);
+            }
+            return Ok(Some(row));
+        }
+    }
+
+    /// Splits the next complete line into raw fields, returning them along with the number of
+    /// bytes consumed.
+    fn split_row(&mut self) -> io::Result<Option<(Vec<Field>, usize)>> {
+        let buf = &self.buf[self.pos..];
+        if buf.is_empty() {
+            if self.eof {
+                self.done = true;
+            }
+            return Ok(None);
+        }
+
+        let csv = self.options.format == TextCopyFormat::Csv;
+        let delimiter = self.options.delimiter;
+        let quote = self.options.quote;
+        let escape = self.options.escape;
+
+        let mut fields = vec![];
+        let mut field = vec![];
+        let mut quoted = false;
+        let mut in_quotes = false;
+        let mut i = 0;
+
+        loop {
+            let b = match buf.get(i) {
+                Some(&b) => b,
+                None if self.eof => {
+                    if in_quotes {
+                        return Err(invalid_data("unterminated CSV quoted field"));
+                    }
+                    fields.push(Field::new(field, quoted));
+                    return Ok(Some((fields, i)));
+                }
+                None => return Ok(None),
+            };
+
+            if in_quotes {
+                if b == escape && i + 1 == buf.len() && !self.eof {
+                    // the next byte determines if this is an escape or the closing quote
+                    return Ok(None);
+                }
+                if b == escape {
+                    match buf.get(i + 1) {
+                        Some(&n) if n == quote || n == escape => {
+                            field.push(n);
+                            i += 2;
+                            continue;
+                        }
+                        _ => {}
+                    }
+                }
+                if b == quote {
+                    in_quotes = false;
+                } else {
+                    field.push(b);
+                }
+                i += 1;
+                continue;
+            }
+
+            if b == b'\n' || (b == b'\r' && buf.get(i + 1) == Some(&b'\n')) {
+                if b == b'\r' {
+                    i += 1;
+                }
+                fields.push(Field::new(field, quoted));
+                return Ok(Some((fields, i + 1)));
+            }
+
+            if b == delimiter {
+                fields.push(Field::new(field, quoted));
+                field = vec![];
+                quoted = false;
+                i += 1;
+                continue;
+            }
+
+            if csv && b == quote {
+                quoted = true;
+                in_quotes = true;
+                i += 1;
+                continue;
+            }
+
+            if !csv && b == b'\\' {
+                // keep escapes intact so NULL detection sees the raw text
+                match buf.get(i + 1) {
+                    Some(&n) => {
+                        field.push(b);
+                        field.push(n);
+                        i += 2;
+                        continue;
+                    }
+                    None if !self.eof => return Ok(None),
+                    None => {}
+                }
+            }
+
+            field.push(b);
+            i += 1;
+        }
+    }
+
+    fn decode_field(&self, field: Field) -> io::Result<Option<String>> {
+        let value = match field {
+            Field::Quoted(value) => value,
+            Field::Plain(raw) => {
+                if raw == self.options.null.as_bytes() {
+                    return Ok(None);
+                }
+                match self.options.format {
+                    TextCopyFormat::Text => unescape_text(&raw),
+                    TextCopyFormat::Csv => raw,
+                }
+            }
+        };
+
+        String::from_utf8(value)
+            .map(Some)
+            .map_err(|_| invalid_data("invalid UTF-8 in COPY data"))
+    }
+}
+
+enum Field {
+    Plain(Vec<u8>),
+    Quoted(Vec<u8>),
+}
+
+impl Field {
+    fn new(value: Vec<u8>, quoted: bool) -> Field {
+        if quoted {
+            Field::Quoted(value)
+        } else {
+            Field::Plain(value)
+        }
+    }
+}
+
+fn unescape_text(raw: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(raw.len());
+    let mut i = 0;
+    while i < raw.len() {
+        if raw[i] != b'\\' || i + 1 == raw.len() {
+            out.push(raw[i]);
+            i += 1;
+            continue;
+        }
+
+        i += 1;
+        match raw[i] {
+            b'b' => out.push(0x08),
+            b'f' => out.push(0x0c),
+            b'n' => out.push(b'\n'),
+            b'r' => out.push(b'\r'),
+            b't' => out.push(b'\t'),
+            b'v' => out.push(0x0b),
+            b'0'..=b'7' => {
+                let start = i;
+                while i < raw.len() && i - start < 3 && raw[i] >= b'0' && raw[i] <= b'7' {
+                    i += 1;
+                }
+                out.push(parse_radix(&raw[start..i], 8));
+                continue;
+            }
+            b'x' if i + 1 < raw.len() && (raw[i + 1] as char).is_ascii_hexdigit() => {
+                let start = i + 1;
+                i = start;
+                while i < raw.len() && i - start < 2 && (raw[i] as char).is_ascii_hexdigit() {
+                    i += 1;
+                }
+                out.push(parse_radix(&raw[start..i], 16));
+                continue;
+            }
+            b => out.push(b),
+        }
+        i += 1;
+    }
+    out
+}
+
+fn parse_radix(digits: &[u8], radix: u32) -> u8 {
+    let s = str::from_utf8(digits).unwrap();
+    u32::from_str_radix(s, radix).unwrap() as u8
+}
+
+fn invalid_data(msg: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn round_trip(options: &TextCopyOptions, rows: &[Vec<Option<&str>>]) -> Vec<u8> {
+        let encoder = TextCopyEncoder::new(options);
+        let mut buf = vec![];
+        if options.get_header() {
+            encoder.encode_header(&["a", "b"], &mut buf);
+        }
+        for row in rows {
+            encoder.encode_row(row, &mut buf).unwrap();
+        }
+
+        let mut decoder = TextCopyDecoder::new(options);
+        let mut decoded = vec![];
+        for chunk in buf.chunks(2) {
+            decoder.push(chunk);
+            while let Some(row) = decoder.next_row().unwrap() {
+                decoded.push(row);
+            }
+        }
+        decoder.end_of_input();
+        while let Some(row) = decoder.next_row().unwrap() {
+            decoded.push(row);
+        }
+
+        let expected = rows
+            .iter()
+            .map(|r| r.iter().map(|v| v.map(str::to_string)).collect::<Vec<_>>())
+            .collect::<Vec<_>>();
+        assert_eq!(decoded, expected);
+        buf
+    }
+
+    #[test]
+    fn text() {
+        let rows = vec![
+            vec![Some("a\tb"), None],
+            vec![Some("back\\slash\nnewline"), Some("\\N")],
+            vec![Some(""), Some("x")],
+        ];
+        let buf = round_trip(&TextCopyOptions::text(), &rows);
+        assert_eq!(
+            &buf[..],
+            &b"a\\tb\t\\N\nback\\\\slash\\nnewline\t\\\\N\n\tx\n"[..]
+        );
+
+        let mut options = TextCopyOptions::text();
+        options.delimiter(b'|').null("NULL").header(true);
+        round_trip(&options, &rows);
+    }
+
+    #[test]
+    fn text_null_string() {
+        let mut options = TextCopyOptions::text();
+        options.null("NULL");
+        let rows = vec![vec![Some("NULL"), None], vec![Some("\\N"), Some("NULLS")]];
+        let buf = round_trip(&options, &rows);
+        assert_eq!(&buf[..], &b"\\116ULL\tNULL\n\\\\N\tNULLS\n"[..]);
+
+        options.null("a\\\\b");
+        round_trip(&options, &[vec![Some("a\\b"), None]]);
+
+        options.null("");
+        round_trip(&options, &[vec![Some("x"), None]]);
+        let encoder = TextCopyEncoder::new(&options);
+        let mut buf = vec![];
+        assert!(encoder
+            .encode_row(&[Some("x"), Some("")], &mut buf)
+            .is_err());
+        assert!(buf.is_empty());
+    }
+
+    #[test]
+    fn text_escapes() {
+        let mut decoder = TextCopyDecoder::new(&TextCopyOptions::text());
+        decoder.push(b"\\101\\x42\\q\n\\.\nignored\n");
+        assert_eq!(
+            decoder.next_row().unwrap(),
+            Some(vec![Some("ABq".to_string())])
+        );
+        assert_eq!(decoder.next_row().unwrap(), None);
+    }
+
+    #[test]
+    fn csv() {
+        let rows = vec![
+            vec![Some("a,b"), None],
+            vec![Some("quote\"d"), Some("")],
+            vec![Some("multi\nline"), Some("\\.")],
+        ];
+        let buf = round_trip(&TextCopyOptions::csv(), &rows);
+        assert_eq!(
+            &buf[..],
+            &b"\"a,b\",\n\"quote\"\"d\",\"\"\n\"multi\nline\",\"\\.\"\n"[..]
+        );
+
+        let mut options = TextCopyOptions::csv();
+        options.delimiter(b';').quote(b'\'').escape(b'\\').header(true);
+        round_trip(&options, &rows);
+    }
+
+    #[test]
+    fn csv_missing_newline() {
+        let mut decoder = TextCopyDecoder::new(&TextCopyOptions::csv());
+        decoder.push(b"1,2\r\n3,\"4");
+        assert_eq!(
+            decoder.next_row().unwrap(),
+            Some(vec![Some("1".to_string()), Some("2".to_string())])
+        );
+        assert_eq!(decoder.next_row().unwrap(), None);
+        decoder.push(b"\"");
+        decoder.end_of_input();
+        assert_eq!(
+            decoder.next_row().unwrap(),
+            Some(vec![Some("3".to_string()), Some("4".to_string())])
+        );
+        assert_eq!(decoder.next_row().unwrap(), None);
+    }
+}
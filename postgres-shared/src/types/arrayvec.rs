@@ -0,0 +1,46 @@
+extern crate arrayvec;
+
+use self::arrayvec::{Array, ArrayVec};
+use std::error::Error;
+
+use types::{array_len, from_sql_array, to_sql_array, FromSql, IsNull, ToSql, Type};
+
+impl<'a, A> FromSql<'a> for ArrayVec<A>
+where
+    A: Array,
+    A::Item: FromSql<'a>,
+{
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<ArrayVec<A>, Box<Error + Sync + Send>> {
+        // collecting into an `ArrayVec` silently drops elements past its capacity
+        let len = array_len(raw)?;
+        if len > A::capacity() {
+            return Err(format!(
+                "expected at most {} array elements but got {}",
+                A::capacity(),
+                len
+            ).into());
+        }
+
+        from_sql_array(ty, raw)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <Vec<A::Item> as FromSql>::accepts(ty)
+    }
+}
+
+impl<A> ToSql for ArrayVec<A>
+where
+    A: Array,
+    A::Item: ToSql,
+{
+    fn to_sql(&self, ty: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<Error + Sync + Send>> {
+        to_sql_array(ty, self.len(), self.iter(), w)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <&[A::Item] as ToSql>::accepts(ty)
+    }
+
+    to_sql_checked!();
+}
@@ -1,20 +1,72 @@
 extern crate chrono;
 
+use self::chrono::naive::{MAX_DATE, MIN_DATE};
 use self::chrono::{DateTime, Duration, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime,
                    Utc};
 use postgres_protocol::types;
 use std::error::Error;
 
-use types::{FromSql, IsNull, ToSql, Type};
+use types::{FromSql, IsNull, Saturating, ToSql, Type};
+
+const USECS_PER_DAY: i64 = 86_400_000_000;
 
 fn base() -> NaiveDateTime {
     NaiveDate::from_ymd(2000, 1, 1).and_hms(0, 0, 0)
 }
 
+// the last microsecond of a day
+fn last_time() -> NaiveTime {
+    NaiveTime::from_hms_micro(23, 59, 59, 999_999)
+}
+
+fn timestamp_from_usec(t: i64) -> Option<NaiveDateTime> {
+    base().checked_add_signed(Duration::microseconds(t))
+}
+
+fn date_from_days(jd: i32) -> Option<NaiveDate> {
+    base().date().checked_add_signed(Duration::days(jd as i64))
+}
+
+fn time_from_usec(usec: i64) -> Option<NaiveTime> {
+    // 24:00:00 is a valid Postgres time, but would wrap around to midnight
+    if usec < 0 || usec >= USECS_PER_DAY {
+        return None;
+    }
+    Some(NaiveTime::from_hms(0, 0, 0) + Duration::microseconds(usec))
+}
+
+fn time_to_usec(time: &NaiveTime) -> Result<i64, Box<Error + Sync + Send>> {
+    let delta = time.signed_duration_since(NaiveTime::from_hms(0, 0, 0));
+    match delta.num_microseconds() {
+        Some(usec) => Ok(usec),
+        None => Err("value too large to transmit".into()),
+    }
+}
+
 impl<'a> FromSql<'a> for NaiveDateTime {
     fn from_sql(_: &Type, raw: &[u8]) -> Result<NaiveDateTime, Box<Error + Sync + Send>> {
         let t = types::timestamp_from_sql(raw)?;
-        Ok(base() + Duration::microseconds(t))
+        match timestamp_from_usec(t) {
+            Some(datetime) => Ok(datetime),
+            None => Err("value out of range for a NaiveDateTime".into()),
+        }
+    }
+
+    accepts!(TIMESTAMP);
+}
+
+impl<'a> FromSql<'a> for Saturating<NaiveDateTime> {
+    fn from_sql(
+        _: &Type,
+        raw: &[u8],
+    ) -> Result<Saturating<NaiveDateTime>, Box<Error + Sync + Send>> {
+        let t = types::timestamp_from_sql(raw)?;
+        let datetime = match timestamp_from_usec(t) {
+            Some(datetime) => datetime,
+            None if t < 0 => MIN_DATE.and_hms(0, 0, 0),
+            None => MAX_DATE.and_time(last_time()),
+        };
+        Ok(Saturating(datetime))
     }
 
     accepts!(TIMESTAMP);
@@ -43,6 +95,18 @@ impl<'a> FromSql<'a> for DateTime<Utc> {
     accepts!(TIMESTAMPTZ);
 }
 
+impl<'a> FromSql<'a> for Saturating<DateTime<Utc>> {
+    fn from_sql(
+        type_: &Type,
+        raw: &[u8],
+    ) -> Result<Saturating<DateTime<Utc>>, Box<Error + Sync + Send>> {
+        let naive = Saturating::<NaiveDateTime>::from_sql(type_, raw)?;
+        Ok(Saturating(DateTime::from_utc(naive.0, Utc)))
+    }
+
+    accepts!(TIMESTAMPTZ);
+}
+
 impl ToSql for DateTime<Utc> {
     fn to_sql(&self, type_: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<Error + Sync + Send>> {
         self.naive_utc().to_sql(type_, w)
@@ -61,6 +125,18 @@ impl<'a> FromSql<'a> for DateTime<Local> {
     accepts!(TIMESTAMPTZ);
 }
 
+impl<'a> FromSql<'a> for Saturating<DateTime<Local>> {
+    fn from_sql(
+        type_: &Type,
+        raw: &[u8],
+    ) -> Result<Saturating<DateTime<Local>>, Box<Error + Sync + Send>> {
+        let utc = Saturating::<DateTime<Utc>>::from_sql(type_, raw)?;
+        Ok(Saturating(utc.0.with_timezone(&Local)))
+    }
+
+    accepts!(TIMESTAMPTZ);
+}
+
 impl ToSql for DateTime<Local> {
     fn to_sql(&self, type_: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<Error + Sync + Send>> {
         self.with_timezone(&Utc).to_sql(type_, w)
@@ -82,6 +158,18 @@ impl<'a> FromSql<'a> for DateTime<FixedOffset> {
     accepts!(TIMESTAMPTZ);
 }
 
+impl<'a> FromSql<'a> for Saturating<DateTime<FixedOffset>> {
+    fn from_sql(
+        type_: &Type,
+        raw: &[u8],
+    ) -> Result<Saturating<DateTime<FixedOffset>>, Box<Error + Sync + Send>> {
+        let utc = Saturating::<DateTime<Utc>>::from_sql(type_, raw)?;
+        Ok(Saturating(utc.0.with_timezone(&FixedOffset::east(0))))
+    }
+
+    accepts!(TIMESTAMPTZ);
+}
+
 impl ToSql for DateTime<FixedOffset> {
     fn to_sql(&self, type_: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<Error + Sync + Send>> {
         self.with_timezone(&Utc).to_sql(type_, w)
@@ -94,7 +182,24 @@ impl ToSql for DateTime<FixedOffset> {
 impl<'a> FromSql<'a> for NaiveDate {
     fn from_sql(_: &Type, raw: &[u8]) -> Result<NaiveDate, Box<Error + Sync + Send>> {
         let jd = types::date_from_sql(raw)?;
-        Ok(base().date() + Duration::days(jd as i64))
+        match date_from_days(jd) {
+            Some(date) => Ok(date),
+            None => Err("value out of range for a NaiveDate".into()),
+        }
+    }
+
+    accepts!(DATE);
+}
+
+impl<'a> FromSql<'a> for Saturating<NaiveDate> {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<Saturating<NaiveDate>, Box<Error + Sync + Send>> {
+        let jd = types::date_from_sql(raw)?;
+        let date = match date_from_days(jd) {
+            Some(date) => date,
+            None if jd < 0 => MIN_DATE,
+            None => MAX_DATE,
+        };
+        Ok(Saturating(date))
     }
 
     accepts!(DATE);
@@ -118,7 +223,24 @@ impl ToSql for NaiveDate {
 impl<'a> FromSql<'a> for NaiveTime {
     fn from_sql(_: &Type, raw: &[u8]) -> Result<NaiveTime, Box<Error + Sync + Send>> {
         let usec = types::time_from_sql(raw)?;
-        Ok(NaiveTime::from_hms(0, 0, 0) + Duration::microseconds(usec))
+        match time_from_usec(usec) {
+            Some(time) => Ok(time),
+            None => Err("value out of range for a NaiveTime".into()),
+        }
+    }
+
+    accepts!(TIME);
+}
+
+impl<'a> FromSql<'a> for Saturating<NaiveTime> {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<Saturating<NaiveTime>, Box<Error + Sync + Send>> {
+        let usec = types::time_from_sql(raw)?;
+        let time = match time_from_usec(usec) {
+            Some(time) => time,
+            None if usec < 0 => NaiveTime::from_hms(0, 0, 0),
+            None => last_time(),
+        };
+        Ok(Saturating(time))
     }
 
     accepts!(TIME);
@@ -126,15 +248,147 @@ impl<'a> FromSql<'a> for NaiveTime {
 
 impl ToSql for NaiveTime {
     fn to_sql(&self, _: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<Error + Sync + Send>> {
-        let delta = self.signed_duration_since(NaiveTime::from_hms(0, 0, 0));
-        let time = match delta.num_microseconds() {
+        types::time_to_sql(time_to_usec(self)?, w);
+        Ok(IsNull::No)
+    }
+
+    accepts!(TIME);
+    to_sql_checked!();
+}
+
+// Postgres stores the offset of a TIMETZ in seconds west of UTC
+impl<'a> FromSql<'a> for (NaiveTime, FixedOffset) {
+    fn from_sql(
+        _: &Type,
+        raw: &[u8],
+    ) -> Result<(NaiveTime, FixedOffset), Box<Error + Sync + Send>> {
+        let timetz = types::timetz_from_sql(raw)?;
+        let time = match time_from_usec(timetz.time()) {
             Some(time) => time,
-            None => return Err("value too large to transmit".into()),
+            None => return Err("value out of range for a NaiveTime".into()),
+        };
+        let offset = match FixedOffset::west_opt(timetz.offset()) {
+            Some(offset) => offset,
+            None => return Err("value out of range for a FixedOffset".into()),
         };
-        types::time_to_sql(time, w);
+        Ok((time, offset))
+    }
+
+    accepts!(TIMETZ);
+}
+
+impl ToSql for (NaiveTime, FixedOffset) {
+    fn to_sql(&self, _: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<Error + Sync + Send>> {
+        types::timetz_to_sql(time_to_usec(&self.0)?, -self.1.local_minus_utc(), w);
         Ok(IsNull::No)
     }
 
-    accepts!(TIME);
+    accepts!(TIMETZ);
     to_sql_checked!();
 }
+
+#[cfg(test)]
+mod test {
+    use std::{i32, i64};
+
+    use super::*;
+    use types::special::test::sample_i64s;
+    use types::Timestamp;
+
+    fn check_round_trip<'a, T>(ty: &Type, raw: &'a [u8]) -> Option<T>
+    where
+        T: FromSql<'a> + ToSql,
+    {
+        let value = T::from_sql(ty, raw).ok()?;
+        let mut buf = vec![];
+        value.to_sql(ty, &mut buf).unwrap();
+        assert_eq!(buf, raw);
+        Some(value)
+    }
+
+    #[test]
+    fn timestamp_range() {
+        for t in sample_i64s() {
+            let mut raw = vec![];
+            types::timestamp_to_sql(t, &mut raw);
+
+            let naive = check_round_trip::<NaiveDateTime>(&Type::TIMESTAMP, &raw);
+            let utc = check_round_trip::<DateTime<Utc>>(&Type::TIMESTAMPTZ, &raw);
+            assert_eq!(naive.map(|n| DateTime::from_utc(n, Utc)), utc);
+
+            let timestamp = Timestamp::<NaiveDateTime>::from_sql(&Type::TIMESTAMP, &raw);
+            match (t, naive) {
+                (i64::MAX, _) => assert_eq!(timestamp.unwrap(), Timestamp::PosInfinity),
+                (i64::MIN, _) => assert_eq!(timestamp.unwrap(), Timestamp::NegInfinity),
+                (_, Some(naive)) => assert_eq!(timestamp.unwrap(), Timestamp::Value(naive)),
+                (_, None) => assert!(timestamp.is_err()),
+            }
+
+            let saturated = Saturating::<NaiveDateTime>::from_sql(&Type::TIMESTAMP, &raw).unwrap();
+            match naive {
+                Some(naive) => assert_eq!(saturated.0, naive),
+                None if t < 0 => assert_eq!(saturated.0, MIN_DATE.and_hms(0, 0, 0)),
+                None => assert_eq!(saturated.0, MAX_DATE.and_time(last_time())),
+            }
+            let utc = Saturating::<DateTime<Utc>>::from_sql(&Type::TIMESTAMPTZ, &raw).unwrap();
+            assert_eq!(utc.0.naive_utc(), saturated.0);
+            let local = Saturating::<DateTime<Local>>::from_sql(&Type::TIMESTAMPTZ, &raw).unwrap();
+            assert_eq!(local.0.naive_utc(), saturated.0);
+            let fixed =
+                Saturating::<DateTime<FixedOffset>>::from_sql(&Type::TIMESTAMPTZ, &raw).unwrap();
+            assert_eq!(fixed.0.naive_utc(), saturated.0);
+        }
+
+        // year 294276, the last year Postgres supports
+        let mut raw = vec![];
+        types::timestamp_to_sql(9_223_371_331_199_999_999, &mut raw);
+        assert!(NaiveDateTime::from_sql(&Type::TIMESTAMP, &raw).is_err());
+    }
+
+    #[test]
+    fn date_range() {
+        let samples = sample_i64s()
+            .into_iter()
+            .map(|v| (v >> 32) as i32)
+            .chain(vec![i32::MIN, i32::MIN + 1, i32::MAX - 1, i32::MAX]);
+        for jd in samples {
+            let mut raw = vec![];
+            types::date_to_sql(jd, &mut raw);
+
+            let date = check_round_trip::<NaiveDate>(&Type::DATE, &raw);
+
+            let saturated = Saturating::<NaiveDate>::from_sql(&Type::DATE, &raw).unwrap();
+            match date {
+                Some(date) => assert_eq!(saturated.0, date),
+                None if jd < 0 => assert_eq!(saturated.0, MIN_DATE),
+                None => assert_eq!(saturated.0, MAX_DATE),
+            }
+        }
+    }
+
+    #[test]
+    fn time_range() {
+        for usec in sample_i64s() {
+            let mut raw = vec![];
+            types::time_to_sql(usec, &mut raw);
+
+            let time = check_round_trip::<NaiveTime>(&Type::TIME, &raw);
+            assert_eq!(time.is_some(), usec >= 0 && usec < USECS_PER_DAY);
+
+            let saturated = Saturating::<NaiveTime>::from_sql(&Type::TIME, &raw).unwrap();
+            match time {
+                Some(time) => assert_eq!(saturated.0, time),
+                None if usec < 0 => assert_eq!(saturated.0, NaiveTime::from_hms(0, 0, 0)),
+                None => assert_eq!(saturated.0, last_time()),
+            }
+
+            let mut raw = vec![];
+            types::timetz_to_sql(usec, -3600, &mut raw);
+            let timetz = check_round_trip::<(NaiveTime, FixedOffset)>(&Type::TIMETZ, &raw);
+            assert_eq!(timetz.map(|t| t.0), time);
+            if let Some((_, offset)) = timetz {
+                assert_eq!(offset, FixedOffset::east(3600));
+            }
+        }
+    }
+}
@@ -1,28 +1,91 @@
 extern crate geo;
 
-use self::geo::{Coordinate, LineString, Point, Rect};
+use self::geo::{Coordinate, Line, LineString, Point, Polygon, Rect};
+#[cfg(feature = "with-postgis")]
+use self::geo::{Geometry, GeometryCollection, MultiLineString, MultiPoint, MultiPolygon};
 use fallible_iterator::FallibleIterator;
 use postgres_protocol::types;
 use std::error::Error;
 
+#[cfg(feature = "with-postgis")]
+use types::postgis;
 use types::{FromSql, IsNull, ToSql, Type};
 
+// Point, LineString and Polygon are also accepted for PostGIS's types, and are converted through
+// the corresponding types of the postgis module
+#[cfg(feature = "with-postgis")]
+trait Postgis: Sized {
+    type Native: for<'a> FromSql<'a> + ToSql;
+
+    fn from_native(native: Self::Native) -> Self;
+
+    fn to_native(&self) -> Self::Native;
+}
+
+#[cfg(feature = "with-postgis")]
+fn is_postgis(ty: &Type) -> bool {
+    postgis::is_postgis(ty)
+}
+
+#[cfg(not(feature = "with-postgis"))]
+fn is_postgis(_: &Type) -> bool {
+    false
+}
+
+#[cfg(feature = "with-postgis")]
+fn postgis_from_sql<T>(ty: &Type, raw: &[u8]) -> Result<T, Box<Error + Sync + Send>>
+where
+    T: Postgis,
+{
+    T::Native::from_sql(ty, raw).map(T::from_native)
+}
+
+#[cfg(feature = "with-postgis")]
+fn postgis_to_sql<T>(
+    value: &T,
+    ty: &Type,
+    out: &mut Vec<u8>,
+) -> Result<IsNull, Box<Error + Sync + Send>>
+where
+    T: Postgis,
+{
+    value.to_native().to_sql(ty, out)
+}
+
 impl<'a> FromSql<'a> for Point<f64> {
-    fn from_sql(_: &Type, raw: &[u8]) -> Result<Self, Box<Error + Sync + Send>> {
+    fn from_sql(ty: &Type, raw: &[u8]) -> Result<Self, Box<Error + Sync + Send>> {
+        if is_postgis(ty) {
+            #[cfg(feature = "with-postgis")]
+            return postgis_from_sql(ty, raw);
+        }
         let point = types::point_from_sql(raw)?;
         Ok(Point::new(point.x(), point.y()))
     }
 
-    accepts!(POINT);
+    fn accepts(ty: &Type) -> bool {
+        match *ty {
+            Type::POINT => true,
+            ref ty => is_postgis(ty),
+        }
+    }
 }
 
 impl ToSql for Point<f64> {
-    fn to_sql(&self, _: &Type, out: &mut Vec<u8>) -> Result<IsNull, Box<Error + Sync + Send>> {
+    fn to_sql(&self, ty: &Type, out: &mut Vec<u8>) -> Result<IsNull, Box<Error + Sync + Send>> {
+        if is_postgis(ty) {
+            #[cfg(feature = "with-postgis")]
+            return postgis_to_sql(self, ty, out);
+        }
         types::point_to_sql(self.x(), self.y(), out);
         Ok(IsNull::No)
     }
 
-    accepts!(POINT);
+    fn accepts(ty: &Type) -> bool {
+        match *ty {
+            Type::POINT => true,
+            ref ty => is_postgis(ty),
+        }
+    }
     to_sql_checked!();
 }
 
@@ -49,22 +112,323 @@ impl ToSql for Rect<f64> {
 }
 
 impl<'a> FromSql<'a> for LineString<f64> {
-    fn from_sql(_: &Type, raw: &[u8]) -> Result<Self, Box<Error + Sync + Send>> {
+    fn from_sql(ty: &Type, raw: &[u8]) -> Result<Self, Box<Error + Sync + Send>> {
+        if is_postgis(ty) {
+            #[cfg(feature = "with-postgis")]
+            return postgis_from_sql(ty, raw);
+        }
         let path = types::path_from_sql(raw)?;
         let points = path.points().map(|p| Coordinate { x: p.x(), y: p.y() }).collect()?;
         Ok(LineString(points))
     }
 
-    accepts!(PATH);
+    fn accepts(ty: &Type) -> bool {
+        match *ty {
+            Type::PATH => true,
+            ref ty => is_postgis(ty),
+        }
+    }
 }
 
 impl ToSql for LineString<f64> {
-    fn to_sql(&self, _: &Type, out: &mut Vec<u8>) -> Result<IsNull, Box<Error + Sync + Send>> {
+    fn to_sql(&self, ty: &Type, out: &mut Vec<u8>) -> Result<IsNull, Box<Error + Sync + Send>> {
+        if is_postgis(ty) {
+            #[cfg(feature = "with-postgis")]
+            return postgis_to_sql(self, ty, out);
+        }
         let closed = false; // always encode an open path from LineString
         types::path_to_sql(closed, self.0.iter().map(|p| (p.x, p.y)), out)?;
         Ok(IsNull::No)
     }
 
-    accepts!(PATH);
+    fn accepts(ty: &Type) -> bool {
+        match *ty {
+            Type::PATH => true,
+            ref ty => is_postgis(ty),
+        }
+    }
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for Line<f64> {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<Self, Box<Error + Sync + Send>> {
+        let lseg = types::lseg_from_sql(raw)?;
+        Ok(Line {
+            start: Coordinate {
+                x: lseg.start().x(),
+                y: lseg.start().y(),
+            },
+            end: Coordinate {
+                x: lseg.end().x(),
+                y: lseg.end().y(),
+            },
+        })
+    }
+
+    accepts!(LSEG);
+}
+
+impl ToSql for Line<f64> {
+    fn to_sql(&self, _: &Type, out: &mut Vec<u8>) -> Result<IsNull, Box<Error + Sync + Send>> {
+        types::lseg_to_sql(self.start.x, self.start.y, self.end.x, self.end.y, out);
+        Ok(IsNull::No)
+    }
+
+    accepts!(LSEG);
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for Polygon<f64> {
+    fn from_sql(ty: &Type, raw: &[u8]) -> Result<Self, Box<Error + Sync + Send>> {
+        if is_postgis(ty) {
+            #[cfg(feature = "with-postgis")]
+            return postgis_from_sql(ty, raw);
+        }
+        let polygon = types::polygon_from_sql(raw)?;
+        let mut points: Vec<_> = polygon
+            .points()
+            .map(|p| Coordinate { x: p.x(), y: p.y() })
+            .collect()?;
+        // geo represents rings as closed line strings
+        if let Some(&first) = points.first() {
+            points.push(first);
+        }
+        Ok(Polygon {
+            exterior: LineString(points),
+            interiors: vec![],
+        })
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        match *ty {
+            Type::POLYGON => true,
+            ref ty => is_postgis(ty),
+        }
+    }
+}
+
+impl ToSql for Polygon<f64> {
+    fn to_sql(&self, ty: &Type, out: &mut Vec<u8>) -> Result<IsNull, Box<Error + Sync + Send>> {
+        if is_postgis(ty) {
+            #[cfg(feature = "with-postgis")]
+            return postgis_to_sql(self, ty, out);
+        }
+        if !self.interiors.is_empty() {
+            return Err("Postgres polygons cannot have interior rings".into());
+        }
+        let mut points = &self.exterior.0[..];
+        if points.len() > 1 && points.first() == points.last() {
+            points = &points[..points.len() - 1];
+        }
+        types::polygon_to_sql(points.iter().map(|p| (p.x, p.y)), out)?;
+        Ok(IsNull::No)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        match *ty {
+            Type::POLYGON => true,
+            ref ty => is_postgis(ty),
+        }
+    }
     to_sql_checked!();
 }
+
+#[cfg(feature = "with-postgis")]
+fn coordinate(point: postgis::Point) -> Coordinate<f64> {
+    Coordinate {
+        x: point.x,
+        y: point.y,
+    }
+}
+
+#[cfg(feature = "with-postgis")]
+impl From<postgis::Point> for Point<f64> {
+    fn from(point: postgis::Point) -> Point<f64> {
+        Point::new(point.x, point.y)
+    }
+}
+
+#[cfg(feature = "with-postgis")]
+impl From<Point<f64>> for postgis::Point {
+    fn from(point: Point<f64>) -> postgis::Point {
+        postgis::Point::new(point.x(), point.y())
+    }
+}
+
+#[cfg(feature = "with-postgis")]
+impl From<postgis::LineString> for LineString<f64> {
+    fn from(line_string: postgis::LineString) -> LineString<f64> {
+        LineString(
+            line_string
+                .into_points()
+                .into_iter()
+                .map(coordinate)
+                .collect(),
+        )
+    }
+}
+
+#[cfg(feature = "with-postgis")]
+impl From<LineString<f64>> for postgis::LineString {
+    fn from(line_string: LineString<f64>) -> postgis::LineString {
+        postgis::LineString::new(
+            line_string
+                .0
+                .into_iter()
+                .map(|c| postgis::Point::new(c.x, c.y))
+                .collect(),
+        )
+    }
+}
+
+#[cfg(feature = "with-postgis")]
+impl From<postgis::Polygon> for Polygon<f64> {
+    fn from(polygon: postgis::Polygon) -> Polygon<f64> {
+        let mut rings = polygon.into_rings().into_iter().map(LineString::from);
+        Polygon {
+            exterior: rings.next().unwrap_or_else(|| LineString(vec![])),
+            interiors: rings.collect(),
+        }
+    }
+}
+
+#[cfg(feature = "with-postgis")]
+impl From<Polygon<f64>> for postgis::Polygon {
+    fn from(polygon: Polygon<f64>) -> postgis::Polygon {
+        let mut rings = vec![];
+        // an empty polygon has no rings
+        if !polygon.exterior.0.is_empty() || !polygon.interiors.is_empty() {
+            rings.push(polygon.exterior.into());
+        }
+        rings.extend(polygon.interiors.into_iter().map(postgis::LineString::from));
+        postgis::Polygon::new(rings)
+    }
+}
+
+#[cfg(feature = "with-postgis")]
+impl From<postgis::Geometry> for Geometry<f64> {
+    fn from(geometry: postgis::Geometry) -> Geometry<f64> {
+        match geometry {
+            postgis::Geometry::Point(g) => Geometry::Point(g.into()),
+            postgis::Geometry::LineString(g) => Geometry::LineString(g.into()),
+            postgis::Geometry::Polygon(g) => Geometry::Polygon(g.into()),
+            postgis::Geometry::MultiPoint(g) => Geometry::MultiPoint(g.into()),
+            postgis::Geometry::MultiLineString(g) => Geometry::MultiLineString(g.into()),
+            postgis::Geometry::MultiPolygon(g) => Geometry::MultiPolygon(g.into()),
+            postgis::Geometry::GeometryCollection(g) => Geometry::GeometryCollection(g.into()),
+        }
+    }
+}
+
+#[cfg(feature = "with-postgis")]
+impl From<Geometry<f64>> for postgis::Geometry {
+    fn from(geometry: Geometry<f64>) -> postgis::Geometry {
+        match geometry {
+            Geometry::Point(g) => postgis::Geometry::Point(g.into()),
+            // PostGIS has no separate type for single line segments
+            Geometry::Line(g) => postgis::Geometry::LineString(postgis::LineString::new(vec![
+                postgis::Point::new(g.start.x, g.start.y),
+                postgis::Point::new(g.end.x, g.end.y),
+            ])),
+            Geometry::LineString(g) => postgis::Geometry::LineString(g.into()),
+            Geometry::Polygon(g) => postgis::Geometry::Polygon(g.into()),
+            Geometry::MultiPoint(g) => postgis::Geometry::MultiPoint(g.into()),
+            Geometry::MultiLineString(g) => postgis::Geometry::MultiLineString(g.into()),
+            Geometry::MultiPolygon(g) => postgis::Geometry::MultiPolygon(g.into()),
+            Geometry::GeometryCollection(g) => postgis::Geometry::GeometryCollection(g.into()),
+        }
+    }
+}
+
+macro_rules! postgis_collection {
+    ($t:ident, $into:ident) => {
+        #[cfg(feature = "with-postgis")]
+        impl From<postgis::$t> for $t<f64> {
+            fn from(collection: postgis::$t) -> $t<f64> {
+                $t(collection.$into().into_iter().map(Into::into).collect())
+            }
+        }
+
+        #[cfg(feature = "with-postgis")]
+        impl From<$t<f64>> for postgis::$t {
+            fn from(collection: $t<f64>) -> postgis::$t {
+                postgis::$t::new(collection.0.into_iter().map(Into::into).collect())
+            }
+        }
+    };
+}
+
+postgis_collection!(MultiPoint, into_points);
+postgis_collection!(MultiLineString, into_line_strings);
+postgis_collection!(MultiPolygon, into_polygons);
+postgis_collection!(GeometryCollection, into_geometries);
+
+macro_rules! postgis_impls {
+    ($($t:ident),+) => {
+        $(
+            #[cfg(feature = "with-postgis")]
+            impl Postgis for $t<f64> {
+                type Native = postgis::$t;
+
+                fn from_native(native: postgis::$t) -> $t<f64> {
+                    native.into()
+                }
+
+                fn to_native(&self) -> postgis::$t {
+                    self.clone().into()
+                }
+            }
+        )+
+    }
+}
+
+postgis_impls!(Point, LineString, Polygon);
+
+// the remaining geometries have no built-in Postgres equivalent
+macro_rules! postgis_only {
+    ($($t:ident),+) => {
+        $(
+            #[cfg(feature = "with-postgis")]
+            impl<'a> FromSql<'a> for $t<f64> {
+                fn from_sql(ty: &Type, raw: &[u8]) -> Result<Self, Box<Error + Sync + Send>> {
+                    postgis_from_sql(ty, raw)
+                }
+
+                fn accepts(ty: &Type) -> bool {
+                    is_postgis(ty)
+                }
+            }
+
+            #[cfg(feature = "with-postgis")]
+            impl ToSql for $t<f64> {
+                fn to_sql(&self,
+                          ty: &Type,
+                          out: &mut Vec<u8>)
+                          -> Result<IsNull, Box<Error + Sync + Send>> {
+                    postgis_to_sql(self, ty, out)
+                }
+
+                fn accepts(ty: &Type) -> bool {
+                    is_postgis(ty)
+                }
+
+                to_sql_checked!();
+            }
+        )+
+    }
+}
+
+postgis_impls!(
+    MultiPoint,
+    MultiLineString,
+    MultiPolygon,
+    GeometryCollection,
+    Geometry
+);
+postgis_only!(
+    MultiPoint,
+    MultiLineString,
+    MultiPolygon,
+    GeometryCollection,
+    Geometry
+);
@@ -0,0 +1,204 @@
+use fallible_iterator::FallibleIterator;
+use postgres_protocol::types;
+use std::error::Error;
+
+use types::{FromSql, IsNull, ToSql, Type};
+
+/// A point, corresponding to the `POINT` type.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct Point {
+    /// The x coordinate.
+    pub x: f64,
+    /// The y coordinate.
+    pub y: f64,
+}
+
+impl Point {
+    /// Creates a new `Point`.
+    pub fn new(x: f64, y: f64) -> Point {
+        Point { x: x, y: y }
+    }
+}
+
+impl From<types::Point> for Point {
+    fn from(point: types::Point) -> Point {
+        Point::new(point.x(), point.y())
+    }
+}
+
+impl<'a> FromSql<'a> for Point {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<Point, Box<Error + Sync + Send>> {
+        types::point_from_sql(raw).map(Point::from)
+    }
+
+    accepts!(POINT);
+}
+
+impl ToSql for Point {
+    fn to_sql(&self, _: &Type, out: &mut Vec<u8>) -> Result<IsNull, Box<Error + Sync + Send>> {
+        types::point_to_sql(self.x, self.y, out);
+        Ok(IsNull::No)
+    }
+
+    accepts!(POINT);
+    to_sql_checked!();
+}
+
+/// An infinite line, corresponding to the `LINE` type.
+///
+/// The line is represented by the coefficients of its equation, `ax + by + c = 0`, where `a` and
+/// `b` are not both zero.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct Line {
+    /// The `a` coefficient.
+    pub a: f64,
+    /// The `b` coefficient.
+    pub b: f64,
+    /// The `c` coefficient.
+    pub c: f64,
+}
+
+impl Line {
+    /// Creates a new `Line` from the coefficients of its equation.
+    pub fn new(a: f64, b: f64, c: f64) -> Line {
+        Line { a: a, b: b, c: c }
+    }
+}
+
+impl<'a> FromSql<'a> for Line {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<Line, Box<Error + Sync + Send>> {
+        let line = types::line_from_sql(raw)?;
+        Ok(Line::new(line.a(), line.b(), line.c()))
+    }
+
+    accepts!(LINE);
+}
+
+impl ToSql for Line {
+    fn to_sql(&self, _: &Type, out: &mut Vec<u8>) -> Result<IsNull, Box<Error + Sync + Send>> {
+        types::line_to_sql(self.a, self.b, self.c, out);
+        Ok(IsNull::No)
+    }
+
+    accepts!(LINE);
+    to_sql_checked!();
+}
+
+/// A finite line segment, corresponding to the `LSEG` type.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct LineSegment {
+    /// The first end point.
+    pub start: Point,
+    /// The second end point.
+    pub end: Point,
+}
+
+impl LineSegment {
+    /// Creates a new `LineSegment` from its end points.
+    pub fn new(start: Point, end: Point) -> LineSegment {
+        LineSegment {
+            start: start,
+            end: end,
+        }
+    }
+}
+
+impl<'a> FromSql<'a> for LineSegment {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<LineSegment, Box<Error + Sync + Send>> {
+        let lseg = types::lseg_from_sql(raw)?;
+        Ok(LineSegment::new(lseg.start().into(), lseg.end().into()))
+    }
+
+    accepts!(LSEG);
+}
+
+impl ToSql for LineSegment {
+    fn to_sql(&self, _: &Type, out: &mut Vec<u8>) -> Result<IsNull, Box<Error + Sync + Send>> {
+        types::lseg_to_sql(self.start.x, self.start.y, self.end.x, self.end.y, out);
+        Ok(IsNull::No)
+    }
+
+    accepts!(LSEG);
+    to_sql_checked!();
+}
+
+/// A polygon, corresponding to the `POLYGON` type.
+///
+/// Polygons are implicitly closed, so the first vertex should not be repeated at the end.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Polygon(Vec<Point>);
+
+impl Polygon {
+    /// Creates a new `Polygon` from its vertices.
+    pub fn new(points: Vec<Point>) -> Polygon {
+        Polygon(points)
+    }
+
+    /// Returns the vertices of the polygon.
+    pub fn points(&self) -> &[Point] {
+        &self.0
+    }
+
+    /// Consumes the `Polygon`, returning its vertices.
+    pub fn into_points(self) -> Vec<Point> {
+        self.0
+    }
+}
+
+impl<'a> FromSql<'a> for Polygon {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<Polygon, Box<Error + Sync + Send>> {
+        let polygon = types::polygon_from_sql(raw)?;
+        let points = polygon.points().map(Point::from).collect()?;
+        Ok(Polygon(points))
+    }
+
+    accepts!(POLYGON);
+}
+
+impl ToSql for Polygon {
+    fn to_sql(&self, _: &Type, out: &mut Vec<u8>) -> Result<IsNull, Box<Error + Sync + Send>> {
+        types::polygon_to_sql(self.0.iter().map(|p| (p.x, p.y)), out)?;
+        Ok(IsNull::No)
+    }
+
+    accepts!(POLYGON);
+    to_sql_checked!();
+}
+
+/// A circle, corresponding to the `CIRCLE` type.
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
+pub struct Circle {
+    /// The center of the circle.
+    pub center: Point,
+    /// The radius of the circle.
+    pub radius: f64,
+}
+
+impl Circle {
+    /// Creates a new `Circle`.
+    pub fn new(center: Point, radius: f64) -> Circle {
+        Circle {
+            center: center,
+            radius: radius,
+        }
+    }
+}
+
+impl<'a> FromSql<'a> for Circle {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<Circle, Box<Error + Sync + Send>> {
+        let circle = types::circle_from_sql(raw)?;
+        Ok(Circle::new(circle.center().into(), circle.radius()))
+    }
+
+    accepts!(CIRCLE);
+}
+
+impl ToSql for Circle {
+    fn to_sql(&self, _: &Type, out: &mut Vec<u8>) -> Result<IsNull, Box<Error + Sync + Send>> {
+        types::circle_to_sql(self.center.x, self.center.y, self.radius, out);
+        Ok(IsNull::No)
+    }
+
+    accepts!(CIRCLE);
+    to_sql_checked!();
+}
@@ -0,0 +1,311 @@
+use postgres_protocol::types::{self, NumericSign};
+use std::error::Error;
+
+use types::{FromSql, IsNull, ToSql, Type, WrongType};
+
+const NUMERIC_BASE: i128 = 10_000;
+
+/// A wrapper which converts integers to and from any Postgres integer type, checking for overflow.
+///
+/// `FromSql` and `ToSql` for `i16`, `i32` and `i64` only accept `INT2`, `INT4` and `INT8`
+/// respectively, so reading a `COUNT(*)` into an `i32` fails, for example. `Lenient<T>` accepts
+/// any of `INT2`, `INT4`, `INT8` and integral `NUMERIC` values, and returns an error if a value
+/// does not fit in the destination type. It is implemented for `i16`, `i32`, `i64`, `i128`, `u8`,
+/// `u16`, `u32` and `u64`.
+///
+/// `u16`, `u64` and `i128` can also be used directly, with the same checks. `u8` must always be
+/// wrapped, since `Vec<u8>` and `&[u8]` correspond to `BYTEA` rather than arrays.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Lenient<T>(pub T);
+
+impl<T> Lenient<T> {
+    /// Consumes the `Lenient`, returning the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+fn accepts_int(ty: &Type) -> bool {
+    match *ty {
+        Type::INT2 | Type::INT4 | Type::INT8 | Type::NUMERIC => true,
+        _ => false,
+    }
+}
+
+fn int_from_sql(ty: &Type, raw: &[u8]) -> Result<i128, Box<Error + Sync + Send>> {
+    match *ty {
+        Type::INT2 => types::int2_from_sql(raw).map(i128::from),
+        Type::INT4 => types::int4_from_sql(raw).map(i128::from),
+        Type::INT8 => types::int8_from_sql(raw).map(i128::from),
+        Type::NUMERIC => numeric_from_sql(raw),
+        _ => Err(Box::new(WrongType::new(ty.clone()))),
+    }
+}
+
+fn numeric_from_sql(raw: &[u8]) -> Result<i128, Box<Error + Sync + Send>> {
+    let numeric = types::numeric_from_sql(raw)?;
+    let negative = match numeric.sign() {
+        NumericSign::Positive => false,
+        NumericSign::Negative => true,
+        _ => return Err("NUMERIC value is not a finite number".into()),
+    };
+
+    // the digit at index i is multiplied by 10000^(weight - i)
+    let overflow = || "NUMERIC value is out of range for i128";
+    let mut magnitude = 0u128;
+    let mut exponent = i32::from(numeric.weight());
+    for digit in numeric.digits() {
+        if digit < 0 || i128::from(digit) >= NUMERIC_BASE {
+            return Err("invalid NUMERIC digit".into());
+        }
+        if exponent < 0 {
+            if digit != 0 {
+                return Err("NUMERIC value is not an integer".into());
+            }
+        } else {
+            magnitude = magnitude
+                .checked_mul(NUMERIC_BASE as u128)
+                .and_then(|v| v.checked_add(digit as u128))
+                .ok_or_else(overflow)?;
+        }
+        exponent -= 1;
+    }
+    // trailing zero digits are not stored
+    while exponent >= 0 {
+        magnitude = magnitude
+            .checked_mul(NUMERIC_BASE as u128)
+            .ok_or_else(overflow)?;
+        exponent -= 1;
+    }
+
+    if negative {
+        // the magnitude of i128::MIN is one larger than i128::MAX
+        if magnitude > i128::max_value() as u128 + 1 {
+            return Err(overflow().into());
+        }
+        Ok((magnitude as i128).wrapping_neg())
+    } else {
+        if magnitude > i128::max_value() as u128 {
+            return Err(overflow().into());
+        }
+        Ok(magnitude as i128)
+    }
+}
+
+fn int_to_sql(
+    value: i128,
+    ty: &Type,
+    out: &mut Vec<u8>,
+) -> Result<IsNull, Box<Error + Sync + Send>> {
+    let (min, max) = match *ty {
+        Type::INT2 => (i16::min_value() as i128, i16::max_value() as i128),
+        Type::INT4 => (i32::min_value() as i128, i32::max_value() as i128),
+        Type::INT8 => (i64::min_value() as i128, i64::max_value() as i128),
+        Type::NUMERIC => (i128::min_value(), i128::max_value()),
+        _ => return Err(Box::new(WrongType::new(ty.clone()))),
+    };
+    if value < min || value > max {
+        return Err(format!("value {} is out of range for type {}", value, ty).into());
+    }
+
+    match *ty {
+        Type::INT2 => types::int2_to_sql(value as i16, out),
+        Type::INT4 => types::int4_to_sql(value as i32, out),
+        Type::INT8 => types::int8_to_sql(value as i64, out),
+        _ => numeric_to_sql(value, out)?,
+    }
+    Ok(IsNull::No)
+}
+
+fn numeric_to_sql(value: i128, out: &mut Vec<u8>) -> Result<(), Box<Error + Sync + Send>> {
+    let sign = if value < 0 {
+        NumericSign::Negative
+    } else {
+        NumericSign::Positive
+    };
+
+    // wrapping so that i128::MIN doesn't overflow
+    let mut magnitude = if value < 0 {
+        (value as u128).wrapping_neg()
+    } else {
+        value as u128
+    };
+    let mut digits = vec![];
+    while magnitude != 0 {
+        digits.push((magnitude % NUMERIC_BASE as u128) as i16);
+        magnitude /= NUMERIC_BASE as u128;
+    }
+    let weight = digits.len() as i16 - 1;
+
+    // Postgres strips trailing zero digits, which are implied by the weight
+    let zeros = digits.iter().take_while(|&&d| d == 0).count();
+    digits.drain(..zeros);
+    digits.reverse();
+
+    let weight = if digits.is_empty() { 0 } else { weight };
+    types::numeric_to_sql(sign, weight, 0, &digits, out)
+}
+
+macro_rules! int_impl {
+    ($($t:ty),*) => {
+        $(
+            impl<'a> FromSql<'a> for Lenient<$t> {
+                fn from_sql(
+                    ty: &Type,
+                    raw: &'a [u8],
+                ) -> Result<Lenient<$t>, Box<Error + Sync + Send>> {
+                    let value = int_from_sql(ty, raw)?;
+                    if value < <$t>::min_value() as i128 || value > <$t>::max_value() as i128 {
+                        return Err(format!(
+                            "{} value {} is out of range for {}",
+                            ty,
+                            value,
+                            stringify!($t)
+                        ).into());
+                    }
+                    Ok(Lenient(value as $t))
+                }
+
+                fn accepts(ty: &Type) -> bool {
+                    accepts_int(ty)
+                }
+            }
+
+            impl ToSql for Lenient<$t> {
+                fn to_sql(
+                    &self,
+                    ty: &Type,
+                    out: &mut Vec<u8>,
+                ) -> Result<IsNull, Box<Error + Sync + Send>> {
+                    int_to_sql(i128::from(self.0), ty, out)
+                }
+
+                fn accepts(ty: &Type) -> bool {
+                    accepts_int(ty)
+                }
+
+                to_sql_checked!();
+            }
+        )*
+    }
+}
+
+int_impl!(i16, i32, i64, i128, u8, u16, u32, u64);
+
+// u8 is left out, since `Vec<u8>` and `&[u8]` correspond to BYTEA rather than arrays, and
+// implementing FromSql and ToSql for it would make their impls overlap with those for `Vec<T>` and
+// `&[T]`
+macro_rules! checked_int_impl {
+    ($($t:ty),*) => {
+        $(
+            impl<'a> FromSql<'a> for $t {
+                fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<$t, Box<Error + Sync + Send>> {
+                    Lenient::<$t>::from_sql(ty, raw).map(Lenient::into_inner)
+                }
+
+                fn accepts(ty: &Type) -> bool {
+                    accepts_int(ty)
+                }
+            }
+
+            impl ToSql for $t {
+                fn to_sql(
+                    &self,
+                    ty: &Type,
+                    out: &mut Vec<u8>,
+                ) -> Result<IsNull, Box<Error + Sync + Send>> {
+                    Lenient(*self).to_sql(ty, out)
+                }
+
+                fn accepts(ty: &Type) -> bool {
+                    accepts_int(ty)
+                }
+
+                to_sql_checked!();
+            }
+        )*
+    }
+}
+
+checked_int_impl!(u16, u64, i128);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn numeric_round_trip() {
+        let values = [
+            0,
+            1,
+            -1,
+            9_999,
+            10_000,
+            -10_000,
+            123_456_789,
+            1_000_000_000_000,
+            i128::from(i64::min_value()),
+            i128::max_value(),
+            i128::min_value(),
+        ];
+        for &value in &values {
+            let mut buf = vec![];
+            numeric_to_sql(value, &mut buf).unwrap();
+            assert_eq!(numeric_from_sql(&buf).unwrap(), value);
+        }
+
+        // 1e4 is stored as a single digit with a weight of 1
+        let mut buf = vec![];
+        numeric_to_sql(10_000, &mut buf).unwrap();
+        let numeric = types::numeric_from_sql(&buf).unwrap();
+        assert_eq!(numeric.weight(), 1);
+        assert_eq!(numeric.digits().collect::<Vec<_>>(), [1]);
+    }
+
+    #[test]
+    fn numeric_fraction() {
+        // 1.5
+        let mut buf = vec![];
+        types::numeric_to_sql(NumericSign::Positive, 0, 1, &[1, 5000], &mut buf).unwrap();
+        assert!(numeric_from_sql(&buf).is_err());
+
+        // 2.0
+        let mut buf = vec![];
+        types::numeric_to_sql(NumericSign::Positive, 0, 1, &[2, 0], &mut buf).unwrap();
+        assert_eq!(numeric_from_sql(&buf).unwrap(), 2);
+    }
+
+    #[test]
+    fn range_checks() {
+        let mut buf = vec![];
+        types::int4_to_sql(300, &mut buf);
+        assert!(Lenient::<u8>::from_sql(&Type::INT4, &buf).is_err());
+        assert_eq!(Lenient::<i64>::from_sql(&Type::INT4, &buf).unwrap().0, 300);
+
+        let mut buf = vec![];
+        types::int8_to_sql(-1, &mut buf);
+        assert!(u64::from_sql(&Type::INT8, &buf).is_err());
+
+        assert!(Lenient(70_000i32).to_sql(&Type::INT2, &mut vec![]).is_err());
+        assert!(u64::max_value().to_sql(&Type::INT8, &mut vec![]).is_err());
+        let min = i16::min_value() as i32;
+        assert!(Lenient(min).to_sql(&Type::INT2, &mut vec![]).is_ok());
+        assert!(Lenient(min - 1).to_sql(&Type::INT2, &mut vec![]).is_err());
+
+        let mut buf = vec![];
+        types::int2_to_sql(255, &mut buf);
+        assert_eq!(Lenient::<u8>::from_sql(&Type::INT2, &buf).unwrap().0, 255);
+        u64::max_value()
+            .to_sql(&Type::NUMERIC, &mut vec![])
+            .unwrap();
+    }
+
+    #[test]
+    fn wrong_type() {
+        let mut buf = vec![];
+        types::int4_to_sql(1, &mut buf);
+        // to_sql and from_sql can be called with any type, unlike their checked counterparts
+        assert!(Lenient::<i32>::from_sql(&Type::TEXT, &buf).is_err());
+        assert!(Lenient(1i32).to_sql(&Type::TEXT, &mut vec![]).is_err());
+    }
+}
@@ -4,18 +4,28 @@ use fallible_iterator::FallibleIterator;
 use postgres_protocol;
 use postgres_protocol::types::{self, ArrayDimension};
 use std::borrow::Cow;
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
 use std::error::Error;
 use std::fmt;
+use std::hash::{BuildHasher, Hash};
+use std::iter::FromIterator;
 use std::sync::Arc;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::vec;
 
+use stmt::{Column, Format};
 use types::type_gen::{Inner, Other};
 
 #[doc(inline)]
 pub use postgres_protocol::Oid;
 
-pub use types::special::{Date, Timestamp};
+pub use types::geometric::{Circle, Line, LineSegment, Point, Polygon};
+pub use types::integer::Lenient;
+pub use types::money::Money;
+pub use types::pg_lsn::{ParseLsnError, PgLsn};
+pub use types::raw_value::RawValue;
+pub use types::special::{Date, Saturating, Timestamp};
+pub use types::text_search::{Lexeme, Position, TsQuery, TsVector, Weight};
 
 // Number of seconds from 1970-01-01 to 2000-01-01
 const TIME_SEC_CONVERSION: u64 = 946684800;
@@ -71,6 +81,8 @@ where
     v.to_sql(ty, out)
 }
 
+#[cfg(feature = "with-arrayvec-0.4")]
+mod arrayvec;
 #[cfg(feature = "with-bit-vec-0.5")]
 mod bit_vec;
 #[cfg(feature = "with-chrono-0.4")]
@@ -79,12 +91,24 @@ mod chrono;
 mod eui48;
 #[cfg(feature = "with-geo-0.10")]
 mod geo;
+#[cfg(feature = "with-postgis")]
+pub mod postgis;
 #[cfg(feature = "with-serde_json-1")]
 mod serde_json;
+#[cfg(feature = "with-smallvec-0.6")]
+mod smallvec;
+#[cfg(feature = "with-time-0.3")]
+mod time;
 #[cfg(feature = "with-uuid-0.6")]
 mod uuid;
 
+mod geometric;
+mod integer;
+mod money;
+mod pg_lsn;
+mod raw_value;
 mod special;
+mod text_search;
 mod type_gen;
 
 /// A Postgres type.
@@ -253,12 +277,25 @@ impl WrongType {
 /// | `i32`                             | INT, SERIAL                                   |
 /// | `u32`                             | OID                                           |
 /// | `i64`                             | BIGINT, BIGSERIAL                             |
+/// | `u16`/`u64`/`i128`                | SMALLINT, INT, BIGINT, NUMERIC                |
+/// | `Lenient<T>` for integers         | SMALLINT, INT, BIGINT, NUMERIC                |
 /// | `f32`                             | REAL                                          |
 /// | `f64`                             | DOUBLE PRECISION                              |
 /// | `&str`/`String`                   | VARCHAR, CHAR(n), TEXT, CITEXT, NAME, UNKNOWN |
+/// | `&str`/`String`                   | XML, JSONPATH, LTREE, LQUERY, LTXTQUERY       |
 /// | `&[u8]`/`Vec<u8>`                 | BYTEA                                         |
 /// | `HashMap<String, Option<String>>` | HSTORE                                        |
 /// | `SystemTime`                      | TIMESTAMP, TIMESTAMP WITH TIME ZONE           |
+/// | `PgLsn`                           | PG_LSN                                        |
+/// | `Money`                           | MONEY                                         |
+/// | `TsVector`                        | TSVECTOR                                      |
+/// | `TsQuery`                         | TSQUERY                                       |
+/// | `Point`                           | POINT                                         |
+/// | `Line`                            | LINE                                          |
+/// | `LineSegment`                     | LSEG                                          |
+/// | `Polygon`                         | POLYGON                                       |
+/// | `Circle`                          | CIRCLE                                        |
+/// | `RawValue`                        | any                                           |
 ///
 /// In addition, some implementations are provided for types in third party
 /// crates. These are disabled by default; to opt into one of these
@@ -266,21 +303,35 @@ impl WrongType {
 /// name prefixed by `with-`. For example, the `with-serde_json` feature enables
 /// the implementation for the `serde_json::Value` type.
 ///
-/// | Rust type                       | Postgres type(s)                    |
-/// |---------------------------------|-------------------------------------|
-/// | `serialize::json::Json`         | JSON, JSONB                         |
-/// | `serde_json::Value`             | JSON, JSONB                         |
-/// | `time::Timespec`                | TIMESTAMP, TIMESTAMP WITH TIME ZONE |
-/// | `chrono::NaiveDateTime`         | TIMESTAMP                           |
-/// | `chrono::DateTime<Utc>`         | TIMESTAMP WITH TIME ZONE            |
-/// | `chrono::DateTime<Local>`       | TIMESTAMP WITH TIME ZONE            |
-/// | `chrono::DateTime<FixedOffset>` | TIMESTAMP WITH TIME ZONE            |
-/// | `chrono::NaiveDate`             | DATE                                |
-/// | `chrono::NaiveTime`             | TIME                                |
-/// | `eui48::MacAddress`             | MACADDR                             |
-/// | `uuid::Uuid`                    | UUID                                |
-/// | `bit_vec::BitVec`               | BIT, VARBIT                         |
-/// | `eui48::MacAddress`             | MACADDR                             |
+/// | Rust type                                  | Postgres type(s)                    |
+/// |--------------------------------------------|-------------------------------------|
+/// | `serialize::json::Json`                    | JSON, JSONB                         |
+/// | `serde_json::Value`                        | JSON, JSONB                         |
+/// | `time::Timespec`                           | TIMESTAMP, TIMESTAMP WITH TIME ZONE |
+/// | `chrono::NaiveDateTime`                    | TIMESTAMP                           |
+/// | `chrono::DateTime<Utc>`                    | TIMESTAMP WITH TIME ZONE            |
+/// | `chrono::DateTime<Local>`                  | TIMESTAMP WITH TIME ZONE            |
+/// | `chrono::DateTime<FixedOffset>`            | TIMESTAMP WITH TIME ZONE            |
+/// | `chrono::NaiveDate`                        | DATE                                |
+/// | `chrono::NaiveTime`                        | TIME                                |
+/// | `(chrono::NaiveTime, chrono::FixedOffset)` | TIME WITH TIME ZONE                 |
+/// | `time::PrimitiveDateTime`                  | TIMESTAMP                           |
+/// | `time::OffsetDateTime`                     | TIMESTAMP WITH TIME ZONE            |
+/// | `time::Date`                               | DATE                                |
+/// | `time::Time`                               | TIME                                |
+/// | `(time::Time, time::UtcOffset)`            | TIME WITH TIME ZONE                 |
+/// | `time::Duration`                           | INTERVAL                            |
+/// | `eui48::MacAddress`                        | MACADDR                             |
+/// | `uuid::Uuid`                               | UUID                                |
+/// | `bit_vec::BitVec`                          | BIT, VARBIT                         |
+/// | `eui48::MacAddress`                        | MACADDR                             |
+/// | `geo::Point<f64>`                          | POINT                               |
+/// | `geo::Rect<f64>`                           | BOX                                 |
+/// | `geo::LineString<f64>`                     | PATH                                |
+/// | `geo::Line<f64>`                           | LSEG                                |
+/// | `geo::Polygon<f64>`                        | POLYGON                             |
+/// | `postgis::Geometry`, etc.                  | GEOMETRY, GEOGRAPHY                 |
+/// | `geo::Geometry<f64>`, etc.                 | GEOMETRY, GEOGRAPHY                 |
 ///
 /// # Nullability
 ///
@@ -290,8 +341,13 @@ impl WrongType {
 ///
 /// # Arrays
 ///
-/// `FromSql` is implemented for `Vec<T>` where `T` implements `FromSql`, and
-/// corresponds to one-dimensional Postgres arrays.
+/// `FromSql` is implemented for `Vec<T>`, `Box<[T]>`, `VecDeque<T>`,
+/// `HashSet<T>`, `BTreeSet<T>` and `[T; N]` for `N` up to 32 where `T`
+/// implements `FromSql`, and corresponds to one-dimensional Postgres arrays.
+/// Reading an array into `[T; N]` fails unless it has exactly `N` elements,
+/// and reading it into a set discards duplicate elements. The
+/// `with-smallvec-0.6` and `with-arrayvec-0.4` features add implementations
+/// for `SmallVec` and `ArrayVec`.
 pub trait FromSql<'a>: Sized {
     /// Creates a new value of this type from a buffer of data of the specified
     /// Postgres `Type` in its binary format.
@@ -327,6 +383,50 @@ pub trait FromSql<'a>: Sized {
     /// Determines if a value of this type can be created from the specified
     /// Postgres `Type`.
     fn accepts(ty: &Type) -> bool;
+
+    #[doc(hidden)]
+    #[allow(unused_variables)]
+    fn __accepts_text(ty: &Type) -> bool {
+        Self::accepts(&Type::TEXT)
+    }
+
+    #[doc(hidden)]
+    #[allow(unused_variables)]
+    fn __from_sql_text(ty: &Type, raw: &'a [u8]) -> Result<Self, Box<Error + Sync + Send>> {
+        Self::from_sql(&Type::TEXT, raw)
+    }
+}
+
+/// Decodes a value of a column in the column's result format.
+///
+/// A value in the text format is decoded like a binary `TEXT` value, unless the Rust type handles
+/// the text format of the column's type itself.
+#[doc(hidden)]
+pub fn __from_sql_column<'a, T>(
+    column: &Column,
+    raw: Option<&'a [u8]>,
+) -> Result<T, Box<Error + Sync + Send>>
+where
+    T: FromSql<'a>,
+{
+    let ty = column.type_();
+    match column.format() {
+        Format::Binary => {
+            if !T::accepts(ty) {
+                return Err(Box::new(WrongType::new(ty.clone())));
+            }
+            T::from_sql_nullable(ty, raw)
+        }
+        Format::Text => {
+            if !T::__accepts_text(ty) {
+                return Err(Box::new(WrongType::new(ty.clone())));
+            }
+            match raw {
+                Some(raw) => T::__from_sql_text(ty, raw),
+                None => T::from_sql_null(&Type::TEXT),
+            }
+        }
+    }
 }
 
 /// A trait for types which can be created from a Postgres value without borrowing any data.
@@ -352,24 +452,56 @@ impl<'a, T: FromSql<'a>> FromSql<'a> for Option<T> {
     fn accepts(ty: &Type) -> bool {
         <T as FromSql>::accepts(ty)
     }
+
+    fn __accepts_text(ty: &Type) -> bool {
+        <T as FromSql>::__accepts_text(ty)
+    }
+
+    fn __from_sql_text(ty: &Type, raw: &'a [u8]) -> Result<Option<T>, Box<Error + Sync + Send>> {
+        <T as FromSql>::__from_sql_text(ty, raw).map(Some)
+    }
 }
 
-impl<'a, T: FromSql<'a>> FromSql<'a> for Vec<T> {
-    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Vec<T>, Box<Error + Sync + Send>> {
-        let member_type = match *ty.kind() {
-            Kind::Array(ref member) => member,
-            _ => panic!("expected array type"),
-        };
+// Decodes a one-dimensional array into any collection of its elements.
+fn from_sql_array<'a, T, C>(ty: &Type, raw: &'a [u8]) -> Result<C, Box<Error + Sync + Send>>
+where
+    T: FromSql<'a>,
+    C: FromIterator<T>,
+{
+    let member_type = match *ty.kind() {
+        Kind::Array(ref member) => member,
+        _ => panic!("expected array type"),
+    };
+
+    let array = types::array_from_sql(raw)?;
+    if array.dimensions().count()? > 1 {
+        return Err("array contains too many dimensions".into());
+    }
 
-        let array = types::array_from_sql(raw)?;
-        if array.dimensions().count()? > 1 {
-            return Err("array contains too many dimensions".into());
-        }
+    array
+        .values()
+        .and_then(|v| T::from_sql_nullable(member_type, v))
+        .iterator()
+        .collect()
+}
 
-        array
-            .values()
-            .and_then(|v| T::from_sql_nullable(member_type, v))
-            .collect()
+// Returns the number of elements of a one-dimensional array without decoding them.
+fn array_len(raw: &[u8]) -> Result<usize, Box<Error + Sync + Send>> {
+    let array = types::array_from_sql(raw)?;
+    let mut dimensions = array.dimensions();
+    let len = match dimensions.next()? {
+        Some(dimension) => dimension.len as usize,
+        None => 0,
+    };
+    if dimensions.next()?.is_some() {
+        return Err("array contains too many dimensions".into());
+    }
+    Ok(len)
+}
+
+impl<'a, T: FromSql<'a>> FromSql<'a> for Vec<T> {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Vec<T>, Box<Error + Sync + Send>> {
+        from_sql_array(ty, raw)
     }
 
     fn accepts(ty: &Type) -> bool {
@@ -380,6 +512,50 @@ impl<'a, T: FromSql<'a>> FromSql<'a> for Vec<T> {
     }
 }
 
+impl<'a, T: FromSql<'a>> FromSql<'a> for Box<[T]> {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<Box<[T]>, Box<Error + Sync + Send>> {
+        Vec::<T>::from_sql(ty, raw).map(Vec::into_boxed_slice)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <Vec<T> as FromSql>::accepts(ty)
+    }
+}
+
+impl<'a, T: FromSql<'a>> FromSql<'a> for VecDeque<T> {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<VecDeque<T>, Box<Error + Sync + Send>> {
+        from_sql_array(ty, raw)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <Vec<T> as FromSql>::accepts(ty)
+    }
+}
+
+impl<'a, T, S> FromSql<'a> for HashSet<T, S>
+where
+    T: FromSql<'a> + Eq + Hash,
+    S: BuildHasher + Default,
+{
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<HashSet<T, S>, Box<Error + Sync + Send>> {
+        from_sql_array(ty, raw)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <Vec<T> as FromSql>::accepts(ty)
+    }
+}
+
+impl<'a, T: FromSql<'a> + Ord> FromSql<'a> for BTreeSet<T> {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<BTreeSet<T>, Box<Error + Sync + Send>> {
+        from_sql_array(ty, raw)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <Vec<T> as FromSql>::accepts(ty)
+    }
+}
+
 impl<'a> FromSql<'a> for Vec<u8> {
     fn from_sql(_: &Type, raw: &'a [u8]) -> Result<Vec<u8>, Box<Error + Sync + Send>> {
         Ok(types::bytea_from_sql(raw).to_owned())
@@ -397,8 +573,8 @@ impl<'a> FromSql<'a> for &'a [u8] {
 }
 
 impl<'a> FromSql<'a> for String {
-    fn from_sql(_: &Type, raw: &'a [u8]) -> Result<String, Box<Error + Sync + Send>> {
-        types::text_from_sql(raw).map(|b| b.to_owned())
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<String, Box<Error + Sync + Send>> {
+        <&str as FromSql>::from_sql(ty, raw).map(|b| b.to_owned())
     }
 
     fn accepts(ty: &Type) -> bool {
@@ -407,16 +583,28 @@ impl<'a> FromSql<'a> for String {
 }
 
 impl<'a> FromSql<'a> for &'a str {
-    fn from_sql(_: &Type, raw: &'a [u8]) -> Result<&'a str, Box<Error + Sync + Send>> {
-        types::text_from_sql(raw)
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<&'a str, Box<Error + Sync + Send>> {
+        match ty.name() {
+            "jsonpath" => types::jsonpath_from_sql(raw),
+            "ltree" | "lquery" | "ltxtquery" => types::ltree_from_sql(raw),
+            _ => types::text_from_sql(raw),
+        }
     }
 
     fn accepts(ty: &Type) -> bool {
-        match *ty {
-            Type::VARCHAR | Type::TEXT | Type::BPCHAR | Type::NAME | Type::UNKNOWN => true,
-            ref ty if ty.name() == "citext" => true,
+        accepts_str(ty)
+    }
+}
+
+// jsonpath and the ltree extension's types are text prefixed by a format version in their binary
+// representation
+fn accepts_str(ty: &Type) -> bool {
+    match *ty {
+        Type::VARCHAR | Type::TEXT | Type::BPCHAR | Type::NAME | Type::UNKNOWN | Type::XML => true,
+        ref ty => match ty.name() {
+            "citext" | "jsonpath" | "ltree" | "lquery" | "ltxtquery" => true,
             _ => false,
-        }
+        },
     }
 }
 
@@ -462,24 +650,50 @@ impl<'a> FromSql<'a> for SystemTime {
         let epoch = UNIX_EPOCH + Duration::from_secs(TIME_SEC_CONVERSION);
 
         let negative = time < 0;
-        let time = time.abs() as u64;
+        // wrapping so that i64::MIN doesn't overflow
+        let time = time.wrapping_abs() as u64;
 
         let secs = time / USEC_PER_SEC;
         let nsec = (time % USEC_PER_SEC) * NSEC_PER_USEC;
         let offset = Duration::new(secs, nsec as u32);
 
         let time = if negative {
-            epoch - offset
+            system_time_checked_sub(epoch, offset)
         } else {
-            epoch + offset
+            system_time_checked_add(epoch, offset)
         };
 
-        Ok(time)
+        match time {
+            Some(time) => Ok(time),
+            None => Err("value out of range for a SystemTime".into()),
+        }
     }
 
     accepts!(TIMESTAMP, TIMESTAMPTZ);
 }
 
+#[cfg(has_system_time_checked)]
+fn system_time_checked_add(time: SystemTime, duration: Duration) -> Option<SystemTime> {
+    time.checked_add(duration)
+}
+
+#[cfg(has_system_time_checked)]
+fn system_time_checked_sub(time: SystemTime, duration: Duration) -> Option<SystemTime> {
+    time.checked_sub(duration)
+}
+
+// Older compilers can't check for overflow, which panics on platforms whose `SystemTime` can't
+// represent the full range of a Postgres timestamp.
+#[cfg(not(has_system_time_checked))]
+fn system_time_checked_add(time: SystemTime, duration: Duration) -> Option<SystemTime> {
+    Some(time + duration)
+}
+
+#[cfg(not(has_system_time_checked))]
+fn system_time_checked_sub(time: SystemTime, duration: Duration) -> Option<SystemTime> {
+    Some(time - duration)
+}
+
 /// An enum representing the nullability of a Postgres value.
 pub enum IsNull {
     /// The value is NULL.
@@ -503,12 +717,25 @@ pub enum IsNull {
 /// | `i32`                             | INT, SERIAL                          |
 /// | `u32`                             | OID                                  |
 /// | `i64`                             | BIGINT, BIGSERIAL                    |
+/// | `u16`/`u64`/`i128`                | SMALLINT, INT, BIGINT, NUMERIC       |
+/// | `Lenient<T>` for integers         | SMALLINT, INT, BIGINT, NUMERIC       |
 /// | `f32`                             | REAL                                 |
 /// | `f64`                             | DOUBLE PRECISION                     |
 /// | `&str`/`String`                   | VARCHAR, CHAR(n), TEXT, CITEXT, NAME |
+/// | `&str`/`String`                   | XML, JSONPATH, LTREE, LQUERY, LTXTQUERY |
 /// | `&[u8]`/Vec<u8>`                  | BYTEA                                |
 /// | `HashMap<String, Option<String>>` | HSTORE                               |
 /// | `SystemTime`                      | TIMESTAMP, TIMESTAMP WITH TIME ZONE           |
+/// | `PgLsn`                           | PG_LSN                                        |
+/// | `Money`                           | MONEY                                         |
+/// | `TsVector`                        | TSVECTOR                                      |
+/// | `TsQuery`                         | TSQUERY                                       |
+/// | `Point`                           | POINT                                         |
+/// | `Line`                            | LINE                                          |
+/// | `LineSegment`                     | LSEG                                          |
+/// | `Polygon`                         | POLYGON                                       |
+/// | `Circle`                          | CIRCLE                                        |
+/// | `RawValue`                        | any                                           |
 ///
 /// In addition, some implementations are provided for types in third party
 /// crates. These are disabled by default; to opt into one of these
@@ -516,20 +743,34 @@ pub enum IsNull {
 /// name prefixed by `with-`. For example, the `with-serde_json` feature enables
 /// the implementation for the `serde_json::Value` type.
 ///
-/// | Rust type                       | Postgres type(s)                    |
-/// |---------------------------------|-------------------------------------|
-/// | `serialize::json::Json`         | JSON, JSONB                         |
-/// | `serde_json::Value`             | JSON, JSONB                         |
-/// | `time::Timespec`                | TIMESTAMP, TIMESTAMP WITH TIME ZONE |
-/// | `chrono::NaiveDateTime`         | TIMESTAMP                           |
-/// | `chrono::DateTime<Utc>`         | TIMESTAMP WITH TIME ZONE            |
-/// | `chrono::DateTime<Local>`       | TIMESTAMP WITH TIME ZONE            |
-/// | `chrono::DateTime<FixedOffset>` | TIMESTAMP WITH TIME ZONE            |
-/// | `chrono::NaiveDate`             | DATE                                |
-/// | `chrono::NaiveTime`             | TIME                                |
-/// | `uuid::Uuid`                    | UUID                                |
-/// | `bit_vec::BitVec`               | BIT, VARBIT                         |
-/// | `eui48::MacAddress`             | MACADDR                             |
+/// | Rust type                                  | Postgres type(s)                    |
+/// |--------------------------------------------|-------------------------------------|
+/// | `serialize::json::Json`                    | JSON, JSONB                         |
+/// | `serde_json::Value`                        | JSON, JSONB                         |
+/// | `time::Timespec`                           | TIMESTAMP, TIMESTAMP WITH TIME ZONE |
+/// | `chrono::NaiveDateTime`                    | TIMESTAMP                           |
+/// | `chrono::DateTime<Utc>`                    | TIMESTAMP WITH TIME ZONE            |
+/// | `chrono::DateTime<Local>`                  | TIMESTAMP WITH TIME ZONE            |
+/// | `chrono::DateTime<FixedOffset>`            | TIMESTAMP WITH TIME ZONE            |
+/// | `chrono::NaiveDate`                        | DATE                                |
+/// | `chrono::NaiveTime`                        | TIME                                |
+/// | `(chrono::NaiveTime, chrono::FixedOffset)` | TIME WITH TIME ZONE                 |
+/// | `time::PrimitiveDateTime`                  | TIMESTAMP                           |
+/// | `time::OffsetDateTime`                     | TIMESTAMP WITH TIME ZONE            |
+/// | `time::Date`                               | DATE                                |
+/// | `time::Time`                               | TIME                                |
+/// | `(time::Time, time::UtcOffset)`            | TIME WITH TIME ZONE                 |
+/// | `time::Duration`                           | INTERVAL                            |
+/// | `uuid::Uuid`                               | UUID                                |
+/// | `bit_vec::BitVec`                          | BIT, VARBIT                         |
+/// | `eui48::MacAddress`                        | MACADDR                             |
+/// | `geo::Point<f64>`                          | POINT                               |
+/// | `geo::Rect<f64>`                           | BOX                                 |
+/// | `geo::LineString<f64>`                     | PATH                                |
+/// | `geo::Line<f64>`                           | LSEG                                |
+/// | `geo::Polygon<f64>`                        | POLYGON                             |
+/// | `postgis::Geometry`, etc.                  | GEOMETRY, GEOGRAPHY                 |
+/// | `geo::Geometry<f64>`, etc.                 | GEOMETRY, GEOGRAPHY                 |
 ///
 /// # Nullability
 ///
@@ -539,8 +780,11 @@ pub enum IsNull {
 ///
 /// # Arrays
 ///
-/// `ToSql` is implemented for `Vec<T>` and `&[T]` where `T` implements `ToSql`,
-/// and corresponds to one-dimentional Postgres arrays with an index offset of 1.
+/// `ToSql` is implemented for `Vec<T>`, `&[T]`, `Box<[T]>`, `[T; N]` for `N`
+/// up to 32, `VecDeque<T>`, `HashSet<T>` and `BTreeSet<T>` where `T`
+/// implements `ToSql`, and corresponds to one-dimentional Postgres arrays with
+/// an index offset of 1. The `with-smallvec-0.6` and `with-arrayvec-0.4`
+/// features add implementations for `SmallVec` and `ArrayVec`.
 pub trait ToSql: fmt::Debug {
     /// Converts the value of `self` into the binary format of the specified
     /// Postgres `Type`, appending it to `out`.
@@ -602,29 +846,43 @@ impl<T: ToSql> ToSql for Option<T> {
     to_sql_checked!();
 }
 
+// Encodes a one-dimensional array from any collection of its elements.
+fn to_sql_array<'a, T, I>(
+    ty: &Type,
+    len: usize,
+    elements: I,
+    w: &mut Vec<u8>,
+) -> Result<IsNull, Box<Error + Sync + Send>>
+where
+    T: 'a + ToSql,
+    I: IntoIterator<Item = &'a T>,
+{
+    let member_type = match *ty.kind() {
+        Kind::Array(ref member) => member,
+        _ => panic!("expected array type"),
+    };
+
+    let dimension = ArrayDimension {
+        len: downcast(len)?,
+        lower_bound: 1,
+    };
+
+    types::array_to_sql(
+        Some(dimension),
+        member_type.oid(),
+        elements,
+        |e, w| match e.to_sql(member_type, w)? {
+            IsNull::No => Ok(postgres_protocol::IsNull::No),
+            IsNull::Yes => Ok(postgres_protocol::IsNull::Yes),
+        },
+        w,
+    )?;
+    Ok(IsNull::No)
+}
+
 impl<'a, T: ToSql> ToSql for &'a [T] {
     fn to_sql(&self, ty: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<Error + Sync + Send>> {
-        let member_type = match *ty.kind() {
-            Kind::Array(ref member) => member,
-            _ => panic!("expected array type"),
-        };
-
-        let dimension = ArrayDimension {
-            len: downcast(self.len())?,
-            lower_bound: 1,
-        };
-
-        types::array_to_sql(
-            Some(dimension),
-            member_type.oid(),
-            self.iter(),
-            |e, w| match e.to_sql(member_type, w)? {
-                IsNull::No => Ok(postgres_protocol::IsNull::No),
-                IsNull::Yes => Ok(postgres_protocol::IsNull::Yes),
-            },
-            w,
-        )?;
-        Ok(IsNull::No)
+        to_sql_array(ty, self.len(), self.iter(), w)
     }
 
     fn accepts(ty: &Type) -> bool {
@@ -660,6 +918,108 @@ impl<T: ToSql> ToSql for Vec<T> {
     to_sql_checked!();
 }
 
+impl<T: ToSql> ToSql for Box<[T]> {
+    fn to_sql(&self, ty: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<Error + Sync + Send>> {
+        <&[T] as ToSql>::to_sql(&&**self, ty, w)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <&[T] as ToSql>::accepts(ty)
+    }
+
+    to_sql_checked!();
+}
+
+// Moves the next element out of an array whose length has already been checked.
+fn next_element<T>(values: &mut vec::IntoIter<T>) -> T {
+    values.next().expect("array length was checked")
+}
+
+// Implements `FromSql` and `ToSql` for `[T; $n]` and every smaller array, taking one `T` per
+// element.
+macro_rules! array_impls {
+    (@next $n:expr, $t:ident $($ts:ident)*) => {
+        array_impls!($n - 1, $($ts)*);
+    };
+    (@next $n:expr,) => {};
+    ($n:expr, $($t:ident)*) => {
+        impl<'a, T: FromSql<'a>> FromSql<'a> for [T; $n] {
+            #[allow(unused_mut, unused_variables)]
+            fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<[T; $n], Box<Error + Sync + Send>> {
+                let len = array_len(raw)?;
+                if len != $n {
+                    return Err(
+                        format!("expected an array of {} elements but got {}", $n, len).into(),
+                    );
+                }
+
+                let mut values = Vec::<T>::from_sql(ty, raw)?.into_iter();
+                Ok([$(next_element::<$t>(&mut values)),*])
+            }
+
+            fn accepts(ty: &Type) -> bool {
+                <Vec<T> as FromSql>::accepts(ty)
+            }
+        }
+
+        impl<T: ToSql> ToSql for [T; $n] {
+            fn to_sql(
+                &self,
+                ty: &Type,
+                w: &mut Vec<u8>,
+            ) -> Result<IsNull, Box<Error + Sync + Send>> {
+                <&[T] as ToSql>::to_sql(&&self[..], ty, w)
+            }
+
+            fn accepts(ty: &Type) -> bool {
+                <&[T] as ToSql>::accepts(ty)
+            }
+
+            to_sql_checked!();
+        }
+
+        array_impls!(@next $n, $($t)*);
+    };
+}
+
+array_impls!(32, T T T T T T T T T T T T T T T T T T T T T T T T T T T T T T T T);
+
+impl<T: ToSql> ToSql for VecDeque<T> {
+    fn to_sql(&self, ty: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<Error + Sync + Send>> {
+        to_sql_array(ty, self.len(), self, w)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <&[T] as ToSql>::accepts(ty)
+    }
+
+    to_sql_checked!();
+}
+
+impl<T: ToSql + Eq + Hash, S: BuildHasher> ToSql for HashSet<T, S> {
+    fn to_sql(&self, ty: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<Error + Sync + Send>> {
+        to_sql_array(ty, self.len(), self, w)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <&[T] as ToSql>::accepts(ty)
+    }
+
+    to_sql_checked!();
+}
+
+impl<T: ToSql + Ord> ToSql for BTreeSet<T> {
+    fn to_sql(&self, ty: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<Error + Sync + Send>> {
+        to_sql_array(ty, self.len(), self, w)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <&[T] as ToSql>::accepts(ty)
+    }
+
+    to_sql_checked!();
+}
+
 impl ToSql for Vec<u8> {
     fn to_sql(&self, ty: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<Error + Sync + Send>> {
         <&[u8] as ToSql>::to_sql(&&**self, ty, w)
@@ -673,17 +1033,17 @@ impl ToSql for Vec<u8> {
 }
 
 impl<'a> ToSql for &'a str {
-    fn to_sql(&self, _: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<Error + Sync + Send>> {
-        types::text_to_sql(*self, w);
+    fn to_sql(&self, ty: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<Error + Sync + Send>> {
+        match ty.name() {
+            "jsonpath" => types::jsonpath_to_sql(*self, w),
+            "ltree" | "lquery" | "ltxtquery" => types::ltree_to_sql(*self, w),
+            _ => types::text_to_sql(*self, w),
+        }
         Ok(IsNull::No)
     }
 
     fn accepts(ty: &Type) -> bool {
-        match *ty {
-            Type::VARCHAR | Type::TEXT | Type::BPCHAR | Type::NAME | Type::UNKNOWN => true,
-            ref ty if ty.name() == "citext" => true,
-            _ => false,
-        }
+        accepts_str(ty)
     }
 
     to_sql_checked!();
@@ -760,12 +1120,32 @@ impl ToSql for SystemTime {
     fn to_sql(&self, _: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<Error + Sync + Send>> {
         let epoch = UNIX_EPOCH + Duration::from_secs(TIME_SEC_CONVERSION);
 
-        let to_usec =
-            |d: Duration| d.as_secs() * USEC_PER_SEC + (d.subsec_nanos() as u64) / NSEC_PER_USEC;
+        let to_usec = |d: Duration| {
+            d.as_secs()
+                .checked_mul(USEC_PER_SEC)
+                .and_then(|usec| usec.checked_add((d.subsec_nanos() as u64) / NSEC_PER_USEC))
+        };
 
         let time = match self.duration_since(epoch) {
-            Ok(duration) => to_usec(duration) as i64,
-            Err(e) => -(to_usec(e.duration()) as i64),
+            Ok(duration) => to_usec(duration).and_then(|usec| {
+                if usec > i64::max_value() as u64 {
+                    None
+                } else {
+                    Some(usec as i64)
+                }
+            }),
+            // the magnitude of i64::MIN is one more than i64::MAX
+            Err(e) => to_usec(e.duration()).and_then(|usec| {
+                if usec > i64::max_value() as u64 + 1 {
+                    None
+                } else {
+                    Some((usec as i64).wrapping_neg())
+                }
+            }),
+        };
+        let time = match time {
+            Some(time) => time,
+            None => return Err("value too large to transmit".into()),
         };
 
         types::timestamp_to_sql(time, w);
@@ -784,3 +1164,29 @@ fn downcast(len: usize) -> Result<i32, Box<Error + Sync + Send>> {
         Ok(len as i32)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use types::special::test::sample_i64s;
+
+    #[test]
+    fn system_time_range() {
+        // representable by `SystemTime` on every platform
+        const USEC_PER_YEAR: i64 = 366 * 86_400 * USEC_PER_SEC as i64;
+
+        for t in sample_i64s() {
+            let mut raw = vec![];
+            types::timestamp_to_sql(t, &mut raw);
+
+            match SystemTime::from_sql(&Type::TIMESTAMP, &raw) {
+                Ok(time) => {
+                    let mut buf = vec![];
+                    time.to_sql(&Type::TIMESTAMP, &mut buf).unwrap();
+                    assert_eq!(buf, raw);
+                }
+                Err(_) => assert!(t < -30 * USEC_PER_YEAR || t > 30 * USEC_PER_YEAR),
+            }
+        }
+    }
+}
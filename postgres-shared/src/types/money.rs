@@ -0,0 +1,91 @@
+use postgres_protocol::types;
+use std::error::Error;
+use std::fmt;
+
+use types::{FromSql, IsNull, ToSql, Type};
+
+/// A monetary amount, corresponding to the `MONEY` type.
+///
+/// Postgres stores amounts as a number of the smallest units of the currency configured by the
+/// `lc_monetary` setting. `Money` assumes that currency has two fractional digits, which is the
+/// case for most locales, and formats amounts without a currency symbol or grouping separators,
+/// e.g. `-1234.56`.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Money(i64);
+
+impl Money {
+    /// Creates a new `Money` from a number of cents.
+    pub fn from_cents(cents: i64) -> Money {
+        Money(cents)
+    }
+
+    /// Returns the amount in cents.
+    pub fn cents(self) -> i64 {
+        self.0
+    }
+}
+
+impl From<i64> for Money {
+    fn from(cents: i64) -> Money {
+        Money(cents)
+    }
+}
+
+impl From<Money> for i64 {
+    fn from(money: Money) -> i64 {
+        money.0
+    }
+}
+
+impl fmt::Display for Money {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        // computed in u64 so i64::MIN doesn't overflow
+        let cents = if self.0 < 0 {
+            fmt.write_str("-")?;
+            (self.0 as u64).wrapping_neg()
+        } else {
+            self.0 as u64
+        };
+        write!(fmt, "{}.{:02}", cents / 100, cents % 100)
+    }
+}
+
+impl fmt::Debug for Money {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "Money({})", self)
+    }
+}
+
+impl<'a> FromSql<'a> for Money {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<Money, Box<Error + Sync + Send>> {
+        types::int8_from_sql(raw).map(Money)
+    }
+
+    accepts!(MONEY);
+}
+
+impl ToSql for Money {
+    fn to_sql(&self, _: &Type, out: &mut Vec<u8>) -> Result<IsNull, Box<Error + Sync + Send>> {
+        types::int8_to_sql(self.0, out);
+        Ok(IsNull::No)
+    }
+
+    accepts!(MONEY);
+    to_sql_checked!();
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn display() {
+        assert_eq!(Money::from_cents(123456).to_string(), "1234.56");
+        assert_eq!(Money::from_cents(-5).to_string(), "-0.05");
+        assert_eq!(Money::from_cents(0).to_string(), "0.00");
+        assert_eq!(
+            Money::from_cents(i64::min_value()).to_string(),
+            "-92233720368547758.08"
+        );
+    }
+}
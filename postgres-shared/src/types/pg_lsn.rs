@@ -0,0 +1,183 @@
+use postgres_protocol::types;
+use std::error::Error;
+use std::fmt;
+use std::ops::{Add, AddAssign, Sub};
+use std::str::FromStr;
+
+use types::{FromSql, IsNull, ToSql, Type};
+
+/// A Postgres write-ahead log location, corresponding to the `PG_LSN` type.
+///
+/// LSNs are formatted as two hexadecimal numbers of up to 8 digits each, separated by a slash,
+/// e.g. `16/B374D848`.
+///
+/// A number of bytes can be added to or subtracted from an LSN with `+` and `-`, and subtracting
+/// one LSN from another returns the number of bytes between them. Like integer arithmetic, these
+/// panic on overflow when overflow checks are enabled; use `checked_add` and `checked_sub` to
+/// detect it instead.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct PgLsn(u64);
+
+impl PgLsn {
+    /// Adds a number of bytes to the LSN, returning `None` on overflow.
+    pub fn checked_add(self, bytes: u64) -> Option<PgLsn> {
+        self.0.checked_add(bytes).map(PgLsn)
+    }
+
+    /// Subtracts a number of bytes from the LSN, returning `None` on underflow.
+    pub fn checked_sub(self, bytes: u64) -> Option<PgLsn> {
+        self.0.checked_sub(bytes).map(PgLsn)
+    }
+
+    /// Returns the number of the WAL segment containing the LSN, given the server's WAL segment
+    /// size (16MB by default).
+    ///
+    /// Returns `None` if `segment_size` is not a power of two no larger than 4GB.
+    pub fn segment_number(self, segment_size: u64) -> Option<u64> {
+        if valid_segment_size(segment_size) {
+            Some(self.0 / segment_size)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the offset of the LSN within its WAL segment.
+    ///
+    /// Returns `None` if `segment_size` is not a power of two no larger than 4GB.
+    pub fn segment_offset(self, segment_size: u64) -> Option<u64> {
+        if valid_segment_size(segment_size) {
+            Some(self.0 % segment_size)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the name of the WAL segment file containing the LSN on the specified timeline.
+    ///
+    /// Returns `None` if `segment_size` is not a power of two no larger than 4GB.
+    pub fn wal_file_name(self, timeline: u32, segment_size: u64) -> Option<String> {
+        let segment = self.segment_number(segment_size)?;
+        let segments_per_id = 0x1_0000_0000 / segment_size;
+        Some(format!(
+            "{:08X}{:08X}{:08X}",
+            timeline,
+            segment / segments_per_id,
+            segment % segments_per_id
+        ))
+    }
+}
+
+// the server only supports power of two segment sizes, and file names assume a segment can't span
+// more than one 4GB "log id"
+fn valid_segment_size(segment_size: u64) -> bool {
+    segment_size.is_power_of_two() && segment_size <= 0x1_0000_0000
+}
+
+impl From<u64> for PgLsn {
+    fn from(lsn: u64) -> PgLsn {
+        PgLsn(lsn)
+    }
+}
+
+impl From<PgLsn> for u64 {
+    fn from(lsn: PgLsn) -> u64 {
+        lsn.0
+    }
+}
+
+impl fmt::Display for PgLsn {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "{:X}/{:X}", self.0 >> 32, self.0 & 0xffff_ffff)
+    }
+}
+
+impl fmt::Debug for PgLsn {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        write!(fmt, "PgLsn({})", self)
+    }
+}
+
+impl FromStr for PgLsn {
+    type Err = ParseLsnError;
+
+    fn from_str(s: &str) -> Result<PgLsn, ParseLsnError> {
+        let mut it = s.splitn(2, '/');
+        let hi = it.next().and_then(parse_half);
+        let lo = it.next().and_then(parse_half);
+        match (hi, lo) {
+            (Some(hi), Some(lo)) => Ok(PgLsn((u64::from(hi) << 32) | u64::from(lo))),
+            _ => Err(ParseLsnError(())),
+        }
+    }
+}
+
+fn parse_half(s: &str) -> Option<u32> {
+    if s.is_empty() || s.len() > 8 {
+        return None;
+    }
+    u32::from_str_radix(s, 16).ok()
+}
+
+impl Add<u64> for PgLsn {
+    type Output = PgLsn;
+
+    fn add(self, bytes: u64) -> PgLsn {
+        PgLsn(self.0 + bytes)
+    }
+}
+
+impl AddAssign<u64> for PgLsn {
+    fn add_assign(&mut self, bytes: u64) {
+        self.0 += bytes;
+    }
+}
+
+impl Sub<u64> for PgLsn {
+    type Output = PgLsn;
+
+    fn sub(self, bytes: u64) -> PgLsn {
+        PgLsn(self.0 - bytes)
+    }
+}
+
+impl Sub<PgLsn> for PgLsn {
+    type Output = u64;
+
+    fn sub(self, other: PgLsn) -> u64 {
+        self.0 - other.0
+    }
+}
+
+impl<'a> FromSql<'a> for PgLsn {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<PgLsn, Box<Error + Sync + Send>> {
+        types::int8_from_sql(raw).map(|v| PgLsn(v as u64))
+    }
+
+    accepts!(PG_LSN);
+}
+
+impl ToSql for PgLsn {
+    fn to_sql(&self, _: &Type, out: &mut Vec<u8>) -> Result<IsNull, Box<Error + Sync + Send>> {
+        types::int8_to_sql(self.0 as i64, out);
+        Ok(IsNull::No)
+    }
+
+    accepts!(PG_LSN);
+    to_sql_checked!();
+}
+
+/// An error parsing a `PgLsn`.
+#[derive(Debug)]
+pub struct ParseLsnError(());
+
+impl fmt::Display for ParseLsnError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str(self.description())
+    }
+}
+
+impl Error for ParseLsnError {
+    fn description(&self) -> &str {
+        "invalid LSN"
+    }
+}
@@ -0,0 +1,678 @@
+//! PostGIS geometries.
+//!
+//! Values of PostGIS's `geometry` and `geography` types are transferred in the extended
+//! well-known binary (EWKB) format, which extends the OGC's well-known binary format with a
+//! spatial reference system identifier (SRID) and Z and M coordinates.
+//!
+//! The geometry types of this module can be used directly, in which case the SRID is ignored
+//! when reading values and omitted when writing them, or wrapped in an `Ewkb` to preserve it. If
+//! the `with-geo-0.10` feature is enabled, the two dimensional `geo` types can be used as well.
+use byteorder::{BigEndian, ByteOrder, LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::error::Error;
+
+use types::{FromSql, IsNull, ToSql, Type};
+
+const POINT: u32 = 1;
+const LINE_STRING: u32 = 2;
+const POLYGON: u32 = 3;
+const MULTI_POINT: u32 = 4;
+const MULTI_LINE_STRING: u32 = 5;
+const MULTI_POLYGON: u32 = 6;
+const GEOMETRY_COLLECTION: u32 = 7;
+
+const Z_FLAG: u32 = 0x8000_0000;
+const M_FLAG: u32 = 0x4000_0000;
+const SRID_FLAG: u32 = 0x2000_0000;
+const TYPE_MASK: u32 = 0x0fff_ffff;
+
+// PostGIS types don't have fixed OIDs, so they're matched by name like hstore
+pub(crate) fn is_postgis(ty: &Type) -> bool {
+    match ty.name() {
+        "geometry" | "geography" => true,
+        _ => false,
+    }
+}
+
+/// A point.
+///
+/// Empty points are represented by NaN coordinates.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Point {
+    /// The x coordinate.
+    pub x: f64,
+    /// The y coordinate.
+    pub y: f64,
+    /// The z coordinate, if the point has one.
+    pub z: Option<f64>,
+    /// The measure of the point, if it has one.
+    pub m: Option<f64>,
+}
+
+impl Point {
+    /// Creates a new two dimensional `Point`.
+    pub fn new(x: f64, y: f64) -> Point {
+        Point {
+            x: x,
+            y: y,
+            z: None,
+            m: None,
+        }
+    }
+
+    /// Returns the point with its z coordinate set.
+    pub fn with_z(self, z: f64) -> Point {
+        Point { z: Some(z), ..self }
+    }
+
+    /// Returns the point with its measure set.
+    pub fn with_m(self, m: f64) -> Point {
+        Point { m: Some(m), ..self }
+    }
+}
+
+macro_rules! collection {
+    ($(#[$attr:meta])* $t:ident, $member:ty, $getter:ident, $into:ident) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, PartialEq, Default)]
+        pub struct $t(Vec<$member>);
+
+        impl $t {
+            /// Creates a new value from its members.
+            pub fn new(members: Vec<$member>) -> $t {
+                $t(members)
+            }
+
+            /// Returns the members of the value.
+            pub fn $getter(&self) -> &[$member] {
+                &self.0
+            }
+
+            /// Consumes the value, returning its members.
+            pub fn $into(self) -> Vec<$member> {
+                self.0
+            }
+        }
+    }
+}
+
+collection!(
+    /// A sequence of points connected by straight lines.
+    LineString,
+    Point,
+    points,
+    into_points
+);
+collection!(
+    /// A polygon, made of an exterior ring followed by any number of interior rings.
+    ///
+    /// Each ring is a closed `LineString`, with its first point repeated at the end.
+    Polygon,
+    LineString,
+    rings,
+    into_rings
+);
+collection!(
+    /// A collection of points.
+    MultiPoint,
+    Point,
+    points,
+    into_points
+);
+collection!(
+    /// A collection of line strings.
+    MultiLineString,
+    LineString,
+    line_strings,
+    into_line_strings
+);
+collection!(
+    /// A collection of polygons.
+    MultiPolygon,
+    Polygon,
+    polygons,
+    into_polygons
+);
+collection!(
+    /// A collection of geometries of any type.
+    GeometryCollection,
+    Geometry,
+    geometries,
+    into_geometries
+);
+
+/// A geometry of any type.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Geometry {
+    /// A point.
+    Point(Point),
+    /// A line string.
+    LineString(LineString),
+    /// A polygon.
+    Polygon(Polygon),
+    /// A collection of points.
+    MultiPoint(MultiPoint),
+    /// A collection of line strings.
+    MultiLineString(MultiLineString),
+    /// A collection of polygons.
+    MultiPolygon(MultiPolygon),
+    /// A collection of geometries.
+    GeometryCollection(GeometryCollection),
+}
+
+/// A geometry along with its spatial reference system identifier (SRID).
+#[derive(Debug, Clone, PartialEq)]
+pub struct Ewkb<G> {
+    srid: Option<i32>,
+    geometry: G,
+}
+
+impl<G> Ewkb<G> {
+    /// Creates a new `Ewkb`.
+    pub fn new(srid: Option<i32>, geometry: G) -> Ewkb<G> {
+        Ewkb {
+            srid: srid,
+            geometry: geometry,
+        }
+    }
+
+    /// Returns the SRID of the geometry, if it has one.
+    pub fn srid(&self) -> Option<i32> {
+        self.srid
+    }
+
+    /// Returns the geometry.
+    pub fn geometry(&self) -> &G {
+        &self.geometry
+    }
+
+    /// Consumes the `Ewkb`, returning its geometry.
+    pub fn into_geometry(self) -> G {
+        self.geometry
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct Dimensions {
+    z: bool,
+    m: bool,
+}
+
+impl Dimensions {
+    fn of(point: &Point) -> Dimensions {
+        Dimensions {
+            z: point.z.is_some(),
+            m: point.m.is_some(),
+        }
+    }
+
+    fn from_type(type_: u32) -> Dimensions {
+        Dimensions {
+            z: type_ & Z_FLAG != 0,
+            m: type_ & M_FLAG != 0,
+        }
+    }
+
+    fn flags(self) -> u32 {
+        let mut flags = 0;
+        if self.z {
+            flags |= Z_FLAG;
+        }
+        if self.m {
+            flags |= M_FLAG;
+        }
+        flags
+    }
+}
+
+trait Encode {
+    fn type_code(&self) -> u32;
+
+    // every point of a geometry must have the same dimensions as the first
+    fn first_point(&self) -> Option<&Point>;
+
+    fn write_body(
+        &self,
+        dims: Dimensions,
+        buf: &mut Vec<u8>,
+    ) -> Result<(), Box<Error + Sync + Send>>;
+}
+
+trait Decode: Sized {
+    fn from_geometry(geometry: Geometry) -> Option<Self>;
+}
+
+impl Encode for Point {
+    fn type_code(&self) -> u32 {
+        POINT
+    }
+
+    fn first_point(&self) -> Option<&Point> {
+        Some(self)
+    }
+
+    fn write_body(
+        &self,
+        dims: Dimensions,
+        buf: &mut Vec<u8>,
+    ) -> Result<(), Box<Error + Sync + Send>> {
+        if Dimensions::of(self) != dims {
+            return Err("geometry has points with different dimensions".into());
+        }
+        buf.write_f64::<LittleEndian>(self.x).unwrap();
+        buf.write_f64::<LittleEndian>(self.y).unwrap();
+        if let Some(z) = self.z {
+            buf.write_f64::<LittleEndian>(z).unwrap();
+        }
+        if let Some(m) = self.m {
+            buf.write_f64::<LittleEndian>(m).unwrap();
+        }
+        Ok(())
+    }
+}
+
+impl Encode for LineString {
+    fn type_code(&self) -> u32 {
+        LINE_STRING
+    }
+
+    fn first_point(&self) -> Option<&Point> {
+        self.0.first()
+    }
+
+    fn write_body(
+        &self,
+        dims: Dimensions,
+        buf: &mut Vec<u8>,
+    ) -> Result<(), Box<Error + Sync + Send>> {
+        write_count(self.0.len(), buf)?;
+        for point in &self.0 {
+            point.write_body(dims, buf)?;
+        }
+        Ok(())
+    }
+}
+
+impl Encode for Polygon {
+    fn type_code(&self) -> u32 {
+        POLYGON
+    }
+
+    fn first_point(&self) -> Option<&Point> {
+        self.0.iter().filter_map(Encode::first_point).next()
+    }
+
+    fn write_body(
+        &self,
+        dims: Dimensions,
+        buf: &mut Vec<u8>,
+    ) -> Result<(), Box<Error + Sync + Send>> {
+        write_count(self.0.len(), buf)?;
+        for ring in &self.0 {
+            ring.write_body(dims, buf)?;
+        }
+        Ok(())
+    }
+}
+
+macro_rules! encode_collection {
+    ($t:ident, $code:ident) => {
+        impl Encode for $t {
+            fn type_code(&self) -> u32 {
+                $code
+            }
+
+            fn first_point(&self) -> Option<&Point> {
+                self.0.iter().filter_map(Encode::first_point).next()
+            }
+
+            fn write_body(
+                &self,
+                dims: Dimensions,
+                buf: &mut Vec<u8>,
+            ) -> Result<(), Box<Error + Sync + Send>> {
+                write_count(self.0.len(), buf)?;
+                for member in &self.0 {
+                    write_geometry(member, None, dims, buf)?;
+                }
+                Ok(())
+            }
+        }
+    };
+}
+
+encode_collection!(MultiPoint, MULTI_POINT);
+encode_collection!(MultiLineString, MULTI_LINE_STRING);
+encode_collection!(MultiPolygon, MULTI_POLYGON);
+encode_collection!(GeometryCollection, GEOMETRY_COLLECTION);
+
+impl Geometry {
+    fn as_encode(&self) -> &Encode {
+        match *self {
+            Geometry::Point(ref g) => g,
+            Geometry::LineString(ref g) => g,
+            Geometry::Polygon(ref g) => g,
+            Geometry::MultiPoint(ref g) => g,
+            Geometry::MultiLineString(ref g) => g,
+            Geometry::MultiPolygon(ref g) => g,
+            Geometry::GeometryCollection(ref g) => g,
+        }
+    }
+}
+
+impl Encode for Geometry {
+    fn type_code(&self) -> u32 {
+        self.as_encode().type_code()
+    }
+
+    fn first_point(&self) -> Option<&Point> {
+        self.as_encode().first_point()
+    }
+
+    fn write_body(
+        &self,
+        dims: Dimensions,
+        buf: &mut Vec<u8>,
+    ) -> Result<(), Box<Error + Sync + Send>> {
+        self.as_encode().write_body(dims, buf)
+    }
+}
+
+macro_rules! decode {
+    ($t:ident) => {
+        impl Decode for $t {
+            fn from_geometry(geometry: Geometry) -> Option<$t> {
+                match geometry {
+                    Geometry::$t(g) => Some(g),
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+decode!(Point);
+decode!(LineString);
+decode!(Polygon);
+decode!(MultiPoint);
+decode!(MultiLineString);
+decode!(MultiPolygon);
+decode!(GeometryCollection);
+
+impl Decode for Geometry {
+    fn from_geometry(geometry: Geometry) -> Option<Geometry> {
+        Some(geometry)
+    }
+}
+
+fn write_count(count: usize, buf: &mut Vec<u8>) -> Result<(), Box<Error + Sync + Send>> {
+    if count > u32::max_value() as usize {
+        return Err("value too large to transmit".into());
+    }
+    buf.write_u32::<LittleEndian>(count as u32).unwrap();
+    Ok(())
+}
+
+fn write_ewkb<G>(
+    geometry: &G,
+    srid: Option<i32>,
+    buf: &mut Vec<u8>,
+) -> Result<(), Box<Error + Sync + Send>>
+where
+    G: Encode,
+{
+    let dims = match geometry.first_point() {
+        Some(point) => Dimensions::of(point),
+        None => Dimensions { z: false, m: false },
+    };
+    write_geometry(geometry, srid, dims, buf)
+}
+
+fn write_geometry<G>(
+    geometry: &G,
+    srid: Option<i32>,
+    dims: Dimensions,
+    buf: &mut Vec<u8>,
+) -> Result<(), Box<Error + Sync + Send>>
+where
+    G: Encode + ?Sized,
+{
+    // values are always written in little endian byte order
+    buf.push(1);
+    let mut type_ = geometry.type_code() | dims.flags();
+    if srid.is_some() {
+        type_ |= SRID_FLAG;
+    }
+    buf.write_u32::<LittleEndian>(type_).unwrap();
+    if let Some(srid) = srid {
+        buf.write_i32::<LittleEndian>(srid).unwrap();
+    }
+    geometry.write_body(dims, buf)
+}
+
+fn read_ewkb(mut buf: &[u8]) -> Result<(Option<i32>, Geometry), Box<Error + Sync + Send>> {
+    let geometry = read_geometry(&mut buf)?;
+    if !buf.is_empty() {
+        return Err("invalid buffer size".into());
+    }
+    Ok(geometry)
+}
+
+fn read_geometry(buf: &mut &[u8]) -> Result<(Option<i32>, Geometry), Box<Error + Sync + Send>> {
+    match buf.read_u8()? {
+        0 => read_geometry_body::<BigEndian>(buf),
+        1 => read_geometry_body::<LittleEndian>(buf),
+        _ => Err("invalid byte order".into()),
+    }
+}
+
+fn read_geometry_body<B>(
+    buf: &mut &[u8],
+) -> Result<(Option<i32>, Geometry), Box<Error + Sync + Send>>
+where
+    B: ByteOrder,
+{
+    let type_ = buf.read_u32::<B>()?;
+    let dims = Dimensions::from_type(type_);
+    let srid = if type_ & SRID_FLAG != 0 {
+        Some(buf.read_i32::<B>()?)
+    } else {
+        None
+    };
+
+    let geometry = match type_ & TYPE_MASK {
+        POINT => Geometry::Point(read_point::<B>(buf, dims)?),
+        LINE_STRING => Geometry::LineString(read_line_string::<B>(buf, dims)?),
+        POLYGON => {
+            let count = buf.read_u32::<B>()?;
+            let mut rings = vec![];
+            for _ in 0..count {
+                rings.push(read_line_string::<B>(buf, dims)?);
+            }
+            Geometry::Polygon(Polygon(rings))
+        }
+        MULTI_POINT => Geometry::MultiPoint(MultiPoint(read_members::<B, _>(buf)?)),
+        MULTI_LINE_STRING => Geometry::MultiLineString(MultiLineString(read_members::<B, _>(buf)?)),
+        MULTI_POLYGON => Geometry::MultiPolygon(MultiPolygon(read_members::<B, _>(buf)?)),
+        GEOMETRY_COLLECTION => {
+            Geometry::GeometryCollection(GeometryCollection(read_members::<B, _>(buf)?))
+        }
+        _ => return Err("unsupported geometry type".into()),
+    };
+
+    Ok((srid, geometry))
+}
+
+fn read_point<B>(buf: &mut &[u8], dims: Dimensions) -> Result<Point, Box<Error + Sync + Send>>
+where
+    B: ByteOrder,
+{
+    let x = buf.read_f64::<B>()?;
+    let y = buf.read_f64::<B>()?;
+    let z = if dims.z {
+        Some(buf.read_f64::<B>()?)
+    } else {
+        None
+    };
+    let m = if dims.m {
+        Some(buf.read_f64::<B>()?)
+    } else {
+        None
+    };
+    Ok(Point {
+        x: x,
+        y: y,
+        z: z,
+        m: m,
+    })
+}
+
+fn read_line_string<B>(
+    buf: &mut &[u8],
+    dims: Dimensions,
+) -> Result<LineString, Box<Error + Sync + Send>>
+where
+    B: ByteOrder,
+{
+    let count = buf.read_u32::<B>()?;
+    let mut points = vec![];
+    for _ in 0..count {
+        points.push(read_point::<B>(buf, dims)?);
+    }
+    Ok(LineString(points))
+}
+
+// the members of multi-geometries and collections are complete geometries with their own byte
+// order and type
+fn read_members<B, T>(buf: &mut &[u8]) -> Result<Vec<T>, Box<Error + Sync + Send>>
+where
+    B: ByteOrder,
+    T: Decode,
+{
+    let count = buf.read_u32::<B>()?;
+    let mut members = vec![];
+    for _ in 0..count {
+        let (_, geometry) = read_geometry(buf)?;
+        match T::from_geometry(geometry) {
+            Some(member) => members.push(member),
+            None => return Err("unexpected geometry type in collection".into()),
+        }
+    }
+    Ok(members)
+}
+
+macro_rules! sql_impls {
+    ($($t:ident),+) => {
+        $(
+            impl<'a> FromSql<'a> for $t {
+                fn from_sql(ty: &Type, raw: &[u8]) -> Result<$t, Box<Error + Sync + Send>> {
+                    Ewkb::<$t>::from_sql(ty, raw).map(Ewkb::into_geometry)
+                }
+
+                fn accepts(ty: &Type) -> bool {
+                    is_postgis(ty)
+                }
+            }
+
+            impl ToSql for $t {
+                fn to_sql(&self,
+                          _: &Type,
+                          out: &mut Vec<u8>)
+                          -> Result<IsNull, Box<Error + Sync + Send>> {
+                    write_ewkb(self, None, out)?;
+                    Ok(IsNull::No)
+                }
+
+                fn accepts(ty: &Type) -> bool {
+                    is_postgis(ty)
+                }
+
+                to_sql_checked!();
+            }
+
+            impl<'a> FromSql<'a> for Ewkb<$t> {
+                fn from_sql(_: &Type, raw: &[u8]) -> Result<Ewkb<$t>, Box<Error + Sync + Send>> {
+                    let (srid, geometry) = read_ewkb(raw)?;
+                    match $t::from_geometry(geometry) {
+                        Some(geometry) => Ok(Ewkb::new(srid, geometry)),
+                        None => Err(concat!("expected a ", stringify!($t), " geometry").into()),
+                    }
+                }
+
+                fn accepts(ty: &Type) -> bool {
+                    is_postgis(ty)
+                }
+            }
+
+            impl ToSql for Ewkb<$t> {
+                fn to_sql(&self,
+                          _: &Type,
+                          out: &mut Vec<u8>)
+                          -> Result<IsNull, Box<Error + Sync + Send>> {
+                    write_ewkb(&self.geometry, self.srid, out)?;
+                    Ok(IsNull::No)
+                }
+
+                fn accepts(ty: &Type) -> bool {
+                    is_postgis(ty)
+                }
+
+                to_sql_checked!();
+            }
+        )+
+    }
+}
+
+sql_impls!(
+    Point,
+    LineString,
+    Polygon,
+    MultiPoint,
+    MultiLineString,
+    MultiPolygon,
+    GeometryCollection,
+    Geometry
+);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let geometry = Geometry::GeometryCollection(GeometryCollection::new(vec![
+            Geometry::Point(Point::new(1., 2.).with_z(3.)),
+            Geometry::MultiPolygon(MultiPolygon::new(vec![Polygon::new(vec![
+                LineString::new(vec![
+                    Point::new(0., 0.).with_z(1.),
+                    Point::new(1., 0.).with_z(1.),
+                    Point::new(0., 1.).with_z(1.),
+                    Point::new(0., 0.).with_z(1.),
+                ]),
+            ])])),
+        ]));
+        let value = Ewkb::new(Some(4326), geometry);
+
+        let mut buf = vec![];
+        value.to_sql(&Type::BYTEA, &mut buf).unwrap();
+        assert_eq!(
+            Ewkb::<Geometry>::from_sql(&Type::BYTEA, &buf).unwrap(),
+            value
+        );
+        assert!(Ewkb::<Point>::from_sql(&Type::BYTEA, &buf).is_err());
+
+        let mixed = LineString::new(vec![Point::new(0., 0.), Point::new(1., 1.).with_m(2.)]);
+        assert!(mixed.to_sql(&Type::BYTEA, &mut vec![]).is_err());
+    }
+
+    #[test]
+    fn big_endian_point() {
+        // SRID=4326;POINT(1 2) in XDR
+        let buf = [
+            0, 0x20, 0, 0, 1, 0, 0, 0x10, 0xe6, 0x3f, 0xf0, 0, 0, 0, 0, 0, 0, 0x40, 0, 0, 0, 0, 0,
+            0, 0,
+        ];
+        let value = Ewkb::<Point>::from_sql(&Type::BYTEA, &buf).unwrap();
+        assert_eq!(value.srid(), Some(4326));
+        assert_eq!(*value.geometry(), Point::new(1., 2.));
+    }
+}
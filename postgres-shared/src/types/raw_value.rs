@@ -0,0 +1,137 @@
+use std::error::Error;
+
+use stmt::Format;
+use types::{FromSql, IsNull, ToSql, Type, WrongType};
+
+/// A value of any Postgres type, kept in its wire format without being decoded.
+///
+/// `RawValue` can be retrieved from a column of any type and passed back as a parameter of the
+/// same type, making it possible to forward values between connections. `NULL` values can be
+/// handled by wrapping it in an `Option`.
+///
+/// The binary format of arrays, and of composite types with fields of non-builtin types, embeds
+/// the OIDs of the types involved. The OIDs of non-builtin types differ between databases, so
+/// such values can only be forwarded to a connection to the same database.
+///
+/// Values retrieved from columns in the text format keep the column's type, but can only be
+/// passed as parameters of string types, since parameters are always sent in the binary format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RawValue {
+    type_: Type,
+    format: Format,
+    raw: Vec<u8>,
+}
+
+impl RawValue {
+    /// Creates a new `RawValue` from the binary format of a value of the specified type.
+    ///
+    /// The value is sent to the server as-is; it is the caller's responsibility to ensure it is
+    /// in the proper format.
+    pub fn new(type_: Type, raw: Vec<u8>) -> RawValue {
+        RawValue {
+            type_: type_,
+            format: Format::Binary,
+            raw: raw,
+        }
+    }
+
+    /// Returns the type of the value.
+    pub fn type_(&self) -> &Type {
+        &self.type_
+    }
+
+    /// Returns the format of the value's representation.
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Returns the value's representation.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.raw
+    }
+
+    /// Consumes the `RawValue`, returning its representation.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.raw
+    }
+}
+
+impl<'a> FromSql<'a> for RawValue {
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<RawValue, Box<Error + Sync + Send>> {
+        Ok(RawValue::new(ty.clone(), raw.to_vec()))
+    }
+
+    fn accepts(_: &Type) -> bool {
+        true
+    }
+
+    fn __accepts_text(_: &Type) -> bool {
+        true
+    }
+
+    fn __from_sql_text(ty: &Type, raw: &'a [u8]) -> Result<RawValue, Box<Error + Sync + Send>> {
+        Ok(RawValue {
+            type_: ty.clone(),
+            format: Format::Text,
+            raw: raw.to_vec(),
+        })
+    }
+}
+
+impl ToSql for RawValue {
+    fn to_sql(&self, _: &Type, out: &mut Vec<u8>) -> Result<IsNull, Box<Error + Sync + Send>> {
+        out.extend_from_slice(&self.raw);
+        Ok(IsNull::No)
+    }
+
+    fn accepts(_: &Type) -> bool {
+        true
+    }
+
+    // Any type is accepted, but only values of the parameter's type can be passed through. Types
+    // are matched by name rather than OID since the OIDs of non-builtin types differ between
+    // databases.
+    fn to_sql_checked(
+        &self,
+        ty: &Type,
+        out: &mut Vec<u8>,
+    ) -> Result<IsNull, Box<Error + Sync + Send>> {
+        if ty.name() != self.type_.name() || ty.schema() != self.type_.schema() {
+            return Err(Box::new(WrongType::new(ty.clone())));
+        }
+        // the text and binary formats only coincide for string types
+        if self.format == Format::Text && !<String as ToSql>::accepts(ty) {
+            return Err("a value in the text format can only be passed as a string".into());
+        }
+        self.to_sql(ty, out)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        let value = RawValue::from_sql(&Type::INT4, &[0, 0, 0, 1]).unwrap();
+        assert_eq!(value.type_(), &Type::INT4);
+
+        let mut buf = vec![];
+        value.to_sql_checked(&Type::INT4, &mut buf).unwrap();
+        assert_eq!(buf, [0, 0, 0, 1]);
+        assert!(value.to_sql_checked(&Type::INT8, &mut buf).is_err());
+    }
+
+    #[test]
+    fn text_format() {
+        let value = RawValue::__from_sql_text(&Type::INT4, b"1").unwrap();
+        assert_eq!(value.type_(), &Type::INT4);
+        assert_eq!(value.format(), Format::Text);
+        assert!(value.to_sql_checked(&Type::INT4, &mut vec![]).is_err());
+
+        let value = RawValue::__from_sql_text(&Type::VARCHAR, b"hi").unwrap();
+        let mut buf = vec![];
+        value.to_sql_checked(&Type::VARCHAR, &mut buf).unwrap();
+        assert_eq!(buf, b"hi");
+    }
+}
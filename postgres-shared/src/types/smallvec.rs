@@ -0,0 +1,36 @@
+extern crate smallvec;
+
+use self::smallvec::{Array, SmallVec};
+use std::error::Error;
+
+use types::{from_sql_array, to_sql_array, FromSql, IsNull, ToSql, Type};
+
+impl<'a, A> FromSql<'a> for SmallVec<A>
+where
+    A: Array,
+    A::Item: FromSql<'a>,
+{
+    fn from_sql(ty: &Type, raw: &'a [u8]) -> Result<SmallVec<A>, Box<Error + Sync + Send>> {
+        from_sql_array(ty, raw)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <Vec<A::Item> as FromSql>::accepts(ty)
+    }
+}
+
+impl<A> ToSql for SmallVec<A>
+where
+    A: Array,
+    A::Item: ToSql,
+{
+    fn to_sql(&self, ty: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<Error + Sync + Send>> {
+        to_sql_array(ty, self.len(), self.iter(), w)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        <&[A::Item] as ToSql>::accepts(ty)
+    }
+
+    to_sql_checked!();
+}
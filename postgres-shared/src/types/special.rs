@@ -98,3 +98,66 @@ impl<T: ToSql> ToSql for Timestamp<T> {
 
     to_sql_checked!();
 }
+
+/// A wrapper which clamps date and time values to the range of `T` rather than failing to convert
+/// them.
+///
+/// Postgres supports a wider range of dates and timestamps than most Rust types do, as well as
+/// `infinity` and `-infinity`. Converting a value outside of the range of a type normally returns
+/// an error. `Saturating<T>` instead converts values past either end of the range, including the
+/// infinities, to the latest or earliest value `T` can represent.
+///
+/// `Saturating<T>` is written to Postgres exactly like `T`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Saturating<T>(pub T);
+
+impl<T> Saturating<T> {
+    /// Consumes the `Saturating`, returning the wrapped value.
+    pub fn into_inner(self) -> T {
+        self.0
+    }
+}
+
+impl<T: ToSql> ToSql for Saturating<T> {
+    fn to_sql(&self, ty: &Type, out: &mut Vec<u8>) -> Result<IsNull, Box<Error + Sync + Send>> {
+        self.0.to_sql(ty, out)
+    }
+
+    fn accepts(ty: &Type) -> bool {
+        T::accepts(ty)
+    }
+
+    to_sql_checked!();
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use std::i64;
+
+    // xorshift64*, which is plenty for spreading test inputs around
+    struct Rng(u64);
+
+    impl Rng {
+        fn next(&mut self) -> u64 {
+            self.0 ^= self.0 >> 12;
+            self.0 ^= self.0 << 25;
+            self.0 ^= self.0 >> 27;
+            self.0.wrapping_mul(0x2545_f491_4f6c_dd1d)
+        }
+    }
+
+    /// Returns the edges of the `i64` range along with pseudo-random values spread over all of it.
+    ///
+    /// Uniformly distributed values would almost all be far outside the range of any date or time
+    /// type, so the random values are shifted right by a random amount to cover every magnitude.
+    pub fn sample_i64s() -> Vec<i64> {
+        let mut samples = vec![i64::MIN, i64::MIN + 1, -1, 0, 1, i64::MAX - 1, i64::MAX];
+        let mut rng = Rng(0x9e37_79b9_7f4a_7c15);
+        for _ in 0..100_000 {
+            let value = rng.next() as i64;
+            let shift = rng.next() % 64;
+            samples.push(value >> shift);
+        }
+        samples
+    }
+}
@@ -0,0 +1,374 @@
+use fallible_iterator::FallibleIterator;
+use postgres_protocol::types::{self, TsQueryItem};
+use std::error::Error;
+
+use types::{FromSql, IsNull, ToSql, Type};
+
+// the largest position which can be stored in a `TSVECTOR`
+const MAX_POSITION: u16 = 0x3fff;
+
+/// The weight of a lexeme, used to mark lexemes coming from different parts of a document.
+///
+/// `D` is the default weight, and is omitted from Postgres's text representation.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Weight {
+    /// The `A` weight.
+    A,
+    /// The `B` weight.
+    B,
+    /// The `C` weight.
+    C,
+    /// The `D` weight.
+    D,
+}
+
+impl Weight {
+    fn from_position_bits(bits: u16) -> Weight {
+        match bits {
+            3 => Weight::A,
+            2 => Weight::B,
+            1 => Weight::C,
+            _ => Weight::D,
+        }
+    }
+
+    fn position_bits(self) -> u16 {
+        match self {
+            Weight::A => 3,
+            Weight::B => 2,
+            Weight::C => 1,
+            Weight::D => 0,
+        }
+    }
+
+    fn mask(self) -> u8 {
+        1 << self.position_bits()
+    }
+}
+
+/// A position of a lexeme within a document, along with its weight.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Position {
+    position: u16,
+    weight: Weight,
+}
+
+impl Position {
+    /// Creates a new `Position`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `position` is not between 1 and 16383, inclusive.
+    pub fn new(position: u16, weight: Weight) -> Position {
+        assert!(
+            position > 0 && position <= MAX_POSITION,
+            "position out of range: {}",
+            position
+        );
+        Position {
+            position: position,
+            weight: weight,
+        }
+    }
+
+    /// Returns the position of the lexeme, starting at 1.
+    pub fn position(&self) -> u16 {
+        self.position
+    }
+
+    /// Returns the weight of the lexeme at this position.
+    pub fn weight(&self) -> Weight {
+        self.weight
+    }
+
+    fn from_raw(raw: u16) -> Position {
+        Position {
+            position: raw & MAX_POSITION,
+            weight: Weight::from_position_bits(raw >> 14),
+        }
+    }
+
+    fn to_raw(&self) -> u16 {
+        (self.weight.position_bits() << 14) | self.position
+    }
+}
+
+/// A lexeme of a `TsVector`, along with the positions it appears at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Lexeme {
+    value: String,
+    positions: Vec<Position>,
+}
+
+impl Lexeme {
+    /// Creates a new `Lexeme`.
+    ///
+    /// The positions are sorted, and if the same position appears more than once only the first
+    /// is kept. Lexemes without positions are allowed.
+    pub fn new(value: String, mut positions: Vec<Position>) -> Lexeme {
+        positions.sort_by_key(|p| p.position);
+        positions.dedup_by_key(|p| p.position);
+        Lexeme {
+            value: value,
+            positions: positions,
+        }
+    }
+
+    /// Returns the lexeme itself.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Returns the positions of the lexeme, in increasing order.
+    pub fn positions(&self) -> &[Position] {
+        &self.positions
+    }
+}
+
+/// A document processed for text search, corresponding to the `TSVECTOR` type.
+///
+/// Postgres stores the lexemes of a `TSVECTOR` sorted and without duplicates, so values read from
+/// the database will be normalized even if the values written to it were not.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct TsVector(Vec<Lexeme>);
+
+impl TsVector {
+    /// Creates a new `TsVector` from its lexemes.
+    pub fn new(lexemes: Vec<Lexeme>) -> TsVector {
+        TsVector(lexemes)
+    }
+
+    /// Returns the lexemes of the vector.
+    pub fn lexemes(&self) -> &[Lexeme] {
+        &self.0
+    }
+
+    /// Consumes the `TsVector`, returning its lexemes.
+    pub fn into_lexemes(self) -> Vec<Lexeme> {
+        self.0
+    }
+}
+
+impl<'a> FromSql<'a> for TsVector {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<TsVector, Box<Error + Sync + Send>> {
+        let lexemes = types::tsvector_from_sql(raw)?
+            .map(|(value, positions)| Lexeme {
+                value: value.to_string(),
+                positions: positions.map(Position::from_raw).collect(),
+            }).collect()?;
+        Ok(TsVector(lexemes))
+    }
+
+    accepts!(TS_VECTOR);
+}
+
+impl ToSql for TsVector {
+    fn to_sql(&self, _: &Type, out: &mut Vec<u8>) -> Result<IsNull, Box<Error + Sync + Send>> {
+        types::tsvector_to_sql(
+            self.0
+                .iter()
+                .map(|l| (&*l.value, l.positions.iter().map(Position::to_raw))),
+            out,
+        )?;
+        Ok(IsNull::No)
+    }
+
+    accepts!(TS_VECTOR);
+    to_sql_checked!();
+}
+
+/// A text search query, corresponding to the `TSQUERY` type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TsQuery {
+    /// A query with no lexemes, which matches nothing.
+    ///
+    /// Postgres produces empty queries from search strings containing only stop words. An empty
+    /// query cannot be the operand of an operator.
+    Empty,
+    /// A lexeme to match.
+    Lexeme {
+        /// The lexeme.
+        value: String,
+        /// The weights the lexeme matches. An empty list matches any weight.
+        weights: Vec<Weight>,
+        /// If set, the lexeme matches any lexeme it is a prefix of.
+        prefix: bool,
+    },
+    /// Matches if the operand does not match (`!`).
+    Not(Box<TsQuery>),
+    /// Matches if both operands match (`&`).
+    And(Box<TsQuery>, Box<TsQuery>),
+    /// Matches if either operand matches (`|`).
+    Or(Box<TsQuery>, Box<TsQuery>),
+    /// Matches if the second operand follows the first at the specified distance (`<N>`).
+    ///
+    /// The `<->` operator has a distance of 1.
+    Phrase(Box<TsQuery>, Box<TsQuery>, u16),
+}
+
+impl TsQuery {
+    // binary operators are followed by their right operand and then their left operand
+    fn items<'a>(
+        &'a self,
+        items: &mut Vec<TsQueryItem<'a>>,
+    ) -> Result<(), Box<Error + Sync + Send>> {
+        let (item, left, right) = match *self {
+            TsQuery::Empty => return Err("an empty tsquery cannot be an operand".into()),
+            TsQuery::Lexeme {
+                ref value,
+                ref weights,
+                prefix,
+            } => {
+                items.push(TsQueryItem::Operand {
+                    weights: weights.iter().fold(0, |mask, w| mask | w.mask()),
+                    prefix: prefix,
+                    value: value,
+                });
+                return Ok(());
+            }
+            TsQuery::Not(ref operand) => {
+                items.push(TsQueryItem::Not);
+                return operand.items(items);
+            }
+            TsQuery::And(ref left, ref right) => (TsQueryItem::And, left, right),
+            TsQuery::Or(ref left, ref right) => (TsQueryItem::Or, left, right),
+            TsQuery::Phrase(ref left, ref right, distance) => {
+                (TsQueryItem::Phrase(distance), left, right)
+            }
+        };
+        items.push(item);
+        right.items(items)?;
+        left.items(items)
+    }
+
+    fn from_items<'a, I>(items: &mut I) -> Result<TsQuery, Box<Error + Sync + Send>>
+    where
+        I: Iterator<Item = TsQueryItem<'a>>,
+    {
+        let query = match items.next() {
+            Some(TsQueryItem::Operand {
+                weights,
+                prefix,
+                value,
+            }) => TsQuery::Lexeme {
+                value: value.to_string(),
+                weights: [Weight::A, Weight::B, Weight::C, Weight::D]
+                    .iter()
+                    .cloned()
+                    .filter(|w| weights & w.mask() != 0)
+                    .collect(),
+                prefix: prefix,
+            },
+            Some(TsQueryItem::Not) => TsQuery::Not(Box::new(TsQuery::from_items(items)?)),
+            Some(item) => {
+                let right = Box::new(TsQuery::from_items(items)?);
+                let left = Box::new(TsQuery::from_items(items)?);
+                match item {
+                    TsQueryItem::And => TsQuery::And(left, right),
+                    TsQueryItem::Or => TsQuery::Or(left, right),
+                    TsQueryItem::Phrase(distance) => TsQuery::Phrase(left, right, distance),
+                    _ => unreachable!(),
+                }
+            }
+            None => return Err("missing tsquery operand".into()),
+        };
+        Ok(query)
+    }
+}
+
+impl<'a> FromSql<'a> for TsQuery {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<TsQuery, Box<Error + Sync + Send>> {
+        let items = types::tsquery_from_sql(raw)?.collect::<Vec<_>>()?;
+        if items.is_empty() {
+            return Ok(TsQuery::Empty);
+        }
+
+        let mut items = items.into_iter();
+        let query = TsQuery::from_items(&mut items)?;
+        if items.next().is_some() {
+            return Err("invalid tsquery".into());
+        }
+        Ok(query)
+    }
+
+    accepts!(TSQUERY);
+}
+
+impl ToSql for TsQuery {
+    fn to_sql(&self, _: &Type, out: &mut Vec<u8>) -> Result<IsNull, Box<Error + Sync + Send>> {
+        let mut items = vec![];
+        match *self {
+            TsQuery::Empty => {}
+            _ => self.items(&mut items)?,
+        }
+        types::tsquery_to_sql(items, out)?;
+        Ok(IsNull::No)
+    }
+
+    accepts!(TSQUERY);
+    to_sql_checked!();
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn tsquery_round_trip() {
+        // 'fat':AB & !( 'rat':* <2> 'cat' )
+        let query = TsQuery::And(
+            Box::new(TsQuery::Lexeme {
+                value: "fat".to_string(),
+                weights: vec![Weight::A, Weight::B],
+                prefix: false,
+            }),
+            Box::new(TsQuery::Not(Box::new(TsQuery::Phrase(
+                Box::new(TsQuery::Lexeme {
+                    value: "rat".to_string(),
+                    weights: vec![],
+                    prefix: true,
+                }),
+                Box::new(TsQuery::Lexeme {
+                    value: "cat".to_string(),
+                    weights: vec![],
+                    prefix: false,
+                }),
+                2,
+            )))),
+        );
+
+        let mut buf = vec![];
+        query.to_sql(&Type::TSQUERY, &mut buf).unwrap();
+        assert_eq!(TsQuery::from_sql(&Type::TSQUERY, &buf).unwrap(), query);
+
+        let mut buf = vec![];
+        TsQuery::Empty.to_sql(&Type::TSQUERY, &mut buf).unwrap();
+        assert_eq!(
+            TsQuery::from_sql(&Type::TSQUERY, &buf).unwrap(),
+            TsQuery::Empty
+        );
+
+        let nested = TsQuery::Not(Box::new(TsQuery::Empty));
+        assert!(nested.to_sql(&Type::TSQUERY, &mut vec![]).is_err());
+    }
+
+    #[test]
+    fn lexeme_positions() {
+        let lexeme = Lexeme::new(
+            "fat".to_string(),
+            vec![
+                Position::new(5, Weight::D),
+                Position::new(2, Weight::A),
+                Position::new(5, Weight::B),
+            ],
+        );
+        assert_eq!(
+            lexeme.positions(),
+            [Position::new(2, Weight::A), Position::new(5, Weight::D)]
+        );
+        assert_eq!(
+            Position::from_raw(Position::new(2, Weight::A).to_raw()),
+            Position::new(2, Weight::A)
+        );
+    }
+}
@@ -0,0 +1,347 @@
+extern crate time;
+
+use self::time::{Date, Duration, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+use postgres_protocol::types;
+use std::error::Error;
+
+use types::{FromSql, IsNull, Saturating, ToSql, Type};
+
+// the Julian day of 2000-01-01, which Postgres uses as its epoch
+const BASE_JULIAN_DAY: i32 = 2_451_545;
+
+const USECS_PER_DAY: i64 = 86_400_000_000;
+
+fn base() -> PrimitiveDateTime {
+    PrimitiveDateTime::new(
+        Date::from_julian_day(BASE_JULIAN_DAY).unwrap(),
+        Time::MIDNIGHT,
+    )
+}
+
+// the last microsecond of a day
+fn last_time() -> Time {
+    Time::from_hms_micro(23, 59, 59, 999_999).unwrap()
+}
+
+fn timestamp_from_usec(t: i64) -> Option<PrimitiveDateTime> {
+    base().checked_add(Duration::microseconds(t))
+}
+
+fn date_from_days(jd: i32) -> Option<Date> {
+    BASE_JULIAN_DAY
+        .checked_add(jd)
+        .and_then(|jd| Date::from_julian_day(jd).ok())
+}
+
+fn microseconds(duration: Duration) -> Result<i64, Box<Error + Sync + Send>> {
+    let usec = duration.whole_microseconds();
+    if usec > i64::max_value() as i128 || usec < i64::min_value() as i128 {
+        return Err("value too large to transmit".into());
+    }
+    Ok(usec as i64)
+}
+
+fn time_from_usec(usec: i64) -> Option<Time> {
+    // 24:00:00 is a valid Postgres time, but not a valid `Time`
+    if usec < 0 || usec >= USECS_PER_DAY {
+        return None;
+    }
+    Some(Time::MIDNIGHT + Duration::microseconds(usec))
+}
+
+impl<'a> FromSql<'a> for PrimitiveDateTime {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<PrimitiveDateTime, Box<Error + Sync + Send>> {
+        let t = types::timestamp_from_sql(raw)?;
+        match timestamp_from_usec(t) {
+            Some(datetime) => Ok(datetime),
+            None => Err("value out of range for a PrimitiveDateTime".into()),
+        }
+    }
+
+    accepts!(TIMESTAMP);
+}
+
+impl<'a> FromSql<'a> for Saturating<PrimitiveDateTime> {
+    fn from_sql(
+        _: &Type,
+        raw: &[u8],
+    ) -> Result<Saturating<PrimitiveDateTime>, Box<Error + Sync + Send>> {
+        let t = types::timestamp_from_sql(raw)?;
+        let datetime = match timestamp_from_usec(t) {
+            Some(datetime) => datetime,
+            None if t < 0 => PrimitiveDateTime::MIN,
+            None => PrimitiveDateTime::new(Date::MAX, last_time()),
+        };
+        Ok(Saturating(datetime))
+    }
+
+    accepts!(TIMESTAMP);
+}
+
+impl ToSql for PrimitiveDateTime {
+    fn to_sql(&self, _: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<Error + Sync + Send>> {
+        let time = microseconds(*self - base())?;
+        types::timestamp_to_sql(time, w);
+        Ok(IsNull::No)
+    }
+
+    accepts!(TIMESTAMP);
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for OffsetDateTime {
+    fn from_sql(type_: &Type, raw: &[u8]) -> Result<OffsetDateTime, Box<Error + Sync + Send>> {
+        let naive = PrimitiveDateTime::from_sql(type_, raw)?;
+        Ok(naive.assume_utc())
+    }
+
+    accepts!(TIMESTAMPTZ);
+}
+
+impl<'a> FromSql<'a> for Saturating<OffsetDateTime> {
+    fn from_sql(
+        type_: &Type,
+        raw: &[u8],
+    ) -> Result<Saturating<OffsetDateTime>, Box<Error + Sync + Send>> {
+        let naive = Saturating::<PrimitiveDateTime>::from_sql(type_, raw)?;
+        Ok(Saturating(naive.0.assume_utc()))
+    }
+
+    accepts!(TIMESTAMPTZ);
+}
+
+impl ToSql for OffsetDateTime {
+    fn to_sql(&self, _: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<Error + Sync + Send>> {
+        let time = microseconds(*self - base().assume_utc())?;
+        types::timestamp_to_sql(time, w);
+        Ok(IsNull::No)
+    }
+
+    accepts!(TIMESTAMPTZ);
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for Date {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<Date, Box<Error + Sync + Send>> {
+        let jd = types::date_from_sql(raw)?;
+        match date_from_days(jd) {
+            Some(date) => Ok(date),
+            None => Err("value out of range for a Date".into()),
+        }
+    }
+
+    accepts!(DATE);
+}
+
+impl<'a> FromSql<'a> for Saturating<Date> {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<Saturating<Date>, Box<Error + Sync + Send>> {
+        let jd = types::date_from_sql(raw)?;
+        let date = match date_from_days(jd) {
+            Some(date) => date,
+            None if jd < 0 => Date::MIN,
+            None => Date::MAX,
+        };
+        Ok(Saturating(date))
+    }
+
+    accepts!(DATE);
+}
+
+impl ToSql for Date {
+    fn to_sql(&self, _: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<Error + Sync + Send>> {
+        types::date_to_sql(self.to_julian_day() - BASE_JULIAN_DAY, w);
+        Ok(IsNull::No)
+    }
+
+    accepts!(DATE);
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for Time {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<Time, Box<Error + Sync + Send>> {
+        match time_from_usec(types::time_from_sql(raw)?) {
+            Some(time) => Ok(time),
+            None => Err("value out of range for a Time".into()),
+        }
+    }
+
+    accepts!(TIME);
+}
+
+impl<'a> FromSql<'a> for Saturating<Time> {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<Saturating<Time>, Box<Error + Sync + Send>> {
+        let usec = types::time_from_sql(raw)?;
+        let time = match time_from_usec(usec) {
+            Some(time) => time,
+            None if usec < 0 => Time::MIDNIGHT,
+            None => last_time(),
+        };
+        Ok(Saturating(time))
+    }
+
+    accepts!(TIME);
+}
+
+impl ToSql for Time {
+    fn to_sql(&self, _: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<Error + Sync + Send>> {
+        let time = microseconds(*self - Time::MIDNIGHT)?;
+        types::time_to_sql(time, w);
+        Ok(IsNull::No)
+    }
+
+    accepts!(TIME);
+    to_sql_checked!();
+}
+
+// Postgres stores the offset of a TIMETZ in seconds west of UTC
+impl<'a> FromSql<'a> for (Time, UtcOffset) {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<(Time, UtcOffset), Box<Error + Sync + Send>> {
+        let timetz = types::timetz_from_sql(raw)?;
+        let time = match time_from_usec(timetz.time()) {
+            Some(time) => time,
+            None => return Err("value out of range for a Time".into()),
+        };
+        let offset = match timetz.offset().checked_neg() {
+            Some(offset) => UtcOffset::from_whole_seconds(offset)?,
+            None => return Err("value out of range for a UtcOffset".into()),
+        };
+        Ok((time, offset))
+    }
+
+    accepts!(TIMETZ);
+}
+
+impl ToSql for (Time, UtcOffset) {
+    fn to_sql(&self, _: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<Error + Sync + Send>> {
+        let time = microseconds(self.0 - Time::MIDNIGHT)?;
+        types::timetz_to_sql(time, -self.1.whole_seconds(), w);
+        Ok(IsNull::No)
+    }
+
+    accepts!(TIMETZ);
+    to_sql_checked!();
+}
+
+impl<'a> FromSql<'a> for Duration {
+    fn from_sql(_: &Type, raw: &[u8]) -> Result<Duration, Box<Error + Sync + Send>> {
+        let interval = types::interval_from_sql(raw)?;
+        // a month doesn't have a fixed length, so it can't be represented by a Duration
+        if interval.months() != 0 {
+            return Err("an INTERVAL with a months component cannot be a Duration".into());
+        }
+        Ok(
+            Duration::days(interval.days() as i64)
+                + Duration::microseconds(interval.microseconds()),
+        )
+    }
+
+    accepts!(INTERVAL);
+}
+
+impl ToSql for Duration {
+    fn to_sql(&self, _: &Type, w: &mut Vec<u8>) -> Result<IsNull, Box<Error + Sync + Send>> {
+        let usec = microseconds(*self)?;
+        types::interval_to_sql(usec, 0, 0, w);
+        Ok(IsNull::No)
+    }
+
+    accepts!(INTERVAL);
+    to_sql_checked!();
+}
+
+#[cfg(test)]
+mod test {
+    use std::i32;
+
+    use super::*;
+    use types::special::test::sample_i64s;
+
+    fn check_round_trip<'a, T>(ty: &Type, raw: &'a [u8]) -> Option<T>
+    where
+        T: FromSql<'a> + ToSql,
+    {
+        let value = T::from_sql(ty, raw).ok()?;
+        let mut buf = vec![];
+        value.to_sql(ty, &mut buf).unwrap();
+        assert_eq!(buf, raw);
+        Some(value)
+    }
+
+    #[test]
+    fn timestamp_range() {
+        for t in sample_i64s() {
+            let mut raw = vec![];
+            types::timestamp_to_sql(t, &mut raw);
+
+            let naive = check_round_trip::<PrimitiveDateTime>(&Type::TIMESTAMP, &raw);
+            let utc = check_round_trip::<OffsetDateTime>(&Type::TIMESTAMPTZ, &raw);
+            assert_eq!(naive.map(PrimitiveDateTime::assume_utc), utc);
+
+            let saturated =
+                Saturating::<PrimitiveDateTime>::from_sql(&Type::TIMESTAMP, &raw).unwrap();
+            match naive {
+                Some(naive) => assert_eq!(saturated.0, naive),
+                None if t < 0 => assert_eq!(saturated.0, PrimitiveDateTime::MIN),
+                None => assert_eq!(saturated.0, PrimitiveDateTime::new(Date::MAX, last_time())),
+            }
+            Saturating::<OffsetDateTime>::from_sql(&Type::TIMESTAMPTZ, &raw).unwrap();
+        }
+    }
+
+    #[test]
+    fn date_range() {
+        let samples = sample_i64s()
+            .into_iter()
+            .map(|v| (v >> 32) as i32)
+            .chain(vec![i32::MIN, i32::MIN + 1, i32::MAX - 1, i32::MAX]);
+        for jd in samples {
+            let mut raw = vec![];
+            types::date_to_sql(jd, &mut raw);
+
+            let date = check_round_trip::<Date>(&Type::DATE, &raw);
+
+            let saturated = Saturating::<Date>::from_sql(&Type::DATE, &raw).unwrap();
+            match date {
+                Some(date) => assert_eq!(saturated.0, date),
+                None if jd < 0 => assert_eq!(saturated.0, Date::MIN),
+                None => assert_eq!(saturated.0, Date::MAX),
+            }
+        }
+    }
+
+    #[test]
+    fn time_range() {
+        for usec in sample_i64s() {
+            let mut raw = vec![];
+            types::time_to_sql(usec, &mut raw);
+
+            let time = check_round_trip::<Time>(&Type::TIME, &raw);
+            assert_eq!(time.is_some(), usec >= 0 && usec < USECS_PER_DAY);
+
+            let saturated = Saturating::<Time>::from_sql(&Type::TIME, &raw).unwrap();
+            match time {
+                Some(time) => assert_eq!(saturated.0, time),
+                None if usec < 0 => assert_eq!(saturated.0, Time::MIDNIGHT),
+                None => assert_eq!(saturated.0, last_time()),
+            }
+
+            let mut raw = vec![];
+            types::timetz_to_sql(usec, 0, &mut raw);
+            let timetz = check_round_trip::<(Time, UtcOffset)>(&Type::TIMETZ, &raw);
+            assert_eq!(timetz.map(|t| t.0), time);
+        }
+    }
+
+    #[test]
+    fn interval_range() {
+        for usec in sample_i64s() {
+            let mut raw = vec![];
+            types::interval_to_sql(usec, 0, 0, &mut raw);
+            check_round_trip::<Duration>(&Type::INTERVAL, &raw).unwrap();
+
+            let mut raw = vec![];
+            types::interval_to_sql(usec, usec as i32, 0, &mut raw);
+            Duration::from_sql(&Type::INTERVAL, &raw).unwrap();
+        }
+    }
+}
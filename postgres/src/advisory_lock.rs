@@ -0,0 +1,175 @@
+//! Advisory locks.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! # use postgres::{Connection, TlsMode};
+//! use postgres::advisory_lock::Mode;
+//!
+//! # let conn = Connection::connect("", TlsMode::None).unwrap();
+//! let lock = conn.advisory_lock(42i64, Mode::Exclusive).unwrap();
+//! // only one session at a time can get here
+//! lock.unlock().unwrap();
+//! ```
+
+use std::fmt;
+use std::io;
+use std::marker::PhantomData;
+
+#[doc(inline)]
+pub use postgres_shared::advisory_lock::{Key, Mode};
+
+use postgres_shared::advisory_lock::query;
+use {Connection, Result};
+
+/// A session-level advisory lock.
+///
+/// The lock is released when the `AdvisoryLock` is dropped. Errors are ignored, so
+/// `AdvisoryLock::unlock` should be used to detect them.
+pub struct AdvisoryLock<'conn> {
+    conn: &'conn Connection,
+    key: Key,
+    mode: Mode,
+    finished: bool,
+}
+
+impl<'a> fmt::Debug for AdvisoryLock<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("AdvisoryLock")
+            .field("key", &self.key)
+            .field("mode", &self.mode)
+            .finish()
+    }
+}
+
+impl<'conn> Drop for AdvisoryLock<'conn> {
+    fn drop(&mut self) {
+        if !self.finished {
+            let _ = self.finish_inner();
+        }
+    }
+}
+
+impl<'conn> AdvisoryLock<'conn> {
+    pub(crate) fn lock(
+        conn: &'conn Connection,
+        key: Key,
+        mode: Mode,
+    ) -> Result<AdvisoryLock<'conn>> {
+        conn.simple_query(&query("pg_advisory_lock", key, mode))?;
+        Ok(AdvisoryLock::new(conn, key, mode))
+    }
+
+    pub(crate) fn try_lock(
+        conn: &'conn Connection,
+        key: Key,
+        mode: Mode,
+    ) -> Result<Option<AdvisoryLock<'conn>>> {
+        if try_call(conn, "pg_try_advisory_lock", key, mode)? {
+            Ok(Some(AdvisoryLock::new(conn, key, mode)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn new(conn: &'conn Connection, key: Key, mode: Mode) -> AdvisoryLock<'conn> {
+        AdvisoryLock {
+            conn: conn,
+            key: key,
+            mode: mode,
+            finished: false,
+        }
+    }
+
+    fn finish_inner(&mut self) -> Result<()> {
+        if try_call(self.conn, "pg_advisory_unlock", self.key, self.mode)? {
+            Ok(())
+        } else {
+            Err(io::Error::new(io::ErrorKind::Other, "the advisory lock was not held").into())
+        }
+    }
+
+    /// Returns the lock's key.
+    pub fn key(&self) -> Key {
+        self.key
+    }
+
+    /// Returns the lock's mode.
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// Consumes the `AdvisoryLock`, releasing it.
+    ///
+    /// Functionally equivalent to the `Drop` implementation of `AdvisoryLock` except that it
+    /// returns any error to the caller.
+    pub fn unlock(mut self) -> Result<()> {
+        self.finished = true;
+        self.finish_inner()
+    }
+}
+
+/// A transaction-level advisory lock.
+///
+/// The lock cannot be released early; it is held until the transaction that acquired it ends.
+/// The guard borrows that transaction, ensuring it is not used after the lock is released.
+pub struct AdvisoryXactLock<'trans> {
+    key: Key,
+    mode: Mode,
+    _p: PhantomData<&'trans ()>,
+}
+
+impl<'a> fmt::Debug for AdvisoryXactLock<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("AdvisoryXactLock")
+            .field("key", &self.key)
+            .field("mode", &self.mode)
+            .finish()
+    }
+}
+
+impl<'trans> AdvisoryXactLock<'trans> {
+    pub(crate) fn lock(
+        conn: &Connection,
+        key: Key,
+        mode: Mode,
+    ) -> Result<AdvisoryXactLock<'trans>> {
+        conn.simple_query(&query("pg_advisory_xact_lock", key, mode))?;
+        Ok(AdvisoryXactLock::new(key, mode))
+    }
+
+    pub(crate) fn try_lock(
+        conn: &Connection,
+        key: Key,
+        mode: Mode,
+    ) -> Result<Option<AdvisoryXactLock<'trans>>> {
+        if try_call(conn, "pg_try_advisory_xact_lock", key, mode)? {
+            Ok(Some(AdvisoryXactLock::new(key, mode)))
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn new(key: Key, mode: Mode) -> AdvisoryXactLock<'trans> {
+        AdvisoryXactLock {
+            key: key,
+            mode: mode,
+            _p: PhantomData,
+        }
+    }
+
+    /// Returns the lock's key.
+    pub fn key(&self) -> Key {
+        self.key
+    }
+
+    /// Returns the lock's mode.
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+}
+
+fn try_call(conn: &Connection, function: &str, key: Key, mode: Mode) -> Result<bool> {
+    let rows = conn.query(&query(function, key, mode), &[])?;
+    Ok(rows.get(0).get(0))
+}
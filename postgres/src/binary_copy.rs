@@ -0,0 +1,288 @@
+//! Utilities for working with the binary `COPY` format.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! # use postgres::{Connection, TlsMode};
+//! use postgres::binary_copy::{BinaryCopyIn, BinaryCopyOut};
+//! use postgres::types::{ToSql, Type};
+//!
+//! # let conn = Connection::connect("", TlsMode::None).unwrap();
+//! let types = [Type::INT4, Type::TEXT];
+//! let people = vec![(1i32, Some("john".to_string())), (2, None)];
+//!
+//! let rows = people.iter().map(|&(ref id, ref name)| vec![id as &ToSql, name]);
+//! let mut copy_in = BinaryCopyIn::new(rows, &types).unwrap();
+//! let stmt = conn.prepare("COPY people (id, name) FROM STDIN (FORMAT binary)").unwrap();
+//! stmt.copy_in(&[], &mut copy_in).unwrap();
+//!
+//! let mut copy_out = BinaryCopyOut::new(&types, |row| {
+//!     let id: i32 = row.get(0);
+//!     let name: Option<String> = row.get(1);
+//!     println!("{} {:?}", id, name);
+//!     Ok(())
+//! });
+//! let stmt = conn.prepare("COPY people (id, name) TO STDOUT (FORMAT binary)").unwrap();
+//! stmt.copy_out(&[], &mut copy_out).unwrap();
+//! copy_out.finish().unwrap();
+//! ```
+
+use fallible_iterator::FallibleIterator;
+use postgres_shared::binary_copy::{BinaryCopyDecoder, BinaryCopyEncoder};
+use std::cmp;
+use std::fmt;
+use std::io::{self, Read, Write};
+
+#[doc(inline)]
+pub use postgres_shared::binary_copy::BinaryCopyRow;
+
+use stmt::{CopyInfo, Format, ReadWithInfo, WriteWithInfo};
+use types::{ToSql, Type};
+use {Error, Result};
+
+fn check_format(info: &CopyInfo) -> io::Result<()> {
+    if info.format() == Format::Binary {
+        Ok(())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "COPY is not using the binary format",
+        ))
+    }
+}
+
+/// A `ReadWithInfo` adaptor which encodes rows from an iterator in the binary `COPY` format.
+///
+/// It can be passed to `Statement::copy_in`. Rows are encoded as they are read, so the data is
+/// never buffered in its entirety.
+#[derive(Debug)]
+pub struct BinaryCopyIn<I> {
+    rows: I,
+    encoder: BinaryCopyEncoder,
+    buf: Vec<u8>,
+    pos: usize,
+    done: bool,
+}
+
+impl<'a, I, R> BinaryCopyIn<I>
+where
+    I: Iterator<Item = R>,
+    R: AsRef<[&'a ToSql]>,
+{
+    /// Creates a new adaptor over an iterator of rows with the specified column types.
+    ///
+    /// Returns an error if there are more columns than the format can represent.
+    pub fn new(rows: I, types: &[Type]) -> Result<BinaryCopyIn<I>> {
+        Ok(BinaryCopyIn {
+            rows: rows,
+            encoder: BinaryCopyEncoder::new(types)?,
+            buf: vec![],
+            pos: 0,
+            done: false,
+        })
+    }
+}
+
+impl<'a, I, R> ReadWithInfo for BinaryCopyIn<I>
+where
+    I: Iterator<Item = R>,
+    R: AsRef<[&'a ToSql]>,
+{
+    fn read_with_info(&mut self, buf: &mut [u8], info: &CopyInfo) -> io::Result<usize> {
+        check_format(info)?;
+
+        while self.pos == self.buf.len() {
+            if self.done {
+                return Ok(0);
+            }
+
+            self.buf.clear();
+            self.pos = 0;
+            match self.rows.next() {
+                Some(row) => self
+                    .encoder
+                    .encode_row(row.as_ref(), &mut self.buf)
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?,
+                None => {
+                    self.done = true;
+                    self.encoder.finish(&mut self.buf);
+                }
+            }
+        }
+
+        let len = cmp::min(buf.len(), self.buf.len() - self.pos);
+        buf[..len].copy_from_slice(&self.buf[self.pos..self.pos + len]);
+        self.pos += len;
+        Ok(len)
+    }
+}
+
+/// A `WriteWithInfo` adaptor which decodes rows in the binary `COPY` format.
+///
+/// It can be passed to `Statement::copy_out`. Each row is passed to a callback as soon as it has
+/// been received, and an error returned by the callback aborts the copy.
+pub struct BinaryCopyOut<F> {
+    decoder: BinaryCopyDecoder,
+    callback: F,
+}
+
+impl<F> fmt::Debug for BinaryCopyOut<F> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("BinaryCopyOut")
+            .field("decoder", &self.decoder)
+            .finish()
+    }
+}
+
+impl<F> BinaryCopyOut<F>
+where
+    F: FnMut(BinaryCopyRow) -> io::Result<()>,
+{
+    /// Creates a new adaptor for rows with the specified column types.
+    pub fn new(types: &[Type], callback: F) -> BinaryCopyOut<F> {
+        BinaryCopyOut {
+            decoder: BinaryCopyDecoder::new(types),
+            callback: callback,
+        }
+    }
+
+    /// Consumes the adaptor, returning the callback.
+    ///
+    /// Returns an error if the data did not end with the format's trailer.
+    pub fn finish(self) -> Result<F> {
+        self.decoder.finish()?;
+        Ok(self.callback)
+    }
+}
+
+impl<F> WriteWithInfo for BinaryCopyOut<F>
+where
+    F: FnMut(BinaryCopyRow) -> io::Result<()>,
+{
+    fn write_with_info(&mut self, buf: &[u8], info: &CopyInfo) -> io::Result<usize> {
+        check_format(info)?;
+
+        self.decoder.push(buf);
+        while let Some(row) = self.decoder.next_row()? {
+            (self.callback)(row)?;
+        }
+        Ok(buf.len())
+    }
+}
+
+/// A `Write` adaptor which encodes rows in the binary `COPY` format.
+///
+/// This is useful for producing files in the format; use `BinaryCopyIn` to copy rows into the
+/// database directly.
+#[derive(Debug)]
+pub struct BinaryCopyWriter<W> {
+    writer: W,
+    encoder: BinaryCopyEncoder,
+    buf: Vec<u8>,
+}
+
+impl<W: Write> BinaryCopyWriter<W> {
+    /// Creates a new writer for rows with the specified column types.
+    ///
+    /// Returns an error if there are more columns than the format can represent.
+    pub fn new(writer: W, types: &[Type]) -> Result<BinaryCopyWriter<W>> {
+        Ok(BinaryCopyWriter {
+            writer: writer,
+            encoder: BinaryCopyEncoder::new(types)?,
+            buf: vec![],
+        })
+    }
+
+    /// Returns a shared reference to the underlying writer.
+    pub fn get_ref(&self) -> &W {
+        &self.writer
+    }
+
+    /// Returns a mutable reference to the underlying writer.
+    pub fn get_mut(&mut self) -> &mut W {
+        &mut self.writer
+    }
+
+    /// Encodes a row and writes it to the underlying writer.
+    ///
+    /// Returns an error if the number of values does not match the number of column types or if
+    /// a value cannot be converted to its column's type.
+    pub fn write(&mut self, row: &[&ToSql]) -> Result<()> {
+        self.buf.clear();
+        self.encoder
+            .encode_row(row, &mut self.buf)
+            .map_err(Error::to_sql)?;
+        self.writer.write_all(&self.buf)?;
+        Ok(())
+    }
+
+    /// Writes the trailer marking the end of the data, returning the underlying writer.
+    pub fn finish(mut self) -> Result<W> {
+        self.buf.clear();
+        self.encoder.finish(&mut self.buf);
+        self.writer.write_all(&self.buf)?;
+        self.writer.flush()?;
+        Ok(self.writer)
+    }
+}
+
+/// A reader of rows in the binary `COPY` format.
+///
+/// This is useful for reading files in the format; use `BinaryCopyOut` to copy rows out of the
+/// database directly.
+#[derive(Debug)]
+pub struct BinaryCopyReader<R> {
+    reader: R,
+    decoder: BinaryCopyDecoder,
+    done: bool,
+}
+
+impl<R: Read> BinaryCopyReader<R> {
+    /// Creates a new reader for rows with the specified column types.
+    pub fn new(reader: R, types: &[Type]) -> BinaryCopyReader<R> {
+        BinaryCopyReader {
+            reader: reader,
+            decoder: BinaryCopyDecoder::new(types),
+            done: false,
+        }
+    }
+
+    /// Returns a shared reference to the underlying reader.
+    pub fn get_ref(&self) -> &R {
+        &self.reader
+    }
+
+    /// Returns a mutable reference to the underlying reader.
+    pub fn get_mut(&mut self) -> &mut R {
+        &mut self.reader
+    }
+}
+
+impl<R: Read> FallibleIterator for BinaryCopyReader<R> {
+    type Item = BinaryCopyRow;
+    type Error = Error;
+
+    fn next(&mut self) -> Result<Option<BinaryCopyRow>> {
+        let mut buf = [0; 8 * 1024];
+        loop {
+            if let Some(row) = self.decoder.next_row()? {
+                return Ok(Some(row));
+            }
+            if self.done {
+                return Ok(None);
+            }
+
+            let n = match self.reader.read(&mut buf) {
+                Ok(n) => n,
+                Err(ref e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                Err(e) => return Err(e.into()),
+            };
+            if n == 0 {
+                self.done = true;
+                self.decoder.finish()?;
+            } else {
+                self.decoder.push(&buf[..n]);
+            }
+        }
+    }
+}
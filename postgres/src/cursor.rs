@@ -0,0 +1,104 @@
+//! Server-side cursors.
+
+use std::fmt;
+
+#[doc(inline)]
+pub use postgres_shared::cursor::Config;
+
+use rows::Rows;
+use types::ToSql;
+use {Connection, Result};
+
+/// A server-side cursor.
+///
+/// Cursors which are not holdable only exist until the end of the transaction they were declared
+/// in. Holdable cursors are closed when the `Cursor` is dropped.
+pub struct Cursor<'conn> {
+    conn: &'conn Connection,
+    name: String,
+    hold: bool,
+    finished: bool,
+}
+
+impl<'a> fmt::Debug for Cursor<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("Cursor")
+            .field("name", &self.name)
+            .field("hold", &self.hold)
+            .finish()
+    }
+}
+
+impl<'conn> Drop for Cursor<'conn> {
+    fn drop(&mut self) {
+        // Closing a cursor which did not outlive its transaction could abort an unrelated
+        // transaction started later, so only holdable cursors are cleaned up here.
+        if !self.finished && self.hold {
+            let _ = self.finish_inner();
+        }
+    }
+}
+
+impl<'conn> Cursor<'conn> {
+    pub(crate) fn new(
+        conn: &'conn Connection,
+        query: &str,
+        params: &[&ToSql],
+        config: &Config,
+    ) -> Result<Cursor<'conn>> {
+        let name = conn.0.borrow_mut().make_cursor_name();
+        conn.execute(&config.declare_query(&name, query), params)?;
+        Ok(Cursor {
+            conn: conn,
+            name: name,
+            hold: config.get_hold(),
+            finished: false,
+        })
+    }
+
+    fn finish_inner(&mut self) -> Result<()> {
+        self.conn.execute(&format!("CLOSE {}", self.name), &[])?;
+        Ok(())
+    }
+
+    /// Returns the name of the cursor.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Fetches up to the next `count` rows from the cursor.
+    ///
+    /// Fewer rows are returned once the end of the results is reached.
+    pub fn fetch(&self, count: i64) -> Result<Rows> {
+        self.conn
+            .query(&format!("FETCH FORWARD {} FROM {}", count, self.name), &[])
+    }
+
+    /// Fetches up to the previous `count` rows from the cursor, in reverse order.
+    ///
+    /// The cursor must be scrollable.
+    pub fn fetch_backward(&self, count: i64) -> Result<Rows> {
+        self.conn
+            .query(&format!("FETCH BACKWARD {} FROM {}", count, self.name), &[])
+    }
+
+    /// Positions the cursor on the row at the specified 1-based position without fetching it.
+    ///
+    /// Negative positions count from the end of the results, and position 0 is before the first
+    /// row. Moving backwards requires the cursor to be scrollable. Returns `true` if the cursor
+    /// is positioned on a row.
+    pub fn move_absolute(&self, position: i64) -> Result<bool> {
+        let count = self
+            .conn
+            .execute(&format!("MOVE ABSOLUTE {} IN {}", position, self.name), &[])?;
+        Ok(count != 0)
+    }
+
+    /// Consumes the cursor, closing it.
+    ///
+    /// Unlike the `Drop` implementation, this closes the cursor even if it is not holdable.
+    pub fn close(mut self) -> Result<()> {
+        self.finished = true;
+        self.finish_inner()
+    }
+}
@@ -0,0 +1,204 @@
+//! Large objects.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! # use postgres::{Connection, TlsMode};
+//! use postgres::large_object::{LargeObject, Mode};
+//! use std::io::{Read, Write};
+//!
+//! # let conn = Connection::connect("", TlsMode::None).unwrap();
+//! let trans = conn.transaction().unwrap();
+//! let oid = LargeObject::create(&trans).unwrap();
+//!
+//! let mut lo = LargeObject::open(&trans, oid, Mode::ReadWrite).unwrap();
+//! lo.write_all(b"hello world").unwrap();
+//! lo.finish().unwrap();
+//!
+//! let mut lo = LargeObject::open(&trans, oid, Mode::Read).unwrap();
+//! let mut data = vec![];
+//! lo.read_to_end(&mut data).unwrap();
+//! assert_eq!(data, b"hello world");
+//! ```
+
+use std::cmp;
+use std::fmt;
+use std::i32;
+use std::i64;
+use std::io::{self, Read, Seek, SeekFrom, Write};
+
+#[doc(inline)]
+pub use postgres_shared::large_object::Mode;
+
+use transaction::Transaction;
+use types::{FromSql, Oid, ToSql};
+use {Connection, GenericConnection, Result};
+
+/// A handle to an open large object.
+///
+/// Large objects can only be accessed inside of the transaction which opened them, and are
+/// closed when the `LargeObject` is dropped.
+pub struct LargeObject<'conn> {
+    conn: &'conn Connection,
+    fd: i32,
+    depth: u32,
+    trans_id: u64,
+    finished: bool,
+}
+
+impl<'a> fmt::Debug for LargeObject<'a> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("LargeObject")
+            .field("fd", &self.fd)
+            .field("transaction_depth", &self.depth)
+            .finish()
+    }
+}
+
+impl<'conn> Drop for LargeObject<'conn> {
+    fn drop(&mut self) {
+        if !self.finished && self.is_active() {
+            let _ = self.finish_inner();
+        }
+    }
+}
+
+impl<'conn> LargeObject<'conn> {
+    /// Creates a new, empty large object, returning its OID.
+    pub fn create(conn: &GenericConnection) -> Result<Oid> {
+        let stmt = conn.prepare_cached("SELECT pg_catalog.lo_create(0)")?;
+        let rows = stmt.query(&[])?;
+        Ok(rows.get(0).get(0))
+    }
+
+    /// Deletes the large object with the specified OID.
+    pub fn unlink(conn: &GenericConnection, oid: Oid) -> Result<()> {
+        let stmt = conn.prepare_cached("SELECT pg_catalog.lo_unlink($1)")?;
+        stmt.query(&[&oid])?;
+        Ok(())
+    }
+
+    /// Opens the large object with the specified OID.
+    ///
+    /// The object can only be used while `trans` is open.
+    pub fn open(trans: &Transaction<'conn>, oid: Oid, mode: Mode) -> Result<LargeObject<'conn>> {
+        let stmt = trans.prepare_cached("SELECT pg_catalog.lo_open($1, $2)")?;
+        let rows = stmt.query(&[&oid, &mode.to_flags()])?;
+        Ok(LargeObject {
+            conn: trans.conn(),
+            fd: rows.get(0).get(0),
+            depth: trans.depth(),
+            trans_id: trans.id(),
+            finished: false,
+        })
+    }
+
+    /// Determines if the transaction which opened the large object is still open.
+    fn is_active(&self) -> bool {
+        let conn = self.conn.0.borrow();
+        conn.is_transaction_open(self.depth, self.trans_id)
+    }
+
+    fn call<T>(&self, query: &str, params: &[&ToSql]) -> Result<T>
+    where
+        T: for<'a> FromSql<'a>,
+    {
+        if !self.is_active() {
+            return Err(io::Error::new(
+                io::ErrorKind::Other,
+                "the transaction which opened the large object has ended",
+            ).into());
+        }
+
+        let stmt = self.conn.prepare_cached(query)?;
+        let rows = stmt.query(params)?;
+        Ok(rows.get(0).get(0))
+    }
+
+    fn finish_inner(&mut self) -> Result<()> {
+        self.call::<i32>("SELECT pg_catalog.lo_close($1)", &[&self.fd])?;
+        Ok(())
+    }
+
+    /// Returns the large object's file descriptor.
+    pub fn fd(&self) -> i32 {
+        self.fd
+    }
+
+    /// Returns the current position within the large object.
+    pub fn tell(&self) -> Result<u64> {
+        let pos = self.call::<i64>("SELECT pg_catalog.lo_tell64($1)", &[&self.fd])?;
+        Ok(pos as u64)
+    }
+
+    /// Truncates the large object to the specified length, extending it with zeroes if it is
+    /// shorter.
+    pub fn truncate(&self, len: u64) -> Result<()> {
+        self.call::<i32>(
+            "SELECT pg_catalog.lo_truncate64($1, $2)",
+            &[&self.fd, &(len as i64)],
+        )?;
+        Ok(())
+    }
+
+    /// Consumes the `LargeObject`, closing it.
+    ///
+    /// Functionally equivalent to the `Drop` implementation of `LargeObject` except that it
+    /// returns any error to the caller.
+    pub fn finish(mut self) -> Result<()> {
+        self.finished = true;
+        self.finish_inner()
+    }
+}
+
+fn to_io(e: ::Error) -> io::Error {
+    io::Error::new(io::ErrorKind::Other, e)
+}
+
+impl<'conn> Read for LargeObject<'conn> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let len = cmp::min(buf.len(), i32::MAX as usize) as i32;
+        let data = self
+            .call::<Vec<u8>>("SELECT pg_catalog.loread($1, $2)", &[&self.fd, &len])
+            .map_err(to_io)?;
+        buf[..data.len()].copy_from_slice(&data);
+        Ok(data.len())
+    }
+}
+
+impl<'conn> Write for LargeObject<'conn> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        let buf = &buf[..cmp::min(buf.len(), i32::MAX as usize)];
+        let len = self
+            .call::<i32>("SELECT pg_catalog.lowrite($1, $2)", &[&self.fd, &buf])
+            .map_err(to_io)?;
+        Ok(len as usize)
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl<'conn> Seek for LargeObject<'conn> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        // SEEK_SET, SEEK_CUR and SEEK_END
+        let (pos, whence) = match pos {
+            SeekFrom::Start(pos) if pos > i64::MAX as u64 => {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    "seek position out of range",
+                ));
+            }
+            SeekFrom::Start(pos) => (pos as i64, 0),
+            SeekFrom::Current(pos) => (pos, 1),
+            SeekFrom::End(pos) => (pos, 2),
+        };
+        let pos = self
+            .call::<i64>(
+                "SELECT pg_catalog.lo_lseek64($1, $2, $3)",
+                &[&self.fd, &pos, &whence],
+            ).map_err(to_io)?;
+        Ok(pos as u64)
+    }
+}
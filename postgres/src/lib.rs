@@ -84,7 +84,9 @@ use postgres_protocol::authentication;
 use postgres_protocol::authentication::sasl::{self, ChannelBinding, ScramSha256};
 use postgres_protocol::message::backend::{self, ErrorFields};
 use postgres_protocol::message::frontend;
+use postgres_shared::quote_literal;
 use postgres_shared::rows::RowData;
+use postgres_shared::stmt::{result_format_codes, ColumnDescription};
 use std::cell::{Cell, RefCell};
 use std::collections::{HashMap, VecDeque};
 use std::fmt;
@@ -94,6 +96,8 @@ use std::result;
 use std::sync::Arc;
 use std::time::Duration;
 
+use advisory_lock::AdvisoryLock;
+use cursor::Cursor;
 use error::{DbError, SqlState};
 use notification::{Notification, Notifications};
 use params::{IntoConnectParams, User};
@@ -108,18 +112,23 @@ use types::{Field, FromSql, IsNull, Kind, Oid, ToSql, Type};
 #[doc(inline)]
 pub use error::Error;
 #[doc(inline)]
-pub use postgres_shared::CancelData;
+pub use postgres_shared::{CancelData, PreparedTransaction, WithNotices};
 #[doc(inline)]
 pub use postgres_shared::{error, types};
 
 #[macro_use]
 mod macros;
 
+pub mod advisory_lock;
+pub mod binary_copy;
+pub mod cursor;
+pub mod large_object;
 pub mod notification;
 pub mod params;
 mod priv_io;
 pub mod rows;
 pub mod stmt;
+pub mod text_copy;
 pub mod text_rows;
 pub mod tls;
 pub mod transaction;
@@ -186,7 +195,9 @@ pub fn cancel_query<T>(params: T, tls: TlsMode, data: &CancelData) -> Result<()>
 where
     T: IntoConnectParams,
 {
-    let params = params.into_connect_params().map_err(error::connect)?;
+    let params = params
+        .into_connect_params()
+        .map_err(Error::connect_params)?;
     let mut socket = priv_io::initialize_stream(&params, tls)?;
 
     let mut buf = vec![];
@@ -205,10 +216,7 @@ fn bad_response() -> io::Error {
 }
 
 fn desynchronized() -> io::Error {
-    io::Error::new(
-        io::ErrorKind::Other,
-        "communication with the server has desynchronized due to an earlier IO error",
-    )
+    error::__desynchronized()
 }
 
 /// Specifies the TLS support requested for a new connection.
@@ -233,12 +241,17 @@ struct InnerConnection {
     stream: MessageStream,
     notice_handler: Box<HandleNotice>,
     notifications: VecDeque<Notification>,
+    notices: Vec<DbError>,
+    capturing_notices: bool,
     cancel_data: CancelData,
     unknown_types: HashMap<Oid, Type>,
     cached_statements: HashMap<String, Arc<StatementInfo>>,
     parameters: HashMap<String, String>,
     next_stmt_id: u32,
+    next_cursor_id: u32,
     trans_depth: u32,
+    trans_ids: Vec<u64>,
+    next_trans_id: u64,
     desynchronized: bool,
     finished: bool,
     has_typeinfo_query: bool,
@@ -259,23 +272,24 @@ impl InnerConnection {
     where
         T: IntoConnectParams,
     {
-        let params = params.into_connect_params().map_err(error::connect)?;
-        let stream = priv_io::initialize_stream(&params, tls)?;
-
+        let params = params
+            .into_connect_params()
+            .map_err(Error::connect_params)?;
         let user = match params.user() {
             Some(user) => user,
-            None => {
-                return Err(error::connect(
-                    "user missing from connection parameters".into(),
-                ));
-            }
+            None => return Err(Error::missing_user()),
         };
 
+        let stream = priv_io::initialize_stream(&params, tls)?;
+
         let mut conn = InnerConnection {
             stream: MessageStream::new(stream),
             next_stmt_id: 0,
+            next_cursor_id: 0,
             notice_handler: Box::new(LoggingNoticeHandler),
             notifications: VecDeque::new(),
+            notices: vec![],
+            capturing_notices: false,
             cancel_data: CancelData {
                 process_id: 0,
                 secret_key: 0,
@@ -286,6 +300,8 @@ impl InnerConnection {
             desynchronized: false,
             finished: false,
             trans_depth: 0,
+            trans_ids: vec![],
+            next_trans_id: 0,
             has_typeinfo_query: false,
             has_typeinfo_enum_query: false,
             has_typeinfo_composite_query: false,
@@ -301,6 +317,9 @@ impl InnerConnection {
         if let Some(database) = params.database() {
             options.push(("database".to_owned(), database.to_owned()));
         }
+        if let Some(replication) = params.replication() {
+            options.push(("replication".to_owned(), replication.startup_value().to_owned()));
+        }
 
         let options = options.iter().map(|&(ref a, ref b)| (&**a, &**b));
         conn.stream
@@ -332,6 +351,9 @@ impl InnerConnection {
             match try_desync!(self, self.stream.read_message()) {
                 backend::Message::NoticeResponse(body) => {
                     if let Ok(err) = DbError::new(&mut body.fields()) {
+                        if self.capturing_notices {
+                            self.notices.push(err.clone());
+                        }
                         self.notice_handler.handle_notice(err);
                     }
                 }
@@ -339,6 +361,10 @@ impl InnerConnection {
                     self.parameters
                         .insert(body.name()?.to_owned(), body.value()?.to_owned());
                 }
+                backend::Message::ReadyForQuery(body) => {
+                    self.capturing_notices = false;
+                    return Ok(backend::Message::ReadyForQuery(body));
+                }
                 val => return Ok(val),
             }
         }
@@ -404,17 +430,13 @@ impl InnerConnection {
         match self.read_message()? {
             backend::Message::AuthenticationOk => return Ok(()),
             backend::Message::AuthenticationCleartextPassword => {
-                let pass = user.password().ok_or_else(|| {
-                    error::connect("a password was requested but not provided".into())
-                })?;
+                let pass = user.password().ok_or_else(Error::missing_password)?;
                 self.stream
                     .write_message(|buf| frontend::password_message(pass, buf))?;
                 self.stream.flush()?;
             }
             backend::Message::AuthenticationMd5Password(body) => {
-                let pass = user.password().ok_or_else(|| {
-                    error::connect("a password was requested but not provided".into())
-                })?;
+                let pass = user.password().ok_or_else(Error::missing_password)?;
                 let output =
                     authentication::md5_hash(user.name().as_bytes(), pass.as_bytes(), body.salt());
                 self.stream
@@ -455,14 +477,10 @@ impl InnerConnection {
                         None => (ChannelBinding::unsupported(), sasl::SCRAM_SHA_256),
                     }
                 } else {
-                    return Err(
-                        io::Error::new(io::ErrorKind::Other, "unsupported authentication").into(),
-                    );
+                    return Err(Error::unsupported_authentication());
                 };
 
-                let pass = user.password().ok_or_else(|| {
-                    error::connect("a password was requested but not provided".into())
-                })?;
+                let pass = user.password().ok_or_else(Error::missing_password)?;
 
                 let mut scram = ScramSha256::new(pass.as_bytes(), channel_binding);
 
@@ -495,9 +513,7 @@ impl InnerConnection {
             | backend::Message::AuthenticationScmCredential
             | backend::Message::AuthenticationGss
             | backend::Message::AuthenticationSspi => {
-                return Err(
-                    io::Error::new(io::ErrorKind::Other, "unsupported authentication").into(),
-                )
+                return Err(Error::unsupported_authentication())
             }
             backend::Message::ErrorResponse(body) => return Err(err(&mut body.fields())),
             _ => return Err(bad_response().into()),
@@ -625,6 +641,7 @@ impl InnerConnection {
         row_limit: i32,
         param_types: &[Type],
         params: &[&ToSql],
+        columns: &[Column],
     ) -> Result<()> {
         assert!(
             param_types.len() == params.len(),
@@ -649,14 +666,14 @@ impl InnerConnection {
                         Ok(IsNull::No) => Ok(postgres_protocol::IsNull::No),
                         Err(e) => Err(e),
                     },
-                    Some(1),
+                    result_format_codes(columns),
                     buf,
                 )
             });
             match r {
                 Ok(()) => {}
                 Err(frontend::BindError::Conversion(e)) => {
-                    return Err(error::conversion(e));
+                    return Err(Error::to_sql(e));
                 }
                 Err(frontend::BindError::Serialization(e)) => return Err(e.into()),
             }
@@ -668,6 +685,9 @@ impl InnerConnection {
             .write_message(|buf| Ok::<(), io::Error>(frontend::sync(buf)))?;
         self.stream.flush()?;
 
+        // only once the request is sent, so a failed bind doesn't leave capturing enabled
+        self.capture_notices();
+
         match self.read_message()? {
             backend::Message::BindComplete => Ok(()),
             backend::Message::ErrorResponse(body) => {
@@ -681,12 +701,29 @@ impl InnerConnection {
         }
     }
 
+    /// Starts collecting the notices raised until the next `ReadyForQuery`.
+    fn capture_notices(&mut self) {
+        self.notices.clear();
+        self.capturing_notices = true;
+    }
+
+    /// Returns the notices raised by the most recently executed statement.
+    fn take_notices(&mut self) -> Vec<DbError> {
+        mem::replace(&mut self.notices, vec![])
+    }
+
     fn make_stmt_name(&mut self) -> String {
         let stmt_name = format!("s{}", self.next_stmt_id);
         self.next_stmt_id += 1;
         stmt_name
     }
 
+    fn make_cursor_name(&mut self) -> String {
+        let cursor_name = format!("c{}", self.next_cursor_id);
+        self.next_cursor_id += 1;
+        cursor_name
+    }
+
     fn prepare_typed<'a>(
         &mut self,
         query: &str,
@@ -759,10 +796,8 @@ impl InnerConnection {
             Some(body) => body
                 .fields()
                 .and_then(|field| {
-                    Ok(Column::new(
-                        field.name().to_owned(),
-                        self.get_type(field.type_oid())?,
-                    ))
+                    let ty = self.get_type(field.type_oid())?;
+                    Ok(ColumnDescription::new(&field).into_column(ty))
                 }).collect()
                 .map_err(From::from),
             None => Ok(vec![]),
@@ -810,7 +845,7 @@ impl InnerConnection {
     #[allow(if_not_else)]
     fn read_type(&mut self, oid: Oid) -> Result<Type> {
         self.setup_typeinfo_query()?;
-        self.raw_execute(TYPEINFO_QUERY, "", 0, &[Type::OID], &[&oid])?;
+        self.raw_execute(TYPEINFO_QUERY, "", 0, &[Type::OID], &[&oid], &[])?;
         let mut row = None;
         self.read_rows(|r| row = Some(r))?;
 
@@ -818,19 +853,17 @@ impl InnerConnection {
 
         let (name, type_, elem_oid, rngsubtype, basetype, schema, relid) = {
             let name =
-                String::from_sql_nullable(&Type::NAME, get_raw(0)).map_err(error::conversion)?;
-            let type_ =
-                i8::from_sql_nullable(&Type::CHAR, get_raw(1)).map_err(error::conversion)?;
+                String::from_sql_nullable(&Type::NAME, get_raw(0)).map_err(Error::from_sql)?;
+            let type_ = i8::from_sql_nullable(&Type::CHAR, get_raw(1)).map_err(Error::from_sql)?;
             let elem_oid =
-                Oid::from_sql_nullable(&Type::OID, get_raw(2)).map_err(error::conversion)?;
+                Oid::from_sql_nullable(&Type::OID, get_raw(2)).map_err(Error::from_sql)?;
             let rngsubtype = Option::<Oid>::from_sql_nullable(&Type::OID, get_raw(3))
-                .map_err(error::conversion)?;
+                .map_err(Error::from_sql)?;
             let basetype =
-                Oid::from_sql_nullable(&Type::OID, get_raw(4)).map_err(error::conversion)?;
+                Oid::from_sql_nullable(&Type::OID, get_raw(4)).map_err(Error::from_sql)?;
             let schema =
-                String::from_sql_nullable(&Type::NAME, get_raw(5)).map_err(error::conversion)?;
-            let relid =
-                Oid::from_sql_nullable(&Type::OID, get_raw(6)).map_err(error::conversion)?;
+                String::from_sql_nullable(&Type::NAME, get_raw(5)).map_err(Error::from_sql)?;
+            let relid = Oid::from_sql_nullable(&Type::OID, get_raw(6)).map_err(Error::from_sql)?;
             (name, type_, elem_oid, rngsubtype, basetype, schema, relid)
         };
 
@@ -888,15 +921,14 @@ impl InnerConnection {
 
     fn read_enum_variants(&mut self, oid: Oid) -> Result<Vec<String>> {
         self.setup_typeinfo_enum_query()?;
-        self.raw_execute(TYPEINFO_ENUM_QUERY, "", 0, &[Type::OID], &[&oid])?;
+        self.raw_execute(TYPEINFO_ENUM_QUERY, "", 0, &[Type::OID], &[&oid], &[])?;
         let mut rows = vec![];
         self.read_rows(|row| rows.push(row))?;
 
         let mut variants = vec![];
         for row in rows {
-            variants.push(
-                String::from_sql_nullable(&Type::NAME, row.get(0)).map_err(error::conversion)?,
-            );
+            variants
+                .push(String::from_sql_nullable(&Type::NAME, row.get(0)).map_err(Error::from_sql)?);
         }
 
         Ok(variants)
@@ -924,17 +956,17 @@ impl InnerConnection {
 
     fn read_composite_fields(&mut self, relid: Oid) -> Result<Vec<Field>> {
         self.setup_typeinfo_composite_query()?;
-        self.raw_execute(TYPEINFO_COMPOSITE_QUERY, "", 0, &[Type::OID], &[&relid])?;
+        self.raw_execute(TYPEINFO_COMPOSITE_QUERY, "", 0, &[Type::OID], &[&relid], &[])?;
         let mut rows = vec![];
         self.read_rows(|row| rows.push(row))?;
 
         let mut fields = vec![];
         for row in rows {
             let (name, type_) = {
-                let name = String::from_sql_nullable(&Type::NAME, row.get(0))
-                    .map_err(error::conversion)?;
+                let name =
+                    String::from_sql_nullable(&Type::NAME, row.get(0)).map_err(Error::from_sql)?;
                 let type_ =
-                    Oid::from_sql_nullable(&Type::OID, row.get(1)).map_err(error::conversion)?;
+                    Oid::from_sql_nullable(&Type::OID, row.get(1)).map_err(Error::from_sql)?;
                 (name, type_)
             };
             let type_ = self.get_type(type_)?;
@@ -1035,6 +1067,48 @@ impl InnerConnection {
         Ok(result)
     }
 
+    fn quick_command(&mut self, query: &str) -> Result<String> {
+        check_desync!(self);
+        debug!("executing command: {}", query);
+        self.stream
+            .write_message(|buf| frontend::query(query, buf))?;
+        self.stream.flush()?;
+
+        let mut tag = String::new();
+        loop {
+            match self.read_message()? {
+                backend::Message::ReadyForQuery(_) => break,
+                backend::Message::CommandComplete(body) => tag = body.tag()?.to_owned(),
+                backend::Message::ErrorResponse(body) => {
+                    self.wait_for_ready()?;
+                    return Err(err(&mut body.fields()));
+                }
+                _ => {}
+            }
+        }
+        Ok(tag)
+    }
+
+    // Records the start of a (possibly nested) transaction, returning an ID
+    // which is unique over the lifetime of the connection.
+    fn begin_transaction(&mut self) -> u64 {
+        self.trans_depth += 1;
+        self.next_trans_id += 1;
+        self.trans_ids.push(self.next_trans_id);
+        self.next_trans_id
+    }
+
+    fn end_transaction(&mut self) {
+        self.trans_depth -= 1;
+        self.trans_ids.pop();
+    }
+
+    // Determines if the transaction with the specified depth and ID is still
+    // open.
+    fn is_transaction_open(&self, depth: u32, id: u64) -> bool {
+        depth > 0 && self.trans_ids.get(depth as usize - 1) == Some(&id)
+    }
+
     fn finish_inner(&mut self) -> Result<()> {
         check_desync!(self);
         self.stream
@@ -1167,6 +1241,21 @@ impl Connection {
         stmt.execute(params)
     }
 
+    /// Like `execute`, but also returns the notices the server raised while executing the
+    /// statement, such as the output of `RAISE NOTICE`.
+    ///
+    /// The notices are still passed to the connection's notice handler.
+    pub fn execute_with_notices(&self, query: &str, params: &[&ToSql]) -> Result<WithNotices<u64>> {
+        let (param_types, columns) = self.0.borrow_mut().raw_prepare("", query, &[])?;
+        let info = Arc::new(StatementInfo {
+            name: String::new(),
+            param_types: param_types,
+            columns: columns,
+        });
+        let stmt = Statement::new(self, info, Cell::new(0), true);
+        stmt.execute_with_notices(params)
+    }
+
     /// Executes a statement, returning the resulting rows.
     ///
     /// A statement may contain parameters, specified by `$n` where `n` is the
@@ -1203,6 +1292,94 @@ impl Connection {
         stmt.into_query(params)
     }
 
+    /// Declares a server-side cursor over the results of a query.
+    ///
+    /// Unless the cursor is declared as holdable with `declare_cursor_with`, this must be called
+    /// inside of a transaction.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use postgres::{Connection, TlsMode};
+    /// # let conn = Connection::connect("", TlsMode::None).unwrap();
+    /// let trans = conn.transaction().unwrap();
+    /// let cursor = trans.declare_cursor("SELECT foo FROM bar", &[]).unwrap();
+    /// loop {
+    ///     let rows = cursor.fetch(100).unwrap();
+    ///     if rows.is_empty() {
+    ///         break;
+    ///     }
+    ///     for row in &rows {
+    ///         let foo: i32 = row.get(0);
+    ///         println!("foo: {}", foo);
+    ///     }
+    /// }
+    /// ```
+    pub fn declare_cursor<'a>(&'a self, query: &str, params: &[&ToSql]) -> Result<Cursor<'a>> {
+        self.declare_cursor_with(query, params, &cursor::Config::new())
+    }
+
+    /// Declares a server-side cursor with the specified configuration.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use postgres::{Connection, TlsMode};
+    /// use postgres::cursor::Config;
+    ///
+    /// # let conn = Connection::connect("", TlsMode::None).unwrap();
+    /// let cursor = conn
+    ///     .declare_cursor_with("SELECT foo FROM bar", &[], Config::new().scroll(true).hold(true))
+    ///     .unwrap();
+    /// let last = cursor.fetch_backward(1).unwrap();
+    /// ```
+    pub fn declare_cursor_with<'a>(
+        &'a self,
+        query: &str,
+        params: &[&ToSql],
+        config: &cursor::Config,
+    ) -> Result<Cursor<'a>> {
+        Cursor::new(self, query, params, config)
+    }
+
+    /// Acquires a session-level advisory lock, waiting until it is available.
+    ///
+    /// The key may be an `i64` or an `(i32, i32)` pair. The lock is held until the returned
+    /// `AdvisoryLock` is dropped, regardless of any transactions.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use postgres::{Connection, TlsMode};
+    /// use postgres::advisory_lock::Mode;
+    ///
+    /// # let conn = Connection::connect("", TlsMode::None).unwrap();
+    /// let lock = conn.advisory_lock((1, 2), Mode::Shared).unwrap();
+    /// ```
+    pub fn advisory_lock<'a, K>(
+        &'a self,
+        key: K,
+        mode: advisory_lock::Mode,
+    ) -> Result<AdvisoryLock<'a>>
+    where
+        K: Into<advisory_lock::Key>,
+    {
+        AdvisoryLock::lock(self, key.into(), mode)
+    }
+
+    /// Like `advisory_lock`, but returns `None` rather than waiting if the lock is not
+    /// immediately available.
+    pub fn try_advisory_lock<'a, K>(
+        &'a self,
+        key: K,
+        mode: advisory_lock::Mode,
+    ) -> Result<Option<AdvisoryLock<'a>>>
+    where
+        K: Into<advisory_lock::Key>,
+    {
+        AdvisoryLock::try_lock(self, key.into(), mode)
+    }
+
     /// Begins a new transaction.
     ///
     /// Returns a `Transaction` object which should be used instead of
@@ -1243,8 +1420,51 @@ impl Connection {
         let mut query = "BEGIN".to_owned();
         config.build_command(&mut query);
         conn.quick_query(&query)?;
-        conn.trans_depth += 1;
-        Ok(Transaction::new(self, 1))
+        let id = conn.begin_transaction();
+        Ok(Transaction::new(self, 1, id))
+    }
+
+    /// Commits a transaction previously prepared for two-phase commit with
+    /// `Transaction::prepare_transaction`.
+    ///
+    /// The transaction may have been prepared by any session. This cannot be
+    /// called inside of a transaction.
+    pub fn commit_prepared(&self, gid: &str) -> Result<()> {
+        self.0
+            .borrow_mut()
+            .quick_query(&format!("COMMIT PREPARED {}", quote_literal(gid)))
+            .map(|_| ())
+    }
+
+    /// Rolls back a transaction previously prepared for two-phase commit with
+    /// `Transaction::prepare_transaction`.
+    ///
+    /// The transaction may have been prepared by any session. This cannot be
+    /// called inside of a transaction.
+    pub fn rollback_prepared(&self, gid: &str) -> Result<()> {
+        self.0
+            .borrow_mut()
+            .quick_query(&format!("ROLLBACK PREPARED {}", quote_literal(gid)))
+            .map(|_| ())
+    }
+
+    /// Returns the transactions currently prepared for two-phase commit.
+    pub fn list_prepared(&self) -> Result<Vec<PreparedTransaction>> {
+        let stmt = self.prepare_cached(
+            "SELECT transaction::text::int8, gid, prepared, owner, database \
+             FROM pg_catalog.pg_prepared_xacts ORDER BY prepared",
+        )?;
+        let rows = stmt.query(&[])?;
+        let xacts = rows.iter()
+            .map(|row| PreparedTransaction {
+                transaction: row.get::<_, i64>(0) as u32,
+                gid: row.get(1),
+                prepared: row.get(2),
+                owner: row.get(3),
+                database: row.get(4),
+            })
+            .collect();
+        Ok(xacts)
     }
 
     /// Creates a new prepared statement.
@@ -1560,7 +1780,7 @@ impl<'a> GenericConnection for Transaction<'a> {
 
 fn err(fields: &mut ErrorFields) -> Error {
     match DbError::new(fields) {
-        Ok(err) => error::db(err),
-        Err(err) => err.into(),
+        Ok(err) => err.into(),
+        Err(err) => Error::parse(err),
     }
 }
@@ -2,12 +2,15 @@
 
 use fallible_iterator::{FallibleIterator, IntoFallibleIterator};
 use std::fmt;
+#[cfg(unix)]
+use std::os::unix::io::{AsRawFd, RawFd};
+#[cfg(windows)]
+use std::os::windows::io::{AsRawSocket, RawSocket};
 use std::time::Duration;
 use postgres_protocol::message::backend::{self, ErrorFields};
 use error::DbError;
 
 #[doc(inline)]
-use postgres_shared;
 pub use postgres_shared::Notification;
 
 use {desynchronized, Result, Connection};
@@ -72,6 +75,65 @@ impl<'conn> Notifications<'conn> {
             timeout: timeout,
         }
     }
+
+    /// Reads all notifications available on the socket without blocking,
+    /// returning `true` if any are ready to be returned by `try_next`.
+    ///
+    /// This is intended to be used with an external event loop: register the
+    /// connection's socket (available via `AsRawFd` or `AsRawSocket`) for read
+    /// readiness and call this method when it fires. Pending notifications
+    /// should be drained with `try_next` before waiting on the socket again.
+    pub fn poll_ready(&self) -> Result<bool> {
+        let mut conn = self.conn.0.borrow_mut();
+
+        if !conn.notifications.is_empty() {
+            return Ok(true);
+        }
+
+        if conn.is_desynchronized() {
+            return Err(desynchronized().into());
+        }
+
+        loop {
+            match conn.read_message_with_notification_nonblocking() {
+                Ok(Some(backend::Message::NotificationResponse(body))) => {
+                    let notification = Notification {
+                        process_id: body.process_id(),
+                        channel: body.channel()?.to_owned(),
+                        payload: body.message()?.to_owned(),
+                    };
+                    conn.notifications.push_back(notification);
+                }
+                Ok(Some(backend::Message::ErrorResponse(body))) => {
+                    return Err(err(&mut body.fields()))
+                }
+                Ok(None) => return Ok(!conn.notifications.is_empty()),
+                Err(err) => return Err(err.into()),
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    /// Returns the next notification if one is available without blocking.
+    ///
+    /// This is equivalent to `self.iter().next()`.
+    pub fn try_next(&self) -> Result<Option<Notification>> {
+        self.iter().next()
+    }
+}
+
+#[cfg(unix)]
+impl<'conn> AsRawFd for Notifications<'conn> {
+    fn as_raw_fd(&self) -> RawFd {
+        self.conn.0.borrow().stream.get_ref().get_ref().as_raw_fd()
+    }
+}
+
+#[cfg(windows)]
+impl<'conn> AsRawSocket for Notifications<'conn> {
+    fn as_raw_socket(&self) -> RawSocket {
+        self.conn.0.borrow().stream.get_ref().get_ref().as_raw_socket()
+    }
 }
 
 impl<'a, 'conn> IntoFallibleIterator for &'a Notifications<'conn> {
@@ -203,7 +265,7 @@ impl<'a> FallibleIterator for TimeoutIter<'a> {
 
 fn err(fields: &mut ErrorFields) -> Error {
     match DbError::new(fields) {
-        Ok(err) => postgres_shared::error::db(err),
-        Err(err) => err.into(),
+        Ok(err) => err.into(),
+        Err(err) => Error::parse(err),
     }
 }
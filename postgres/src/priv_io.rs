@@ -11,7 +11,7 @@ use std::os::windows::io::{AsRawSocket, RawSocket};
 use std::result;
 use std::time::Duration;
 
-use error;
+use error::Error;
 use params::{ConnectParams, Host};
 use tls::TlsStream;
 use {Result, TlsMode};
@@ -243,7 +243,7 @@ pub fn initialize_stream(params: &ConnectParams, tls: TlsMode) -> Result<Box<Tls
     socket.read_exact(&mut b)?;
     if b[0] == b'N' {
         if tls_required {
-            return Err(error::tls("the server does not support TLS".into()));
+            return Err(Error::tls("the server does not support TLS".into()));
         } else {
             return Ok(Box::new(socket));
         }
@@ -255,5 +255,5 @@ pub fn initialize_stream(params: &ConnectParams, tls: TlsMode) -> Result<Box<Tls
         Host::Unix(_) => return Err(::bad_response().into()),
     };
 
-    handshaker.tls_handshake(host, socket).map_err(error::tls)
+    handshaker.tls_handshake(host, socket).map_err(Error::tls)
 }
@@ -8,15 +8,16 @@ use std::fmt;
 use std::io;
 use std::ops::Deref;
 use std::slice;
+use std::str;
 use std::sync::Arc;
 
 #[doc(inline)]
 pub use postgres_shared::rows::RowIndex;
 
-use error;
-use stmt::{Column, Statement};
+use error::DbError;
+use stmt::{Column, Format, Statement};
 use transaction::Transaction;
-use types::{FromSql, WrongType};
+use types::{__from_sql_column, FromSql};
 use {Error, Result, StatementInfo};
 
 enum MaybeOwned<'a, T: 'a> {
@@ -39,6 +40,7 @@ impl<'a, T> Deref for MaybeOwned<'a, T> {
 pub struct Rows {
     stmt_info: Arc<StatementInfo>,
     data: Vec<RowData>,
+    notices: Vec<DbError>,
 }
 
 impl fmt::Debug for Rows {
@@ -46,15 +48,17 @@ impl fmt::Debug for Rows {
         fmt.debug_struct("Rows")
             .field("columns", &self.columns())
             .field("rows", &self.data.len())
+            .field("notices", &self.notices.len())
             .finish()
     }
 }
 
 impl Rows {
-    pub(crate) fn new(stmt: &Statement, data: Vec<RowData>) -> Rows {
+    pub(crate) fn new(stmt: &Statement, data: Vec<RowData>, notices: Vec<DbError>) -> Rows {
         Rows {
             stmt_info: stmt.info().clone(),
             data: data,
+            notices: notices,
         }
     }
 
@@ -73,6 +77,14 @@ impl Rows {
         self.len() == 0
     }
 
+    /// Returns the notices the server raised while executing the query, such as the output of
+    /// `RAISE NOTICE`.
+    ///
+    /// The notices are also passed to the connection's notice handler.
+    pub fn notices(&self) -> &[DbError] {
+        &self.notices
+    }
+
     /// Returns a specific `Row`.
     ///
     /// # Panics
@@ -225,12 +237,82 @@ impl<'a> Row<'a> {
             None => return None,
         };
 
-        let ty = self.stmt_info.columns[idx].type_();
-        if !<T as FromSql>::accepts(ty) {
-            return Some(Err(error::conversion(Box::new(WrongType::new(ty.clone())))));
+        let value = __from_sql_column(&self.stmt_info.columns[idx], self.data.get(idx));
+        Some(value.map_err(Error::from_sql))
+    }
+
+    /// Retrieves the raw bytes of a field of the row, without decoding them.
+    ///
+    /// The bytes are in the format the column was retrieved in, normally the binary format of the
+    /// column's type, which is available from `columns`. Returns `None` if the value is `NULL`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index does not reference a column.
+    pub fn get_raw<I>(&self, idx: I) -> Option<&[u8]>
+    where
+        I: RowIndex + fmt::Debug,
+    {
+        match idx.__idx(&self.stmt_info.columns) {
+            Some(idx) => self.data.get(idx),
+            None => panic!("no such column {:?}", idx),
+        }
+    }
+
+    /// Retrieves the server's text representation of a field of the row.
+    ///
+    /// The column must have been retrieved in the text format; see `Statement::set_result_formats`.
+    /// Returns `None` if the value is `NULL`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index does not reference a column, the column was not retrieved in the text
+    /// format, or the value is not valid UTF-8.
+    pub fn get_text<I>(&self, idx: I) -> Option<&str>
+    where
+        I: RowIndex + fmt::Debug,
+    {
+        match self.get_text_inner(&idx) {
+            Some(Ok(ok)) => ok,
+            Some(Err(err)) => panic!("error retrieving column {:?}: {:?}", idx, err),
+            None => panic!("no such column {:?}", idx),
+        }
+    }
+
+    /// Retrieves the server's text representation of a field of the row.
+    ///
+    /// Returns `None` if the index does not reference a column, `Some(Err(..))` if the column was
+    /// not retrieved in the text format or the value is not valid UTF-8, and `Some(Ok(..))` on
+    /// success.
+    pub fn get_text_opt<I>(&self, idx: I) -> Option<Result<Option<&str>>>
+    where
+        I: RowIndex,
+    {
+        self.get_text_inner(&idx)
+    }
+
+    fn get_text_inner<I>(&self, idx: &I) -> Option<Result<Option<&str>>>
+    where
+        I: RowIndex,
+    {
+        let idx = match idx.__idx(&self.stmt_info.columns) {
+            Some(idx) => idx,
+            None => return None,
+        };
+
+        if self.stmt_info.columns[idx].format() != Format::Text {
+            return Some(Err(Error::from_sql(
+                "the column was not retrieved in the text format".into(),
+            )));
+        }
+        let value = match self.data.get(idx) {
+            Some(value) => value,
+            None => return Some(Ok(None)),
+        };
+        match str::from_utf8(value) {
+            Ok(value) => Some(Ok(Some(value))),
+            Err(e) => Some(Err(Error::from_sql(Box::new(e)))),
         }
-        let value = FromSql::from_sql_nullable(ty, self.data.get(idx));
-        Some(value.map_err(error::conversion))
     }
 }
 
@@ -238,6 +320,7 @@ impl<'a> Row<'a> {
 pub struct LazyRows<'trans, 'stmt> {
     stmt: &'stmt Statement<'stmt>,
     data: VecDeque<RowData>,
+    notices: Vec<DbError>,
     name: String,
     row_limit: i32,
     more_rows: bool,
@@ -259,6 +342,7 @@ impl<'a, 'b> fmt::Debug for LazyRows<'a, 'b> {
             .field("name", &self.name)
             .field("row_limit", &self.row_limit)
             .field("remaining_rows", &self.data.len())
+            .field("notices", &self.notices.len())
             .field("more_rows", &self.more_rows)
             .finish()
     }
@@ -268,6 +352,7 @@ impl<'trans, 'stmt> LazyRows<'trans, 'stmt> {
     pub(crate) fn new(
         stmt: &'stmt Statement<'stmt>,
         data: VecDeque<RowData>,
+        notices: Vec<DbError>,
         name: String,
         row_limit: i32,
         more_rows: bool,
@@ -277,6 +362,7 @@ impl<'trans, 'stmt> LazyRows<'trans, 'stmt> {
         LazyRows {
             stmt: stmt,
             data: data,
+            notices: notices,
             name: name,
             row_limit: row_limit,
             more_rows: more_rows,
@@ -299,8 +385,10 @@ impl<'trans, 'stmt> LazyRows<'trans, 'stmt> {
         conn.stream
             .write_message(|buf| Ok::<(), io::Error>(frontend::sync(buf)))?;
         conn.stream.flush()?;
-        conn.read_rows(|row| self.data.push_back(row))
-            .map(|more_rows| self.more_rows = more_rows)
+        conn.capture_notices();
+        self.more_rows = conn.read_rows(|row| self.data.push_back(row))?;
+        self.notices.extend(conn.take_notices());
+        Ok(())
     }
 
     /// Returns a slice describing the columns of the `LazyRows`.
@@ -308,6 +396,14 @@ impl<'trans, 'stmt> LazyRows<'trans, 'stmt> {
         self.stmt.columns()
     }
 
+    /// Returns the notices the server has raised while producing the rows fetched so far.
+    ///
+    /// Each batch of rows is fetched separately, so notices raised by later batches are added as
+    /// the iterator advances. The notices are also passed to the connection's notice handler.
+    pub fn notices(&self) -> &[DbError] {
+        &self.notices
+    }
+
     /// Consumes the `LazyRows`, cleaning up associated state.
     ///
     /// Functionally identical to the `Drop` implementation on `LazyRows`
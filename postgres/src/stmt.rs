@@ -8,14 +8,15 @@ use std::io::{self, Read, Write};
 use std::sync::Arc;
 use postgres_protocol::message::{backend, frontend};
 use postgres_shared::rows::RowData;
+use postgres_shared::stmt::with_result_formats;
 
 #[doc(inline)]
-pub use postgres_shared::stmt::Column;
+pub use postgres_shared::stmt::{Column, Format};
 
 use types::{Type, ToSql};
 use rows::{Rows, LazyRows};
 use transaction::Transaction;
-use {bad_response, err, Connection, Result, StatementInfo};
+use {bad_response, err, Connection, Result, StatementInfo, WithNotices};
 
 /// A prepared statement.
 pub struct Statement<'conn> {
@@ -64,7 +65,8 @@ impl<'conn> Statement<'conn> {
         check_desync!(self.conn);
         let mut rows = vec![];
         self.inner_query("", 0, params, |row| rows.push(row))?;
-        Ok(Rows::new(&self, rows))
+        let notices = self.conn.0.borrow_mut().take_notices();
+        Ok(Rows::new(&self, rows, notices))
     }
 
     fn finish_inner(&mut self) -> Result<()> {
@@ -97,6 +99,7 @@ impl<'conn> Statement<'conn> {
             row_limit,
             self.param_types(),
             params,
+            self.columns(),
         )?;
 
         conn.read_rows(acceptor)
@@ -112,6 +115,38 @@ impl<'conn> Statement<'conn> {
         &self.info.columns
     }
 
+    /// Sets the formats in which the statement's results are retrieved.
+    ///
+    /// Results are retrieved in the binary format by default. Values of types without a `FromSql`
+    /// implementation can be retrieved in the text format and accessed with `Row::get_text`. A
+    /// single format applies to every column; otherwise one must be provided per column, and an
+    /// error is returned if the number of formats does not match the number of columns.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// # use postgres::{Connection, TlsMode};
+    /// use postgres::stmt::Format;
+    ///
+    /// # let conn = Connection::connect("", TlsMode::None).unwrap();
+    /// let mut stmt = conn.prepare("SELECT id, 'a fat cat'::tsvector FROM foo").unwrap();
+    /// stmt.set_result_formats(&[Format::Binary, Format::Text]).unwrap();
+    /// for row in &stmt.query(&[]).unwrap() {
+    ///     let id: i32 = row.get(0);
+    ///     let document = row.get_text(1).unwrap();
+    ///     println!("{}: {}", id, document);
+    /// }
+    /// ```
+    pub fn set_result_formats(&mut self, formats: &[Format]) -> Result<()> {
+        let columns = with_result_formats(&self.info.columns, formats)?;
+        self.info = Arc::new(StatementInfo {
+            name: self.info.name.clone(),
+            param_types: self.info.param_types.clone(),
+            columns: columns,
+        });
+        Ok(())
+    }
+
     /// Executes the prepared statement, returning the number of rows modified.
     ///
     /// If the statement does not modify any rows (e.g. SELECT), 0 is returned.
@@ -141,6 +176,7 @@ impl<'conn> Statement<'conn> {
             0,
             self.param_types(),
             params,
+            self.columns(),
         )?;
 
         let num;
@@ -193,6 +229,24 @@ impl<'conn> Statement<'conn> {
         Ok(num)
     }
 
+    /// Like `execute`, but also returns the notices the server raised while executing the
+    /// statement, such as the output of `RAISE NOTICE`.
+    ///
+    /// The notices are still passed to the connection's notice handler.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the number of parameters provided does not match the number
+    /// expected.
+    pub fn execute_with_notices(&self, params: &[&ToSql]) -> Result<WithNotices<u64>> {
+        let value = self.execute(params)?;
+        let notices = self.conn.0.borrow_mut().take_notices();
+        Ok(WithNotices {
+            value: value,
+            notices: notices,
+        })
+    }
+
     /// Executes the prepared statement, returning the resulting rows.
     ///
     /// # Panics
@@ -216,7 +270,8 @@ impl<'conn> Statement<'conn> {
         check_desync!(self.conn);
         let mut rows = vec![];
         self.inner_query("", 0, params, |row| rows.push(row))?;
-        Ok(Rows::new(self, rows))
+        let notices = self.conn.0.borrow_mut().take_notices();
+        Ok(Rows::new(self, rows, notices))
     }
 
     /// Executes the prepared statement, returning a lazily loaded iterator
@@ -290,9 +345,11 @@ impl<'conn> Statement<'conn> {
             params,
             |row| rows.push_back(row),
         )?;
+        let notices = self.conn.0.borrow_mut().take_notices();
         Ok(LazyRows::new(
             self,
             rows,
+            notices,
             portal_name,
             row_limit,
             more_rows,
@@ -330,6 +387,7 @@ impl<'conn> Statement<'conn> {
             0,
             self.param_types(),
             params,
+            self.columns(),
         )?;
 
         let (format, column_formats) = match conn.read_message()? {
@@ -450,6 +508,7 @@ impl<'conn> Statement<'conn> {
             0,
             self.param_types(),
             params,
+            self.columns(),
         )?;
 
         let (format, column_formats) = match conn.read_message()? {
@@ -626,24 +685,6 @@ impl<W: Write> WriteWithInfo for W {
     }
 }
 
-/// The format of a portion of COPY query data.
-#[derive(Debug, Copy, Clone, PartialEq, Eq)]
-pub enum Format {
-    /// A text based format.
-    Text,
-    /// A binary format.
-    Binary,
-}
-
-impl Format {
-    fn from_u16(value: u16) -> Format {
-        match value {
-            0 => Format::Text,
-            _ => Format::Binary,
-        }
-    }
-}
-
 fn parse_update_count(tag: &str) -> u64 {
     tag.split(' ').last().unwrap().parse().unwrap_or(0)
 }
@@ -0,0 +1,147 @@
+//! Utilities for working with the `text` and `csv` `COPY` formats.
+//!
+//! # Examples
+//!
+//! ```rust,no_run
+//! # use postgres::{Connection, TlsMode};
+//! use postgres::text_copy::{TextCopyIn, TextCopyOptions, TextCopyOut};
+//!
+//! # let conn = Connection::connect("", TlsMode::None).unwrap();
+//! let options = TextCopyOptions::csv();
+//! let rows = vec![
+//!     vec![Some("1"), Some("john, jr.")],
+//!     vec![Some("2"), None],
+//! ];
+//!
+//! let stmt = conn.prepare("COPY people (id, name) FROM STDIN (FORMAT csv)").unwrap();
+//! stmt.copy_in(&[], &mut TextCopyIn::new(rows.into_iter(), &options)).unwrap();
+//!
+//! let stmt = conn.prepare("COPY people (id, name) TO STDOUT (FORMAT csv)").unwrap();
+//! let mut out = TextCopyOut::new(&options);
+//! stmt.copy_out(&[], &mut out).unwrap();
+//! for row in out.into_rows().unwrap() {
+//!     println!("{:?}", row);
+//! }
+//! ```
+
+use postgres_shared::text_copy::{TextCopyDecoder, TextCopyEncoder};
+use std::cmp;
+use std::io;
+
+#[doc(inline)]
+pub use postgres_shared::text_copy::{TextCopyFormat, TextCopyOptions, TextCopyRow};
+
+use stmt::{CopyInfo, Format, ReadWithInfo, WriteWithInfo};
+use Result;
+
+fn check_format(info: &CopyInfo) -> io::Result<()> {
+    if info.format() == Format::Text {
+        Ok(())
+    } else {
+        Err(io::Error::new(
+            io::ErrorKind::InvalidInput,
+            "COPY is not using a textual format",
+        ))
+    }
+}
+
+/// A `ReadWithInfo` adaptor which encodes rows from an iterator in a textual `COPY` format.
+///
+/// It can be passed to `Statement::copy_in`.
+#[derive(Debug)]
+pub struct TextCopyIn<I> {
+    rows: I,
+    encoder: TextCopyEncoder,
+    buf: Vec<u8>,
+    pos: usize,
+}
+
+impl<I, T> TextCopyIn<I>
+where
+    I: Iterator<Item = Vec<Option<T>>>,
+    T: AsRef<str>,
+{
+    /// Creates a new adaptor over an iterator of rows.
+    pub fn new(rows: I, options: &TextCopyOptions) -> TextCopyIn<I> {
+        TextCopyIn {
+            rows: rows,
+            encoder: TextCopyEncoder::new(options),
+            buf: vec![],
+            pos: 0,
+        }
+    }
+
+    /// Writes a header line containing the specified column names before the rows.
+    ///
+    /// This should only be used if the header option is set, and before the adaptor is read
+    /// from.
+    pub fn set_header(&mut self, names: &[&str]) {
+        self.encoder.encode_header(names, &mut self.buf);
+    }
+}
+
+impl<I, T> ReadWithInfo for TextCopyIn<I>
+where
+    I: Iterator<Item = Vec<Option<T>>>,
+    T: AsRef<str>,
+{
+    fn read_with_info(&mut self, buf: &mut [u8], info: &CopyInfo) -> io::Result<usize> {
+        check_format(info)?;
+
+        while self.pos == self.buf.len() {
+            self.buf.clear();
+            self.pos = 0;
+            match self.rows.next() {
+                Some(row) => self.encoder.encode_row(&row, &mut self.buf)?,
+                None => return Ok(0),
+            }
+        }
+
+        let len = cmp::min(buf.len(), self.buf.len() - self.pos);
+        buf[..len].copy_from_slice(&self.buf[self.pos..self.pos + len]);
+        self.pos += len;
+        Ok(len)
+    }
+}
+
+/// A `WriteWithInfo` adaptor which decodes rows in a textual `COPY` format.
+///
+/// It can be passed to `Statement::copy_out`.
+#[derive(Debug)]
+pub struct TextCopyOut {
+    decoder: TextCopyDecoder,
+    rows: Vec<TextCopyRow>,
+}
+
+impl TextCopyOut {
+    /// Creates a new adaptor.
+    pub fn new(options: &TextCopyOptions) -> TextCopyOut {
+        TextCopyOut {
+            decoder: TextCopyDecoder::new(options),
+            rows: vec![],
+        }
+    }
+
+    /// Consumes the adaptor, returning the decoded rows.
+    ///
+    /// Returns an error if the data ended with an incomplete row.
+    pub fn into_rows(mut self) -> Result<Vec<TextCopyRow>> {
+        self.decoder.end_of_input();
+        while let Some(row) = self.decoder.next_row()? {
+            self.rows.push(row);
+        }
+        Ok(self.rows)
+    }
+}
+
+impl WriteWithInfo for TextCopyOut {
+    fn write_with_info(&mut self, buf: &[u8], info: &CopyInfo) -> io::Result<usize> {
+        check_format(info)?;
+
+        self.decoder.push(buf);
+        while let Some(row) = self.decoder.next_row()? {
+            self.rows.push(row);
+        }
+        Ok(buf.len())
+    }
+}
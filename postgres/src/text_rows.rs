@@ -9,7 +9,7 @@ use std::str;
 pub use postgres_shared::rows::RowIndex;
 
 use stmt::{Column};
-use {Result, error};
+use {Error, Result};
 
 /// The resulting rows of a query.
 pub struct TextRows {
@@ -189,6 +189,6 @@ impl<'a> TextRow<'a> {
         };
 
         self.data.get(idx)
-            .map(|s| str::from_utf8(s).map_err(|e| error::conversion(Box::new(e))))
+            .map(|s| str::from_utf8(s).map_err(|e| Error::from_sql(Box::new(e))))
     }
 }
@@ -2,12 +2,15 @@
 
 use std::cell::Cell;
 use std::fmt;
+use std::io;
 
+use advisory_lock::{self, AdvisoryXactLock};
+use cursor::{self, Cursor};
 use rows::Rows;
 use text_rows::TextRows;
 use stmt::Statement;
 use types::ToSql;
-use {bad_response, Connection, Result};
+use {bad_response, quote_literal, Connection, Result, WithNotices};
 
 /// An enumeration of transaction isolation levels.
 ///
@@ -145,15 +148,17 @@ impl Config {
 pub struct Transaction<'conn> {
     conn: &'conn Connection,
     depth: u32,
+    id: u64,
     savepoint_name: Option<String>,
-    commit: Cell<bool>,
+    // `None` until `set_commit` or `set_rollback` is called
+    commit: Cell<Option<bool>>,
     finished: bool,
 }
 
 impl<'a> fmt::Debug for Transaction<'a> {
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         fmt.debug_struct("Transaction")
-            .field("commit", &self.commit.get())
+            .field("commit", &self.will_commit())
             .field("depth", &self.depth)
             .finish()
     }
@@ -168,12 +173,13 @@ impl<'conn> Drop for Transaction<'conn> {
 }
 
 impl<'conn> Transaction<'conn> {
-    pub(crate) fn new(conn: &'conn Connection, depth: u32) -> Transaction<'conn> {
+    pub(crate) fn new(conn: &'conn Connection, depth: u32, id: u64) -> Transaction<'conn> {
         Transaction {
             conn: conn,
             depth: depth,
+            id: id,
             savepoint_name: None,
-            commit: Cell::new(false),
+            commit: Cell::new(None),
             finished: false,
         }
     }
@@ -186,11 +192,15 @@ impl<'conn> Transaction<'conn> {
         self.depth
     }
 
+    pub(crate) fn id(&self) -> u64 {
+        self.id
+    }
+
     fn finish_inner(&mut self) -> Result<()> {
         let mut conn = self.conn.0.borrow_mut();
         debug_assert!(self.depth == conn.trans_depth);
-        conn.trans_depth -= 1;
-        match (self.commit.get(), &self.savepoint_name) {
+        conn.end_transaction();
+        match (self.will_commit(), &self.savepoint_name) {
             (false, &Some(ref sp)) => conn.quick_query(&format!("ROLLBACK TO {}", sp))?,
             (false, &None) => conn.quick_query("ROLLBACK")?,
             (true, &Some(ref sp)) => conn.quick_query(&format!("RELEASE {}", sp))?,
@@ -220,11 +230,59 @@ impl<'conn> Transaction<'conn> {
         self.conn.execute(query, params)
     }
 
+    /// Like `Connection::execute_with_notices`.
+    pub fn execute_with_notices(&self, query: &str, params: &[&ToSql]) -> Result<WithNotices<u64>> {
+        self.conn.execute_with_notices(query, params)
+    }
+
     /// Like `Connection::query`.
     pub fn query<'a>(&'a self, query: &str, params: &[&ToSql]) -> Result<Rows> {
         self.conn.query(query, params)
     }
 
+    /// Like `Connection::declare_cursor`.
+    pub fn declare_cursor<'a>(&'a self, query: &str, params: &[&ToSql]) -> Result<Cursor<'a>> {
+        self.conn.declare_cursor(query, params)
+    }
+
+    /// Like `Connection::declare_cursor_with`.
+    pub fn declare_cursor_with<'a>(
+        &'a self,
+        query: &str,
+        params: &[&ToSql],
+        config: &cursor::Config,
+    ) -> Result<Cursor<'a>> {
+        self.conn.declare_cursor_with(query, params, config)
+    }
+
+    /// Acquires a transaction-level advisory lock, waiting until it is available.
+    ///
+    /// The key may be an `i64` or an `(i32, i32)` pair. The lock is released when the
+    /// transaction ends; for nested transactions, that is the end of the top-level transaction.
+    pub fn advisory_xact_lock<'a, K>(
+        &'a self,
+        key: K,
+        mode: advisory_lock::Mode,
+    ) -> Result<AdvisoryXactLock<'a>>
+    where
+        K: Into<advisory_lock::Key>,
+    {
+        AdvisoryXactLock::lock(self.conn, key.into(), mode)
+    }
+
+    /// Like `advisory_xact_lock`, but returns `None` rather than waiting if the lock is not
+    /// immediately available.
+    pub fn try_advisory_xact_lock<'a, K>(
+        &'a self,
+        key: K,
+        mode: advisory_lock::Mode,
+    ) -> Result<Option<AdvisoryXactLock<'a>>>
+    where
+        K: Into<advisory_lock::Key>,
+    {
+        AdvisoryXactLock::try_lock(self.conn, key.into(), mode)
+    }
+
     /// Like `Connection::batch_execute`.
     #[deprecated(since="0.15.3", note="please use `simple_query` instead")]
     pub fn batch_execute(&self, query: &str) -> Result<()> {
@@ -261,12 +319,13 @@ impl<'conn> Transaction<'conn> {
             "`savepoint` may only be called on the active transaction"
         );
         conn.quick_query(&format!("SAVEPOINT {}", name))?;
-        conn.trans_depth += 1;
+        let id = conn.begin_transaction();
         Ok(Transaction {
             conn: self.conn,
             depth: self.depth + 1,
+            id: id,
             savepoint_name: Some(name.to_owned()),
-            commit: Cell::new(false),
+            commit: Cell::new(None),
             finished: false,
         })
     }
@@ -291,17 +350,17 @@ impl<'conn> Transaction<'conn> {
 
     /// Determines if the transaction is currently set to commit or roll back.
     pub fn will_commit(&self) -> bool {
-        self.commit.get()
+        self.commit.get().unwrap_or(false)
     }
 
     /// Sets the transaction to commit at its completion.
     pub fn set_commit(&self) {
-        self.commit.set(true);
+        self.commit.set(Some(true));
     }
 
     /// Sets the transaction to roll back at its completion.
     pub fn set_rollback(&self) {
-        self.commit.set(false);
+        self.commit.set(Some(false));
     }
 
     /// A convenience method which consumes and commits a transaction.
@@ -310,6 +369,42 @@ impl<'conn> Transaction<'conn> {
         self.finish()
     }
 
+    /// Consumes the transaction, preparing it for two-phase commit with the
+    /// specified global identifier.
+    ///
+    /// The prepared transaction is no longer associated with this session; it
+    /// must later be completed with `Connection::commit_prepared` or
+    /// `Connection::rollback_prepared`, possibly from another session. The
+    /// server must be configured with a nonzero `max_prepared_transactions`.
+    ///
+    /// If the transaction has failed or has been set to roll back with
+    /// `set_rollback`, it is rolled back rather than prepared and an error is
+    /// returned. An error is also returned, and the savepoint rolled back, if
+    /// this is a nested transaction.
+    pub fn prepare_transaction(mut self, gid: &str) -> Result<()> {
+        if self.savepoint_name.is_some() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "nested transactions cannot be prepared",
+            ).into());
+        }
+        self.finished = true;
+        if self.commit.get() == Some(false) {
+            self.finish_inner()?;
+            return Err(not_prepared().into());
+        }
+
+        let mut conn = self.conn.0.borrow_mut();
+        debug_assert!(self.depth == conn.trans_depth);
+        conn.end_transaction();
+        let tag = conn.quick_command(&format!("PREPARE TRANSACTION {}", quote_literal(gid)))?;
+        if tag == "ROLLBACK" {
+            return Err(not_prepared().into());
+        }
+
+        Ok(())
+    }
+
     /// Consumes the transaction, commiting or rolling it back as appropriate.
     ///
     /// Functionally equivalent to the `Drop` implementation of `Transaction`
@@ -319,3 +414,10 @@ impl<'conn> Transaction<'conn> {
         self.finish_inner()
     }
 }
+
+fn not_prepared() -> io::Error {
+    io::Error::new(
+        io::ErrorKind::Other,
+        "the transaction was rolled back instead of being prepared",
+    )
+}
@@ -6,12 +6,16 @@ extern crate url;
 extern crate postgres_shared;
 
 use fallible_iterator::FallibleIterator;
+use postgres::binary_copy::{BinaryCopyIn, BinaryCopyOut, BinaryCopyReader, BinaryCopyWriter};
 use postgres::error::ErrorPosition::Normal;
-use postgres::error::{DbError, SqlState};
+use postgres::cursor;
+use postgres::error::{self, DbError, SqlState};
+use postgres::large_object::{LargeObject, Mode};
 use postgres::notification::Notification;
 use postgres::params::IntoConnectParams;
+use postgres::text_copy::{TextCopyIn, TextCopyOptions, TextCopyOut};
 use postgres::transaction::{self, IsolationLevel};
-use postgres::types::{Kind, Oid, Type, WrongType};
+use postgres::types::{Kind, Oid, RawValue, ToSql, Type, WrongType};
 use postgres::{Connection, GenericConnection, HandleNotice, TlsMode};
 use std::io;
 use std::thread;
@@ -57,6 +61,86 @@ fn test_prepare_err() {
     }
 }
 
+#[test]
+fn test_render_err() {
+    let conn = or_panic!(Connection::connect(
+        "postgres://postgres@localhost:5433",
+        TlsMode::None,
+    ));
+    let query = "SELECT 1\nFORM foo";
+    let err = conn.prepare(query).unwrap_err();
+    let rendered = err.as_db().unwrap().render(query).to_string();
+    assert!(
+        rendered.contains("LINE 2: FORM foo\n        ^"),
+        "{}",
+        rendered
+    );
+}
+
+#[test]
+fn test_constraint_violations() {
+    let conn = or_panic!(Connection::connect(
+        "postgres://postgres@localhost:5433",
+        TlsMode::None,
+    ));
+    or_panic!(conn.batch_execute(
+        "CREATE TEMPORARY TABLE users (
+            id INT PRIMARY KEY,
+            email TEXT NOT NULL,
+            CONSTRAINT users_email_key UNIQUE (email)
+        );
+        CREATE TEMPORARY TABLE orders (user_id INT REFERENCES users (id));
+        INSERT INTO users (id, email) VALUES (1, 'a@example.com');"
+    ));
+
+    let err = conn
+        .execute(
+            "INSERT INTO users (id, email) VALUES (2, 'a@example.com')",
+            &[],
+        )
+        .unwrap_err();
+    let violation = err.as_unique_violation().unwrap();
+    assert_eq!(violation.constraint, Some("users_email_key"));
+    assert_eq!(violation.table, Some("users"));
+    assert_eq!(violation.columns, ["email"]);
+    assert!(err.as_foreign_key_violation().is_none());
+
+    let err = conn
+        .execute("INSERT INTO orders (user_id) VALUES (2)", &[])
+        .unwrap_err();
+    let violation = err.as_foreign_key_violation().unwrap();
+    assert_eq!(violation.table, Some("orders"));
+    assert_eq!(violation.columns, ["user_id"]);
+
+    let err = conn
+        .execute("INSERT INTO users (id) VALUES (3)", &[])
+        .unwrap_err();
+    let violation = err.as_not_null_violation().unwrap();
+    assert_eq!(violation.columns, ["email"]);
+}
+
+#[test]
+fn test_terminated_connection_is_lost() {
+    let conn = or_panic!(Connection::connect(
+        "postgres://postgres@localhost:5433",
+        TlsMode::None,
+    ));
+    let pid: i32 = or_panic!(conn.query("SELECT pg_backend_pid()", &[]))
+        .get(0)
+        .get(0);
+
+    let killer = or_panic!(Connection::connect(
+        "postgres://postgres@localhost:5433",
+        TlsMode::None,
+    ));
+    or_panic!(killer.execute("SELECT pg_terminate_backend($1)", &[&pid]));
+
+    let err = conn.execute("SELECT 1", &[]).unwrap_err();
+    assert!(err.is_connection_lost(), "{:?}", err);
+    assert!(err.is_transient());
+    assert!(!err.is_retryable());
+}
+
 #[test]
 fn test_unknown_database() {
     match Connection::connect("postgres://postgres@localhost:5433/asdf", TlsMode::None) {
@@ -491,6 +575,359 @@ FROM (SELECT gs.i
     };
 }
 
+#[test]
+fn test_cursor() {
+    let conn = or_panic!(Connection::connect(
+        "postgres://postgres@localhost:5433",
+        TlsMode::None,
+    ));
+    let trans = or_panic!(conn.transaction());
+    let mut config = cursor::Config::new();
+    config.scroll(true);
+    let cursor = or_panic!(trans.declare_cursor_with(
+        "SELECT * FROM generate_series(1, $1) AS id",
+        &[&10i32],
+        &config,
+    ));
+
+    let ids = |rows: postgres::rows::Rows| rows.iter().map(|r| r.get(0)).collect::<Vec<i32>>();
+    assert_eq!(ids(or_panic!(cursor.fetch(3))), vec![1, 2, 3]);
+    assert_eq!(ids(or_panic!(cursor.fetch_backward(2))), vec![2, 1]);
+    assert!(or_panic!(cursor.move_absolute(8)));
+    assert_eq!(ids(or_panic!(cursor.fetch(5))), vec![9, 10]);
+    assert!(or_panic!(cursor.fetch(1)).is_empty());
+    assert!(!or_panic!(cursor.move_absolute(11)));
+    or_panic!(cursor.close());
+}
+
+#[test]
+fn test_cursor_with_hold() {
+    let conn = or_panic!(Connection::connect(
+        "postgres://postgres@localhost:5433",
+        TlsMode::None,
+    ));
+    let mut config = cursor::Config::new();
+    config.hold(true);
+    let cursor = or_panic!(conn.declare_cursor_with(
+        "SELECT * FROM generate_series(1, 5)",
+        &[],
+        &config,
+    ));
+    assert!(conn.is_active());
+
+    assert_eq!(or_panic!(cursor.fetch(2)).len(), 2);
+    assert_eq!(or_panic!(cursor.fetch(5)).len(), 3);
+    let name = cursor.name().to_string();
+    drop(cursor);
+
+    let result = or_panic!(conn.query("SELECT 1 FROM pg_cursors WHERE name = $1", &[&name]));
+    assert!(result.is_empty());
+}
+
+#[test]
+fn test_cursor_outside_transaction() {
+    let conn = or_panic!(Connection::connect(
+        "postgres://postgres@localhost:5433",
+        TlsMode::None,
+    ));
+    assert!(conn.declare_cursor("SELECT 1", &[]).is_err());
+}
+
+#[test]
+fn test_large_object() {
+    use std::io::{Read, Seek, SeekFrom, Write};
+
+    let conn = or_panic!(Connection::connect(
+        "postgres://postgres@localhost:5433",
+        TlsMode::None,
+    ));
+    let trans = or_panic!(conn.transaction());
+    let oid = or_panic!(LargeObject::create(&trans));
+
+    let mut lo = or_panic!(LargeObject::open(&trans, oid, Mode::ReadWrite));
+    or_panic!(lo.write_all(b"hello world"));
+    assert_eq!(or_panic!(lo.seek(SeekFrom::Start(6))), 6);
+    let mut buf = vec![];
+    or_panic!(lo.read_to_end(&mut buf));
+    assert_eq!(buf, b"world");
+    assert_eq!(or_panic!(lo.seek(SeekFrom::End(-11))), 0);
+    or_panic!(lo.truncate(5));
+    assert_eq!(or_panic!(lo.tell()), 0);
+    or_panic!(lo.finish());
+    or_panic!(trans.commit());
+
+    let trans = or_panic!(conn.transaction());
+    let mut lo = or_panic!(LargeObject::open(&trans, oid, Mode::Read));
+    let mut buf = vec![];
+    or_panic!(lo.read_to_end(&mut buf));
+    assert_eq!(buf, b"hello");
+    assert!(lo.write_all(b"nope").is_err());
+    drop(lo);
+    drop(trans);
+
+    or_panic!(LargeObject::unlink(&conn, oid));
+    let trans = or_panic!(conn.transaction());
+    assert!(LargeObject::open(&trans, oid, Mode::Read).is_err());
+}
+
+#[test]
+fn test_large_object_after_transaction() {
+    use std::io::Read;
+
+    let conn = or_panic!(Connection::connect(
+        "postgres://postgres@localhost:5433",
+        TlsMode::None,
+    ));
+    let trans = or_panic!(conn.transaction());
+    let oid = or_panic!(LargeObject::create(&trans));
+    let mut lo = or_panic!(LargeObject::open(&trans, oid, Mode::Read));
+    or_panic!(trans.finish());
+
+    let mut buf = [0; 10];
+    assert!(lo.read(&mut buf).is_err());
+
+    // a later transaction at the same depth doesn't revive the descriptor
+    let _trans = or_panic!(conn.transaction());
+    assert!(lo.read(&mut buf).is_err());
+}
+
+#[test]
+fn test_large_object_seek_out_of_range() {
+    use std::io::{ErrorKind, Seek, SeekFrom};
+
+    let conn = or_panic!(Connection::connect(
+        "postgres://postgres@localhost:5433",
+        TlsMode::None,
+    ));
+    let trans = or_panic!(conn.transaction());
+    let oid = or_panic!(LargeObject::create(&trans));
+    let mut lo = or_panic!(LargeObject::open(&trans, oid, Mode::Read));
+    let err = lo.seek(SeekFrom::Start(u64::max_value())).unwrap_err();
+    assert_eq!(err.kind(), ErrorKind::InvalidInput);
+}
+
+#[test]
+fn test_prepare_transaction() {
+    let conn = or_panic!(Connection::connect(
+        "postgres://postgres@localhost:5433",
+        TlsMode::None,
+    ));
+    // temporary tables can't be used in prepared transactions
+    or_panic!(conn.batch_execute(
+        "DROP TABLE IF EXISTS prepare_transaction_test;
+         CREATE TABLE prepare_transaction_test (id INT)"
+    ));
+
+    let trans = or_panic!(conn.transaction());
+    or_panic!(trans.execute("INSERT INTO prepare_transaction_test (id) VALUES (1)", &[]));
+    or_panic!(trans.prepare_transaction("rust-postgres-commit"));
+
+    let trans = or_panic!(conn.transaction());
+    or_panic!(trans.execute("INSERT INTO prepare_transaction_test (id) VALUES (2)", &[]));
+    or_panic!(trans.prepare_transaction("rust-postgres-rollback"));
+
+    let prepared = or_panic!(conn.list_prepared());
+    let gids = prepared.iter().map(|p| &*p.gid).collect::<Vec<_>>();
+    assert!(gids.contains(&"rust-postgres-commit"));
+    assert!(gids.contains(&"rust-postgres-rollback"));
+
+    let rows = or_panic!(conn.query("SELECT id FROM prepare_transaction_test", &[]));
+    assert!(rows.is_empty());
+
+    or_panic!(conn.commit_prepared("rust-postgres-commit"));
+    or_panic!(conn.rollback_prepared("rust-postgres-rollback"));
+
+    let rows = or_panic!(conn.query("SELECT id FROM prepare_transaction_test", &[]));
+    assert_eq!(rows.iter().map(|r| r.get(0)).collect::<Vec<i32>>(), vec![1]);
+    or_panic!(conn.batch_execute("DROP TABLE prepare_transaction_test"));
+}
+
+#[test]
+fn test_prepare_transaction_escaping() {
+    let conn = or_panic!(Connection::connect(
+        "postgres://postgres@localhost:5433",
+        TlsMode::None,
+    ));
+    or_panic!(conn.batch_execute("SET standard_conforming_strings = off"));
+
+    let gid = "rust-postgres-'escaping\\'";
+    let trans = or_panic!(conn.transaction());
+    or_panic!(trans.prepare_transaction(gid));
+
+    let prepared = or_panic!(conn.list_prepared());
+    assert!(prepared.iter().any(|p| p.gid == gid));
+    or_panic!(conn.rollback_prepared(gid));
+}
+
+#[test]
+fn test_prepare_transaction_not_prepared() {
+    let conn = or_panic!(Connection::connect(
+        "postgres://postgres@localhost:5433",
+        TlsMode::None,
+    ));
+
+    let trans = or_panic!(conn.transaction());
+    trans.set_rollback();
+    assert!(trans.prepare_transaction("rust-postgres-set-rollback").is_err());
+    assert!(conn.is_active());
+
+    let trans = or_panic!(conn.transaction());
+    let savepoint = or_panic!(trans.savepoint("sp"));
+    assert!(savepoint.prepare_transaction("rust-postgres-nested").is_err());
+    assert!(trans.is_active());
+    drop(trans);
+
+    let prepared = or_panic!(conn.list_prepared());
+    assert!(!prepared
+        .iter()
+        .any(|p| p.gid == "rust-postgres-set-rollback" || p.gid == "rust-postgres-nested"));
+}
+
+#[test]
+fn test_advisory_lock() {
+    use postgres::advisory_lock::Mode;
+
+    let conn = or_panic!(Connection::connect(
+        "postgres://postgres@localhost:5433",
+        TlsMode::None,
+    ));
+    let conn2 = or_panic!(Connection::connect(
+        "postgres://postgres@localhost:5433",
+        TlsMode::None,
+    ));
+
+    let lock = or_panic!(conn.advisory_lock(7_340_001i64, Mode::Exclusive));
+    assert!(or_panic!(conn2.try_advisory_lock(7_340_001i64, Mode::Shared)).is_none());
+    // the pair key space is distinct from the single key space
+    let lock2 = or_panic!(conn2.try_advisory_lock((0, 7_340_001), Mode::Exclusive));
+    assert!(lock2.is_some());
+    drop(lock);
+
+    let shared = or_panic!(conn2.try_advisory_lock(7_340_001i64, Mode::Shared)).unwrap();
+    let shared2 = or_panic!(conn.try_advisory_lock(7_340_001i64, Mode::Shared)).unwrap();
+    or_panic!(shared.unlock());
+    or_panic!(shared2.unlock());
+}
+
+#[test]
+fn test_advisory_xact_lock() {
+    use postgres::advisory_lock::Mode;
+
+    let conn = or_panic!(Connection::connect(
+        "postgres://postgres@localhost:5433",
+        TlsMode::None,
+    ));
+    let conn2 = or_panic!(Connection::connect(
+        "postgres://postgres@localhost:5433",
+        TlsMode::None,
+    ));
+
+    let trans = or_panic!(conn.transaction());
+    let lock = or_panic!(trans.advisory_xact_lock((1, 7_340_002), Mode::Exclusive));
+    assert_eq!(lock.key(), postgres::advisory_lock::Key::Pair(1, 7_340_002));
+    assert!(or_panic!(conn2.try_advisory_lock((1, 7_340_002), Mode::Exclusive)).is_none());
+    drop(lock);
+    or_panic!(trans.commit());
+
+    assert!(or_panic!(conn2.try_advisory_lock((1, 7_340_002), Mode::Exclusive)).is_some());
+}
+
+#[test]
+fn test_text_result_format() {
+    use postgres::stmt::Format;
+
+    let conn = or_panic!(Connection::connect(
+        "postgres://postgres@localhost:5433",
+        TlsMode::None,
+    ));
+    let mut stmt = or_panic!(conn.prepare("SELECT 1::INT, 'a fat cat'::TSVECTOR, NULL::MONEY"));
+    assert!(stmt.set_result_formats(&[Format::Text, Format::Text]).is_err());
+    or_panic!(stmt.set_result_formats(&[Format::Binary, Format::Text, Format::Text]));
+    assert_eq!(stmt.columns()[1].format(), Format::Text);
+
+    let rows = or_panic!(stmt.query(&[]));
+    let row = rows.get(0);
+    assert_eq!(row.get::<_, i32>(0), 1);
+    assert_eq!(row.get_text(1), Some("'a' 'cat' 'fat'"));
+    assert_eq!(row.get::<_, String>(1), "'a' 'cat' 'fat'");
+    assert_eq!(row.get_text(2), None);
+    assert!(row.get_opt::<_, i32>(1).unwrap().is_err());
+    assert!(row.get_text_opt(0).unwrap().is_err());
+    assert_eq!(row.get_text_opt(2).unwrap().unwrap(), None);
+    assert!(row.get_text_opt(3).is_none());
+
+    or_panic!(stmt.set_result_formats(&[Format::Text]));
+    let rows = or_panic!(stmt.query(&[]));
+    assert_eq!(rows.get(0).get_text(0), Some("1"));
+}
+
+#[test]
+fn test_raw_value() {
+    use postgres::stmt::Format;
+
+    let conn = or_panic!(Connection::connect(
+        "postgres://postgres@localhost:5433",
+        TlsMode::None,
+    ));
+    let rows = or_panic!(conn.query("SELECT 1::INT, NULL::TEXT, 'a fat cat'::TSVECTOR", &[]));
+    let row = rows.get(0);
+    assert_eq!(row.get_raw(0), Some(&[0, 0, 0, 1][..]));
+    assert_eq!(row.get_raw(1), None);
+
+    let value: RawValue = row.get(2);
+    assert_eq!(value.type_().name(), "tsvector");
+    let null: Option<RawValue> = row.get(1);
+    assert_eq!(null, None);
+
+    let rows = or_panic!(conn.query("SELECT $1::TSVECTOR::TEXT, $2::TEXT", &[&value, &null]));
+    assert_eq!(rows.get(0).get::<_, String>(0), "'a' 'cat' 'fat'");
+    assert_eq!(rows.get(0).get::<_, Option<String>>(1), None);
+
+    let stmt = or_panic!(conn.prepare("SELECT $1::INT"));
+    assert!(stmt.query(&[&value]).is_err());
+
+    let mut stmt = or_panic!(conn.prepare("SELECT 1::INT"));
+    or_panic!(stmt.set_result_formats(&[Format::Text]));
+    let rows = or_panic!(stmt.query(&[]));
+    let value: RawValue = rows.get(0).get(0);
+    assert_eq!(value.type_(), &Type::INT4);
+    assert_eq!(value.format(), Format::Text);
+    assert_eq!(value.as_bytes(), b"1");
+}
+
+#[test]
+fn test_column_metadata() {
+    let conn = or_panic!(Connection::connect(
+        "postgres://postgres@localhost:5433",
+        TlsMode::None,
+    ));
+    or_panic!(conn.batch_execute(
+        "CREATE TEMPORARY TABLE foo (
+            id INT,
+            name VARCHAR(10),
+            price NUMERIC(10, 2),
+            created TIMESTAMPTZ(3)
+        )"
+    ));
+    let oid: Oid = or_panic!(conn.query("SELECT 'foo'::regclass::oid", &[]))
+        .get(0)
+        .get(0);
+
+    let stmt = or_panic!(conn.prepare("SELECT id, name, price, created, 1 FROM foo"));
+    let columns = stmt.columns();
+    assert_eq!(columns[0].table_oid(), Some(oid));
+    assert_eq!(columns[0].column_id(), Some(1));
+    assert_eq!(columns[0].type_size(), 4);
+    assert_eq!(columns[1].column_id(), Some(2));
+    assert_eq!(columns[1].character_maximum_length(), Some(10));
+    assert_eq!(columns[2].numeric_precision(), Some(10));
+    assert_eq!(columns[2].numeric_scale(), Some(2));
+    assert_eq!(columns[3].datetime_precision(), Some(3));
+    assert_eq!(columns[4].table_oid(), None);
+    assert_eq!(columns[4].column_id(), None);
+    assert_eq!(columns[4].type_modifier(), -1);
+}
+
 #[test]
 fn test_lazy_query() {
     let conn = or_panic!(Connection::connect(
@@ -726,6 +1163,44 @@ fn test_custom_notice_handler() {
     assert_eq!(unsafe { COUNT }, 1);
 }
 
+#[test]
+fn test_query_notices() {
+    let conn = or_panic!(Connection::connect(
+        "postgres://postgres@localhost:5433",
+        TlsMode::None,
+    ));
+    or_panic!(conn.execute(
+        "CREATE FUNCTION pg_temp.note(msg TEXT) RETURNS INT AS $$
+                           BEGIN
+                            RAISE NOTICE '%', msg;
+                            RETURN 1;
+                           END; $$ LANGUAGE plpgsql",
+        &[],
+    ));
+
+    let rows = or_panic!(conn.query("SELECT pg_temp.note('a'), pg_temp.note('b')", &[]));
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows.notices().len(), 2);
+    assert_eq!(rows.notices()[0].message, "a");
+    assert_eq!(rows.notices()[1].message, "b");
+
+    let result = or_panic!(conn.execute_with_notices("SELECT pg_temp.note($1)", &[&"c"]));
+    assert_eq!(result.value, 1);
+    assert_eq!(result.notices.len(), 1);
+    assert_eq!(result.notices[0].message, "c");
+
+    let rows = or_panic!(conn.query("SELECT 1", &[]));
+    assert!(rows.notices().is_empty());
+
+    let trans = or_panic!(conn.transaction());
+    let stmt = or_panic!(conn.prepare("SELECT pg_temp.note(x::TEXT) FROM generate_series(1, 3) x"));
+    let mut rows = or_panic!(stmt.lazy_query(&trans, &[], 1));
+    assert_eq!(rows.notices().len(), 1);
+    while let Some(_) = or_panic!(rows.next()) {}
+    assert_eq!(rows.notices().len(), 3);
+    assert_eq!(rows.notices()[2].message, "3");
+}
+
 #[test]
 fn test_notification_iterator_none() {
     let conn = or_panic!(Connection::connect(
@@ -789,6 +1264,51 @@ fn test_notification_iterator_some() {
     assert!(it.next().unwrap().is_none());
 }
 
+#[test]
+fn test_notification_poll_ready() {
+    let conn = or_panic!(Connection::connect(
+        "postgres://postgres@localhost:5433",
+        TlsMode::None,
+    ));
+    let notifications = conn.notifications();
+    assert!(!or_panic!(notifications.poll_ready()));
+
+    or_panic!(conn.execute("LISTEN test_notification_poll_ready", &[]));
+    let _t = thread::spawn(|| {
+        let conn = or_panic!(Connection::connect(
+            "postgres://postgres@localhost:5433",
+            TlsMode::None,
+        ));
+        or_panic!(conn.execute("NOTIFY test_notification_poll_ready, 'foo'", &[]));
+    }).join();
+
+    while !or_panic!(notifications.poll_ready()) {
+        thread::sleep(Duration::from_millis(10));
+    }
+    check_notification(
+        Notification {
+            process_id: 0,
+            channel: "test_notification_poll_ready".to_string(),
+            payload: "foo".to_string(),
+        },
+        or_panic!(notifications.try_next()).unwrap(),
+    );
+    assert!(or_panic!(notifications.try_next()).is_none());
+    assert!(!or_panic!(notifications.poll_ready()));
+}
+
+#[test]
+#[cfg(unix)]
+fn test_notifications_raw_fd() {
+    use std::os::unix::io::AsRawFd;
+
+    let conn = or_panic!(Connection::connect(
+        "postgres://postgres@localhost:5433",
+        TlsMode::None,
+    ));
+    assert!(conn.notifications().as_raw_fd() >= 0);
+}
+
 #[test]
 fn test_notifications_next_block() {
     let conn = or_panic!(Connection::connect(
@@ -903,6 +1423,16 @@ fn test_cancel_query() {
     t.join().unwrap();
 }
 
+#[test]
+fn test_no_user() {
+    let ret = Connection::connect("postgres://localhost:5433/postgres", TlsMode::None);
+    match ret {
+        Err(ref e) if e.kind() == error::Kind::MissingUser => (),
+        Err(err) => panic!("Unexpected error {:?}", err),
+        _ => panic!("Expected error"),
+    }
+}
+
 #[test]
 fn test_plaintext_pass() {
     or_panic!(Connection::connect(
@@ -918,7 +1448,7 @@ fn test_plaintext_pass_no_pass() {
         TlsMode::None,
     );
     match ret {
-        Err(ref e) if e.as_connection().is_some() => (),
+        Err(ref e) if e.kind() == error::Kind::MissingPassword => (),
         Err(err) => panic!("Unexpected error {:?}", err),
         _ => panic!("Expected error"),
     }
@@ -949,7 +1479,7 @@ fn test_md5_pass() {
 fn test_md5_pass_no_pass() {
     let ret = Connection::connect("postgres://md5_user@localhost:5433/postgres", TlsMode::None);
     match ret {
-        Err(ref e) if e.as_connection().is_some() => (),
+        Err(ref e) if e.kind() == error::Kind::MissingPassword => (),
         Err(err) => panic!("Unexpected error {:?}", err),
         _ => panic!("Expected error"),
     }
@@ -983,7 +1513,7 @@ fn test_scram_pass_no_pass() {
         TlsMode::None,
     );
     match ret {
-        Err(ref e) if e.as_connection().is_some() => (),
+        Err(ref e) if e.kind() == error::Kind::MissingPassword => (),
         Err(err) => panic!("Unexpected error {:?}", err),
         _ => panic!("Expected error"),
     }
@@ -1123,6 +1653,119 @@ fn test_copy_out() {
     or_panic!(conn.simple_query("SELECT 1"));
 }
 
+#[test]
+fn test_binary_copy() {
+    let conn = or_panic!(Connection::connect(
+        "postgres://postgres@localhost:5433",
+        TlsMode::None,
+    ));
+    or_panic!(conn.simple_query("CREATE TEMPORARY TABLE foo (id INT, name TEXT)"));
+
+    let types = [Type::INT4, Type::TEXT];
+    let rows: Vec<Vec<&ToSql>> = vec![vec![&1i32, &"jim"], vec![&2i32, &None::<String>]];
+    let mut copy_in = or_panic!(BinaryCopyIn::new(rows.iter(), &types));
+    let stmt = or_panic!(conn.prepare("COPY foo (id, name) FROM STDIN (FORMAT binary)"));
+    assert_eq!(or_panic!(stmt.copy_in(&[], &mut copy_in)), 2);
+
+    let bad_rows: Vec<Vec<&ToSql>> = vec![vec![&3i32]];
+    let mut copy_in = or_panic!(BinaryCopyIn::new(bad_rows.iter(), &types));
+    assert!(stmt.copy_in(&[], &mut copy_in).is_err());
+
+    let mut rows = vec![];
+    {
+        let mut copy_out = BinaryCopyOut::new(&types, |row| {
+            rows.push(row);
+            Ok(())
+        });
+        let stmt = or_panic!(conn.prepare("COPY (SELECT id, name FROM foo ORDER BY id) TO STDOUT (FORMAT binary)"));
+        assert_eq!(or_panic!(stmt.copy_out(&[], &mut copy_out)), 2);
+        or_panic!(copy_out.finish());
+    }
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].get::<i32>(0), 1);
+    assert_eq!(rows[0].get::<Option<String>>(1), Some("jim".to_string()));
+    assert_eq!(rows[1].get::<i32>(0), 2);
+    assert_eq!(rows[1].get::<Option<String>>(1), None);
+
+    // the text format is rejected
+    let mut copy_out = BinaryCopyOut::new(&types, |_| Ok(()));
+    let stmt = or_panic!(conn.prepare("COPY foo (id, name) TO STDOUT"));
+    assert!(stmt.copy_out(&[], &mut copy_out).is_err());
+    or_panic!(conn.simple_query("SELECT 1"));
+}
+
+#[test]
+fn test_binary_copy_writer_reader() {
+    let conn = or_panic!(Connection::connect(
+        "postgres://postgres@localhost:5433",
+        TlsMode::None,
+    ));
+    or_panic!(conn.simple_query("CREATE TEMPORARY TABLE foo (id INT, name TEXT)"));
+
+    let types = [Type::INT4, Type::TEXT];
+    let mut writer = or_panic!(BinaryCopyWriter::new(vec![], &types));
+    or_panic!(writer.write(&[&1i32, &"jim"]));
+    or_panic!(writer.write(&[&2i32, &None::<String>]));
+    assert!(writer.write(&[&3i32]).is_err());
+    let data = or_panic!(writer.finish());
+
+    let stmt = or_panic!(conn.prepare("COPY foo (id, name) FROM STDIN (FORMAT binary)"));
+    assert_eq!(or_panic!(stmt.copy_in(&[], &mut &data[..])), 2);
+
+    let stmt = or_panic!(conn.prepare("COPY (SELECT id, name FROM foo ORDER BY id) TO STDOUT (FORMAT binary)"));
+    let mut data = vec![];
+    assert_eq!(or_panic!(stmt.copy_out(&[], &mut data)), 2);
+
+    let rows = or_panic!(BinaryCopyReader::new(&data[..], &types).collect::<Vec<_>>());
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].get::<i32>(0), 1);
+    assert_eq!(rows[0].get::<Option<String>>(1), Some("jim".to_string()));
+    assert_eq!(rows[1].get::<i32>(0), 2);
+    assert_eq!(rows[1].get::<Option<String>>(1), None);
+}
+
+#[test]
+fn test_text_copy() {
+    let conn = or_panic!(Connection::connect(
+        "postgres://postgres@localhost:5433",
+        TlsMode::None,
+    ));
+    or_panic!(conn.simple_query("CREATE TEMPORARY TABLE foo (id INT, name TEXT)"));
+
+    let mut options = TextCopyOptions::csv();
+    options.header(true);
+    let rows = vec![
+        vec![Some("1"), Some("a, \"b\"\nc")],
+        vec![Some("2"), Some("")],
+        vec![Some("3"), None],
+    ];
+    let mut copy_in = TextCopyIn::new(rows.into_iter(), &options);
+    copy_in.set_header(&["id", "name"]);
+    let stmt = or_panic!(conn.prepare("COPY foo (id, name) FROM STDIN (FORMAT csv, HEADER)"));
+    assert_eq!(or_panic!(stmt.copy_in(&[], &mut copy_in)), 3);
+
+    let result = or_panic!(conn.query("SELECT name FROM foo ORDER BY id", &[]));
+    let names = result.iter().map(|r| r.get(0)).collect::<Vec<Option<String>>>();
+    assert_eq!(
+        names,
+        vec![Some("a, \"b\"\nc".to_string()), Some("".to_string()), None]
+    );
+
+    let options = TextCopyOptions::text();
+    let stmt = or_panic!(conn.prepare("COPY (SELECT id, name FROM foo ORDER BY id) TO STDOUT"));
+    let mut copy_out = TextCopyOut::new(&options);
+    assert_eq!(or_panic!(stmt.copy_out(&[], &mut copy_out)), 3);
+    let rows = or_panic!(copy_out.into_rows());
+    assert_eq!(
+        rows,
+        vec![
+            vec![Some("1".to_string()), Some("a, \"b\"\nc".to_string())],
+            vec![Some("2".to_string()), Some("".to_string())],
+            vec![Some("3".to_string()), None],
+        ]
+    );
+}
+
 #[test]
 fn test_copy_out_error() {
     let conn = or_panic!(Connection::connect(
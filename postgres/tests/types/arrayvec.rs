@@ -0,0 +1,31 @@
+extern crate arrayvec;
+
+use self::arrayvec::ArrayVec;
+use postgres::{Connection, TlsMode};
+use types::test_type;
+
+#[test]
+fn test_arrayvec_params() {
+    let mut full = ArrayVec::<[i32; 2]>::new();
+    full.push(1);
+    full.push(2);
+    test_type(
+        "integer[]",
+        &[
+            (Some(full), "ARRAY[1,2]"),
+            (Some(ArrayVec::<[i32; 2]>::new()), "ARRAY[]"),
+            (None, "NULL"),
+        ],
+    );
+
+    let conn = or_panic!(Connection::connect(
+        "postgres://postgres@localhost:5433",
+        TlsMode::None,
+    ));
+    let rows = or_panic!(conn.query("SELECT ARRAY[1, 2, 3]::integer[]", &[]));
+    assert!(rows
+        .get(0)
+        .get_opt::<_, ArrayVec<[i32; 2]>>(0)
+        .unwrap()
+        .is_err());
+}
@@ -1,6 +1,6 @@
 extern crate chrono;
 
-use self::chrono::{TimeZone, NaiveDate, NaiveTime, NaiveDateTime, DateTime, Utc};
+use self::chrono::{TimeZone, NaiveDate, NaiveTime, NaiveDateTime, DateTime, FixedOffset, Utc};
 use types::test_type;
 
 use postgres::types::{Date, Timestamp};
@@ -150,3 +150,28 @@ fn test_time_params() {
         ],
     );
 }
+
+#[test]
+fn test_timetz_params() {
+    fn time(time: &str) -> NaiveTime {
+        NaiveTime::parse_from_str(time, "%H:%M:%S.%f").unwrap()
+    }
+    test_type(
+        "TIME WITH TIME ZONE",
+        &[
+            (
+                Some((time("11:19:33.100314"), FixedOffset::east(0))),
+                "'11:19:33.100314+00'",
+            ),
+            (
+                Some((time("23:11:45.0"), FixedOffset::east(5 * 3600 + 30 * 60))),
+                "'23:11:45+05:30'",
+            ),
+            (
+                Some((time("00:00:00.0"), FixedOffset::west(8 * 3600))),
+                "'00:00:00-08'",
+            ),
+            (None, "NULL"),
+        ],
+    );
+}
@@ -1,6 +1,6 @@
 extern crate geo;
 
-use self::geo::{Coordinate, LineString, Point, Rect};
+use self::geo::{Coordinate, Line, LineString, Point, Polygon, Rect};
 use types::test_type;
 
 #[test]
@@ -50,3 +50,43 @@ fn test_path_params() {
         ],
     );
 }
+
+#[test]
+fn test_lseg_params() {
+    test_type(
+        "LSEG",
+        &[
+            (
+                Some(Line {
+                    start: Coordinate { x: 0.0, y: 0.0 },
+                    end: Coordinate { x: -3.14, y: 1.618 },
+                }),
+                "lseg '[(0, 0), (-3.14, 1.618)]'",
+            ),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn test_polygon_params() {
+    let points = vec![
+        Coordinate { x: 0.0, y: 0.0 },
+        Coordinate { x: 1.0, y: 0.0 },
+        Coordinate { x: 0.0, y: 1.0 },
+        Coordinate { x: 0.0, y: 0.0 },
+    ];
+    test_type(
+        "POLYGON",
+        &[
+            (
+                Some(Polygon {
+                    exterior: LineString(points),
+                    interiors: vec![],
+                }),
+                "polygon '((0, 0), (1, 0), (0, 1))'",
+            ),
+            (None, "NULL"),
+        ],
+    );
+}
@@ -1,4 +1,4 @@
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap, HashSet, VecDeque};
 use std::error;
 use std::f32;
 use std::f64;
@@ -6,9 +6,14 @@ use std::fmt;
 use std::result;
 use std::time::{Duration, UNIX_EPOCH};
 
-use postgres::types::{FromSql, FromSqlOwned, IsNull, Kind, ToSql, Type, WrongType};
+use postgres::types::{
+    Circle, FromSql, FromSqlOwned, IsNull, Kind, Lenient, Lexeme, Line, LineSegment, Money, PgLsn,
+    Point, Polygon, Position, ToSql, TsQuery, TsVector, Type, Weight, WrongType,
+};
 use postgres::{Connection, TlsMode};
 
+#[cfg(feature = "with-arrayvec-0.4")]
+mod arrayvec;
 #[cfg(feature = "with-bit-vec-0.5")]
 mod bit_vec;
 #[cfg(feature = "with-chrono-0.4")]
@@ -17,8 +22,14 @@ mod chrono;
 mod eui48;
 #[cfg(feature = "with-geo-0.10")]
 mod geo;
+#[cfg(feature = "with-postgis")]
+mod postgis;
 #[cfg(feature = "with-serde_json-1")]
 mod serde_json;
+#[cfg(feature = "with-smallvec-0.6")]
+mod smallvec;
+#[cfg(feature = "with-time-0.3")]
+mod time;
 #[cfg(feature = "with-uuid-0.6")]
 mod uuid;
 
@@ -121,6 +132,191 @@ fn test_oid_params() {
     );
 }
 
+#[test]
+fn test_pg_lsn_params() {
+    test_type(
+        "PG_LSN",
+        &[
+            (Some(PgLsn::from(0x16_B374_D848)), "'16/B374D848'"),
+            (Some(PgLsn::from(0)), "'0/0'"),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn test_pg_lsn_format() {
+    let lsn: PgLsn = "16/B374D848".parse().unwrap();
+    assert_eq!(u64::from(lsn), 0x16_B374_D848);
+    assert_eq!(lsn.to_string(), "16/B374D848");
+    assert_eq!((lsn + 0x10).to_string(), "16/B374D858");
+    assert_eq!(lsn - PgLsn::from(0x16_0000_0000), 0xB374_D848);
+    assert_eq!(
+        lsn.wal_file_name(1, 16 * 1024 * 1024).unwrap(),
+        "0000000100000016000000B3"
+    );
+    assert_eq!(lsn.segment_number(0), None);
+    assert_eq!(lsn.wal_file_name(1, 3 * 1024 * 1024), None);
+    assert!(PgLsn::from(u64::max_value()).checked_add(1).is_none());
+    assert!("16/".parse::<PgLsn>().is_err());
+    assert!("123456789/0".parse::<PgLsn>().is_err());
+}
+
+#[test]
+fn test_money_params() {
+    test_type(
+        "MONEY",
+        &[
+            (Some(Money::from_cents(123456)), "'1234.56'"),
+            (Some(Money::from_cents(-5)), "'-0.05'"),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn test_xml_params() {
+    test_type(
+        "XML",
+        &[
+            (
+                Some("<a href=\"b\">c</a>".to_owned()),
+                "'<a href=\"b\">c</a>'",
+            ),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn test_native_point_params() {
+    test_type(
+        "POINT",
+        &[
+            (Some(Point::new(-3.14, 1.618)), "POINT(-3.14, 1.618)"),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn test_line_params() {
+    test_type(
+        "LINE",
+        &[
+            (Some(Line::new(1.0, -1.0, 2.5)), "line '{1, -1, 2.5}'"),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn test_lseg_params() {
+    test_type(
+        "LSEG",
+        &[
+            (
+                Some(LineSegment::new(
+                    Point::new(0.0, 0.0),
+                    Point::new(-3.14, 1.618),
+                )),
+                "lseg '[(0, 0), (-3.14, 1.618)]'",
+            ),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn test_polygon_params() {
+    test_type(
+        "POLYGON",
+        &[
+            (
+                Some(Polygon::new(vec![
+                    Point::new(0.0, 0.0),
+                    Point::new(1.0, 0.0),
+                    Point::new(0.0, 1.0),
+                ])),
+                "polygon '((0, 0), (1, 0), (0, 1))'",
+            ),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn test_circle_params() {
+    test_type(
+        "CIRCLE",
+        &[
+            (
+                Some(Circle::new(Point::new(1.0, 2.0), 3.5)),
+                "circle '<(1, 2), 3.5>'",
+            ),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn test_tsvector_params() {
+    test_type(
+        "TSVECTOR",
+        &[
+            (
+                Some(TsVector::new(vec![
+                    Lexeme::new(
+                        "fat".to_owned(),
+                        vec![Position::new(2, Weight::A), Position::new(11, Weight::D)],
+                    ),
+                    Lexeme::new("rat".to_owned(), vec![]),
+                ])),
+                "'fat:2A,11 rat'",
+            ),
+            (Some(TsVector::default()), "''"),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn test_tsquery_params() {
+    fn lexeme(value: &str, weights: Vec<Weight>, prefix: bool) -> Box<TsQuery> {
+        Box::new(TsQuery::Lexeme {
+            value: value.to_owned(),
+            weights: weights,
+            prefix: prefix,
+        })
+    }
+
+    test_type(
+        "TSQUERY",
+        &[
+            (
+                Some(TsQuery::And(
+                    lexeme("fat", vec![Weight::A, Weight::B], false),
+                    Box::new(TsQuery::Not(lexeme("rat", vec![], true))),
+                )),
+                "'fat:AB & !rat:*'",
+            ),
+            (
+                Some(TsQuery::Or(
+                    lexeme("a", vec![], false),
+                    Box::new(TsQuery::Phrase(
+                        lexeme("b", vec![], false),
+                        lexeme("c", vec![Weight::D], false),
+                        2,
+                    )),
+                )),
+                "'a | b <2> c:D'",
+            ),
+            (Some(TsQuery::Empty), "''"),
+            (None, "NULL"),
+        ],
+    );
+}
+
 #[test]
 fn test_i64_params() {
     test_type(
@@ -133,6 +329,58 @@ fn test_i64_params() {
     );
 }
 
+#[test]
+fn test_unsigned_params() {
+    test_type(
+        "INT4",
+        &[(Some(0u16), "0"), (Some(65535u16), "65535"), (None, "NULL")],
+    );
+    test_type(
+        "NUMERIC",
+        &[
+            (Some(u64::max_value()), "18446744073709551615"),
+            (Some(0u64), "0"),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn test_i128_params() {
+    test_type(
+        "NUMERIC",
+        &[
+            (
+                Some(i128::min_value()),
+                "-170141183460469231731687303715884105728",
+            ),
+            (Some(-10_000i128), "-10000"),
+            (Some(100_000_000i128), "100000000"),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn test_lenient_int_params() {
+    test_type("INT8", &[(Some(Lenient(1i32)), "1"), (None, "NULL")]);
+    test_type("INT2", &[(Some(Lenient(255u8)), "255")]);
+    test_type("NUMERIC", &[(Some(Lenient(-12i16)), "-12.000")]);
+
+    let conn = or_panic!(Connection::connect(
+        "postgres://postgres@localhost:5433",
+        TlsMode::None,
+    ));
+    let rows = or_panic!(conn.query("SELECT COUNT(*), 300::INT4, 1.5::NUMERIC", &[]));
+    let row = rows.get(0);
+    assert_eq!(row.get::<_, Lenient<i32>>(0), Lenient(1));
+    assert!(row.get_opt::<_, Lenient<u8>>(1).unwrap().is_err());
+    assert!(row.get_opt::<_, Lenient<i64>>(2).unwrap().is_err());
+
+    let stmt = or_panic!(conn.prepare("SELECT $1::INT2"));
+    assert!(stmt.query(&[&Lenient(40_000i32)]).is_err());
+}
+
 #[test]
 fn test_f32_params() {
     test_type(
@@ -318,6 +566,70 @@ fn test_array_params() {
     );
 }
 
+#[test]
+fn test_fixed_array_params() {
+    test_type(
+        "integer[]",
+        &[(Some([1i32, 2i32]), "ARRAY[1,2]"), (None, "NULL")],
+    );
+    test_type("integer[]", &[(Some([0i32; 0]), "ARRAY[]::integer[]")]);
+
+    let mut values = [0i32; 32];
+    for (i, value) in values.iter_mut().enumerate() {
+        *value = i as i32;
+    }
+    test_type(
+        "integer[]",
+        &[(Some(values), "ARRAY(SELECT generate_series(0, 31))")],
+    );
+
+    let conn = or_panic!(Connection::connect(
+        "postgres://postgres@localhost:5433",
+        TlsMode::None,
+    ));
+    let rows = or_panic!(conn.query("SELECT ARRAY[1, 2, 3]::integer[]", &[]));
+    assert!(rows.get(0).get_opt::<_, [i32; 2]>(0).unwrap().is_err());
+}
+
+#[test]
+fn test_array_collection_params() {
+    test_type(
+        "integer[]",
+        &[
+            (Some(vec![1i32, 2i32].into_boxed_slice()), "ARRAY[1,2]"),
+            (None, "NULL"),
+        ],
+    );
+    test_type(
+        "integer[]",
+        &[
+            (Some(VecDeque::from(vec![1i32, 2i32])), "ARRAY[1,2]"),
+            (None, "NULL"),
+        ],
+    );
+    test_type(
+        "integer[]",
+        &[
+            (
+                Some(vec![1i32, 2i32].into_iter().collect::<HashSet<_>>()),
+                "ARRAY[2,1]",
+            ),
+            (None, "NULL"),
+        ],
+    );
+    test_type(
+        "integer[]",
+        &[
+            (
+                Some(vec![1i32, 2i32].into_iter().collect::<BTreeSet<_>>()),
+                "ARRAY[2,1,2]",
+            ),
+            (Some(BTreeSet::new()), "ARRAY[]"),
+            (None, "NULL"),
+        ],
+    );
+}
+
 fn test_nan_param<T>(sql_type: &str)
 where
     T: PartialEq + ToSql + FromSqlOwned,
@@ -0,0 +1,138 @@
+use postgres::types::postgis::{
+    Ewkb, Geometry, GeometryCollection, LineString, MultiPoint, Point, Polygon,
+};
+use types::test_type;
+
+#[test]
+fn test_point_params() {
+    test_type(
+        "geometry",
+        &[
+            (Some(Point::new(1.0, 2.0)), "'POINT(1 2)'"),
+            (Some(Point::new(1.0, 2.0).with_z(3.0)), "'POINT Z (1 2 3)'"),
+            (Some(Point::new(1.0, 2.0).with_m(4.0)), "'POINT M (1 2 4)'"),
+            (
+                Some(Point::new(1.0, 2.0).with_z(3.0).with_m(4.0)),
+                "'POINT ZM (1 2 3 4)'",
+            ),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn test_ewkb_params() {
+    test_type(
+        "geometry",
+        &[
+            (
+                Some(Ewkb::new(Some(4326), Point::new(1.0, 2.0))),
+                "'SRID=4326;POINT(1 2)'",
+            ),
+            (Some(Ewkb::new(None, Point::new(1.0, 2.0))), "'POINT(1 2)'"),
+            (None, "NULL"),
+        ],
+    );
+    test_type(
+        "geography",
+        &[
+            (
+                Some(Ewkb::new(Some(4326), Point::new(1.0, 2.0))),
+                "'SRID=4326;POINT(1 2)'",
+            ),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn test_line_string_params() {
+    test_type(
+        "geometry",
+        &[
+            (
+                Some(LineString::new(vec![
+                    Point::new(0.0, 0.0),
+                    Point::new(1.0, 1.0),
+                ])),
+                "'LINESTRING(0 0, 1 1)'",
+            ),
+            (Some(LineString::new(vec![])), "'LINESTRING EMPTY'"),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn test_polygon_params() {
+    let ring = |points: &[(f64, f64)]| {
+        LineString::new(points.iter().map(|&(x, y)| Point::new(x, y)).collect())
+    };
+    test_type(
+        "geometry",
+        &[
+            (
+                Some(Polygon::new(vec![
+                    ring(&[(0.0, 0.0), (4.0, 0.0), (0.0, 4.0), (0.0, 0.0)]),
+                    ring(&[(1.0, 1.0), (2.0, 1.0), (1.0, 2.0), (1.0, 1.0)]),
+                ])),
+                "'POLYGON((0 0, 4 0, 0 4, 0 0), (1 1, 2 1, 1 2, 1 1))'",
+            ),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn test_geometry_params() {
+    test_type(
+        "geometry",
+        &[
+            (
+                Some(Geometry::MultiPoint(MultiPoint::new(vec![
+                    Point::new(0.0, 0.0),
+                    Point::new(1.0, 2.0),
+                ]))),
+                "'MULTIPOINT(0 0, 1 2)'",
+            ),
+            (
+                Some(Geometry::GeometryCollection(GeometryCollection::new(vec![
+                    Geometry::Point(Point::new(0.0, 0.0)),
+                    Geometry::LineString(LineString::new(vec![
+                        Point::new(0.0, 0.0),
+                        Point::new(1.0, 1.0),
+                    ])),
+                ]))),
+                "'GEOMETRYCOLLECTION(POINT(0 0), LINESTRING(0 0, 1 1))'",
+            ),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[cfg(feature = "with-geo-0.10")]
+#[test]
+fn test_geo_params() {
+    extern crate geo;
+
+    test_type(
+        "geometry",
+        &[
+            (Some(geo::Point::new(1.0, 2.0)), "'POINT(1 2)'"),
+            (None, "NULL"),
+        ],
+    );
+    test_type(
+        "geometry",
+        &[
+            (
+                Some(geo::MultiPoint(vec![
+                    geo::Point::new(0.0, 0.0),
+                    geo::Point::new(1.0, 2.0),
+                ])),
+                "'MULTIPOINT(0 0, 1 2)'",
+            ),
+            (None, "NULL"),
+        ],
+    );
+}
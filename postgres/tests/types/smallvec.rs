@@ -0,0 +1,23 @@
+extern crate smallvec;
+
+use self::smallvec::SmallVec;
+use types::test_type;
+
+#[test]
+fn test_smallvec_params() {
+    test_type(
+        "integer[]",
+        &[
+            (
+                Some(SmallVec::<[i32; 2]>::from_vec(vec![1, 2])),
+                "ARRAY[1,2]",
+            ),
+            (
+                Some(SmallVec::<[i32; 2]>::from_vec(vec![1, 2, 3])),
+                "ARRAY[1,2,3]",
+            ),
+            (Some(SmallVec::<[i32; 2]>::new()), "ARRAY[]"),
+            (None, "NULL"),
+        ],
+    );
+}
@@ -0,0 +1,151 @@
+extern crate time;
+
+use self::time::{Date, Duration, Month, PrimitiveDateTime, Time, UtcOffset};
+use types::test_type;
+
+use postgres::types::Timestamp;
+
+fn date(year: i32, month: Month, day: u8) -> Date {
+    Date::from_calendar_date(year, month, day).unwrap()
+}
+
+fn time(hour: u8, minute: u8, second: u8, microsecond: u32) -> Time {
+    Time::from_hms_micro(hour, minute, second, microsecond).unwrap()
+}
+
+#[test]
+fn test_primitive_date_time_params() {
+    test_type(
+        "TIMESTAMP",
+        &[
+            (
+                Some(PrimitiveDateTime::new(
+                    date(1970, Month::January, 1),
+                    time(0, 0, 0, 10_000),
+                )),
+                "'1970-01-01 00:00:00.01'",
+            ),
+            (
+                Some(PrimitiveDateTime::new(
+                    date(1965, Month::September, 25),
+                    time(11, 19, 33, 100_314),
+                )),
+                "'1965-09-25 11:19:33.100314'",
+            ),
+            (
+                Some(PrimitiveDateTime::new(
+                    date(2010, Month::February, 9),
+                    time(23, 11, 45, 120_200),
+                )),
+                "'2010-02-09 23:11:45.1202'",
+            ),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn test_with_special_primitive_date_time_params() {
+    test_type(
+        "TIMESTAMP",
+        &[
+            (
+                Timestamp::Value(PrimitiveDateTime::new(
+                    date(1965, Month::September, 25),
+                    time(11, 19, 33, 100_314),
+                )),
+                "'1965-09-25 11:19:33.100314'",
+            ),
+            (Timestamp::PosInfinity, "'infinity'"),
+            (Timestamp::NegInfinity, "'-infinity'"),
+        ],
+    );
+}
+
+#[test]
+fn test_offset_date_time_params() {
+    test_type(
+        "TIMESTAMP WITH TIME ZONE",
+        &[
+            (
+                Some(
+                    PrimitiveDateTime::new(date(1970, Month::January, 1), time(0, 0, 0, 10_000))
+                        .assume_utc(),
+                ),
+                "'1970-01-01 00:00:00.01+00'",
+            ),
+            (
+                Some(
+                    PrimitiveDateTime::new(
+                        date(2010, Month::February, 9),
+                        time(23, 11, 45, 120_200),
+                    )
+                    .assume_utc(),
+                ),
+                "'2010-02-09 23:11:45.1202+00'",
+            ),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn test_date_params() {
+    test_type(
+        "DATE",
+        &[
+            (Some(date(1970, Month::January, 1)), "'1970-01-01'"),
+            (Some(date(1965, Month::September, 25)), "'1965-09-25'"),
+            (Some(date(2010, Month::February, 9)), "'2010-02-09'"),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn test_time_params() {
+    test_type(
+        "TIME",
+        &[
+            (Some(time(0, 0, 0, 10_000)), "'00:00:00.01'"),
+            (Some(time(11, 19, 33, 100_314)), "'11:19:33.100314'"),
+            (Some(time(23, 11, 45, 120_200)), "'23:11:45.1202'"),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn test_timetz_params() {
+    test_type(
+        "TIME WITH TIME ZONE",
+        &[
+            (
+                Some((time(11, 19, 33, 100_314), UtcOffset::UTC)),
+                "'11:19:33.100314+00'",
+            ),
+            (
+                Some((time(23, 11, 45, 0), UtcOffset::from_hms(5, 30, 0).unwrap())),
+                "'23:11:45+05:30'",
+            ),
+            (
+                Some((time(0, 0, 0, 0), UtcOffset::from_hms(-8, 0, 0).unwrap())),
+                "'00:00:00-08'",
+            ),
+            (None, "NULL"),
+        ],
+    );
+}
+
+#[test]
+fn test_duration_params() {
+    test_type(
+        "INTERVAL",
+        &[
+            (Some(Duration::microseconds(1_500_000)), "'1.5 seconds'"),
+            (Some(Duration::hours(-36)), "'-36 hours'"),
+            (Some(Duration::ZERO), "'0'"),
+            (None, "NULL"),
+        ],
+    );
+}
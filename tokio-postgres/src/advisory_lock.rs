@@ -0,0 +1,196 @@
+//! Advisory locks.
+use futures::{Async, Future, Poll};
+use std::io;
+
+#[doc(inline)]
+pub use postgres_shared::advisory_lock::{Key, Mode};
+
+use error::Error;
+use postgres_shared::advisory_lock::query;
+use proto;
+
+/// A session-level advisory lock.
+///
+/// The lock is released when the `AdvisoryLock` is dropped, without waiting for the server's
+/// response. Use `AdvisoryLock::unlock` to wait for the lock to be released and detect errors.
+pub struct AdvisoryLock {
+    client: proto::Client,
+    key: Key,
+    mode: Mode,
+    locked: bool,
+}
+
+impl Drop for AdvisoryLock {
+    fn drop(&mut self) {
+        if self.locked {
+            self.client
+                .batch_execute_detached(&query("pg_advisory_unlock", self.key, self.mode));
+        }
+    }
+}
+
+impl AdvisoryLock {
+    fn new(client: proto::Client, key: Key, mode: Mode) -> AdvisoryLock {
+        AdvisoryLock {
+            client,
+            key,
+            mode,
+            // set by the acquiring future once its request has been sent
+            locked: false,
+        }
+    }
+
+    /// Returns the lock's key.
+    pub fn key(&self) -> Key {
+        self.key
+    }
+
+    /// Returns the lock's mode.
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// Consumes the `AdvisoryLock`, releasing it.
+    pub fn unlock(mut self) -> ReleaseAdvisoryLock {
+        self.locked = false;
+        let query = query("pg_advisory_unlock", self.key, self.mode);
+        ReleaseAdvisoryLock(proto::FunctionFuture::new(
+            self.client.clone(),
+            &query,
+            None,
+        ))
+    }
+}
+
+#[must_use = "futures do nothing unless polled"]
+pub struct AcquireAdvisoryLock {
+    future: proto::SimpleQueryFuture,
+    lock: Option<AdvisoryLock>,
+}
+
+impl AcquireAdvisoryLock {
+    pub(crate) fn new(client: proto::Client, key: Key, mode: Mode) -> AcquireAdvisoryLock {
+        let future = client.batch_execute(&query("pg_advisory_lock", key, mode));
+        AcquireAdvisoryLock {
+            future,
+            lock: Some(AdvisoryLock::new(client, key, mode)),
+        }
+    }
+}
+
+impl Future for AcquireAdvisoryLock {
+    type Item = AdvisoryLock;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<AdvisoryLock, Error> {
+        // the request is sent on the first poll, after which the lock may be acquired even if
+        // this future is dropped, so the guard releases it just in case
+        if let Some(ref mut lock) = self.lock {
+            lock.locked = true;
+        }
+
+        match self.future.poll() {
+            Ok(Async::Ready(())) => {
+                let lock = self.lock.take().expect("future polled after completion");
+                Ok(Async::Ready(lock))
+            }
+            Ok(Async::NotReady) => Ok(Async::NotReady),
+            Err(e) => {
+                if let Some(mut lock) = self.lock.take() {
+                    lock.locked = false;
+                }
+                Err(e)
+            }
+        }
+    }
+}
+
+#[must_use = "futures do nothing unless polled"]
+pub struct TryAcquireAdvisoryLock {
+    future: proto::FunctionFuture<bool>,
+    lock: Option<AdvisoryLock>,
+}
+
+impl TryAcquireAdvisoryLock {
+    pub(crate) fn new(client: proto::Client, key: Key, mode: Mode) -> TryAcquireAdvisoryLock {
+        let query = query("pg_try_advisory_lock", key, mode);
+        let future = proto::FunctionFuture::new(client.clone(), &query, None);
+        TryAcquireAdvisoryLock {
+            future,
+            lock: Some(AdvisoryLock::new(client, key, mode)),
+        }
+    }
+}
+
+impl Future for TryAcquireAdvisoryLock {
+    type Item = Option<AdvisoryLock>;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Option<AdvisoryLock>, Error> {
+        let result = self.future.poll();
+        // the statement is prepared before the lock is requested, so only treat the lock as held
+        // once the request itself has been sent
+        if self.future.is_calling() {
+            if let Some(ref mut lock) = self.lock {
+                lock.locked = true;
+            }
+        }
+
+        match result {
+            Ok(Async::Ready(true)) => {
+                let lock = self.lock.take().expect("future polled after completion");
+                Ok(Async::Ready(Some(lock)))
+            }
+            Ok(Async::NotReady) => Ok(Async::NotReady),
+            result => {
+                if let Some(mut lock) = self.lock.take() {
+                    lock.locked = false;
+                }
+                result.map(|_| Async::Ready(None))
+            }
+        }
+    }
+}
+
+#[must_use = "futures do nothing unless polled"]
+pub struct ReleaseAdvisoryLock(proto::FunctionFuture<bool>);
+
+impl Future for ReleaseAdvisoryLock {
+    type Item = ();
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<(), Error> {
+        if try_ready!(self.0.poll()) {
+            Ok(Async::Ready(()))
+        } else {
+            Err(Error::io(io::Error::new(
+                io::ErrorKind::Other,
+                "the advisory lock was not held",
+            )))
+        }
+    }
+}
+
+#[must_use = "futures do nothing unless polled"]
+pub struct AcquireAdvisoryXactLock(pub(crate) proto::SimpleQueryFuture);
+
+impl Future for AcquireAdvisoryXactLock {
+    type Item = ();
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<(), Error> {
+        self.0.poll()
+    }
+}
+
+#[must_use = "futures do nothing unless polled"]
+pub struct TryAcquireAdvisoryXactLock(pub(crate) proto::FunctionFuture<bool>);
+
+impl Future for TryAcquireAdvisoryXactLock {
+    type Item = bool;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<bool, Error> {
+        self.0.poll()
+    }
+}
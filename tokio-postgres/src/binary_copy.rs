@@ -0,0 +1,122 @@
+//! Utilities for working with the binary `COPY` format.
+
+use futures::{Async, Poll, Stream};
+use postgres_shared::binary_copy::{BinaryCopyDecoder, BinaryCopyEncoder};
+use std::error::Error as StdError;
+
+#[doc(inline)]
+pub use postgres_shared::binary_copy::BinaryCopyRow;
+
+use types::{ToSql, Type};
+use Error;
+
+/// A stream adaptor which encodes rows in the binary `COPY` format.
+///
+/// The adaptor yields chunks of data which can be passed to `Client::copy_in`.
+#[must_use = "streams do nothing unless polled"]
+pub struct BinaryCopyWriter<S> {
+    stream: S,
+    encoder: BinaryCopyEncoder,
+    done: bool,
+}
+
+impl<S> BinaryCopyWriter<S>
+where
+    S: Stream<Item = Vec<Box<ToSql + Send>>>,
+    S::Error: Into<Box<StdError + Sync + Send>>,
+{
+    /// Creates a new adaptor for a stream of rows with the specified column types.
+    ///
+    /// Returns an error if there are more columns than the format can represent.
+    pub fn new(stream: S, types: &[Type]) -> Result<BinaryCopyWriter<S>, Error> {
+        let encoder = BinaryCopyEncoder::new(types).map_err(Error::io)?;
+        Ok(BinaryCopyWriter {
+            stream: stream,
+            encoder: encoder,
+            done: false,
+        })
+    }
+}
+
+impl<S> Stream for BinaryCopyWriter<S>
+where
+    S: Stream<Item = Vec<Box<ToSql + Send>>>,
+    S::Error: Into<Box<StdError + Sync + Send>>,
+{
+    type Item = Vec<u8>;
+    type Error = Box<StdError + Sync + Send>;
+
+    fn poll(&mut self) -> Poll<Option<Vec<u8>>, Box<StdError + Sync + Send>> {
+        if self.done {
+            return Ok(Async::Ready(None));
+        }
+
+        let mut buf = vec![];
+        match try_ready!(self.stream.poll().map_err(Into::into)) {
+            Some(row) => {
+                let row = row.iter().map(|v| &**v as &ToSql).collect::<Vec<_>>();
+                self.encoder.encode_row(&row, &mut buf)?;
+            }
+            None => {
+                self.done = true;
+                self.encoder.finish(&mut buf);
+            }
+        }
+
+        Ok(Async::Ready(Some(buf)))
+    }
+}
+
+/// A stream adaptor which decodes rows in the binary `COPY` format.
+///
+/// The adaptor wraps the stream of data returned by `Client::copy_out`.
+#[must_use = "streams do nothing unless polled"]
+pub struct BinaryCopyReader<S> {
+    stream: S,
+    decoder: BinaryCopyDecoder,
+    done: bool,
+}
+
+impl<S> BinaryCopyReader<S>
+where
+    S: Stream<Error = Error>,
+    S::Item: AsRef<[u8]>,
+{
+    /// Creates a new adaptor for a stream of data containing rows with the specified column
+    /// types.
+    pub fn new(stream: S, types: &[Type]) -> BinaryCopyReader<S> {
+        BinaryCopyReader {
+            stream: stream,
+            decoder: BinaryCopyDecoder::new(types),
+            done: false,
+        }
+    }
+}
+
+impl<S> Stream for BinaryCopyReader<S>
+where
+    S: Stream<Error = Error>,
+    S::Item: AsRef<[u8]>,
+{
+    type Item = BinaryCopyRow;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Option<BinaryCopyRow>, Error> {
+        loop {
+            if let Some(row) = self.decoder.next_row().map_err(Error::parse)? {
+                return Ok(Async::Ready(Some(row)));
+            }
+            if self.done {
+                return Ok(Async::Ready(None));
+            }
+
+            match try_ready!(self.stream.poll()) {
+                Some(data) => self.decoder.push(data.as_ref()),
+                None => {
+                    self.done = true;
+                    self.decoder.finish().map_err(Error::parse)?;
+                }
+            }
+        }
+    }
+}
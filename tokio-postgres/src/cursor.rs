@@ -0,0 +1,221 @@
+//! Server-side cursors.
+use futures::{Async, Future, Poll, Stream};
+
+#[doc(inline)]
+pub use postgres_shared::cursor::Config;
+
+use error::Error;
+use proto;
+use types::{ToSql, Type};
+use {next_cursor, next_statement, Row};
+
+/// A prepared statement declaring a server-side cursor.
+///
+/// Every cursor declared with the statement has the same name, so a cursor must be closed before
+/// the statement is used to declare another one in the same session.
+pub struct CursorStatement {
+    statement: proto::Statement,
+    name: String,
+    hold: bool,
+}
+
+impl CursorStatement {
+    /// Returns the name of the cursors declared by the statement.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the expected types of the statement's parameters.
+    pub fn params(&self) -> &[Type] {
+        self.statement.params()
+    }
+}
+
+#[must_use = "futures do nothing unless polled"]
+pub struct PrepareCursor {
+    future: proto::PrepareFuture,
+    name: Option<String>,
+    hold: bool,
+}
+
+impl PrepareCursor {
+    pub(crate) fn new(client: &proto::Client, query: &str, config: &Config) -> PrepareCursor {
+        let name = next_cursor();
+        let future = client.prepare(next_statement(), &config.declare_query(&name, query), &[]);
+        PrepareCursor {
+            future,
+            name: Some(name),
+            hold: config.get_hold(),
+        }
+    }
+}
+
+impl Future for PrepareCursor {
+    type Item = CursorStatement;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<CursorStatement, Error> {
+        let statement = try_ready!(self.future.poll());
+        Ok(Async::Ready(CursorStatement {
+            statement,
+            name: self.name.take().expect("future polled after completion"),
+            hold: self.hold,
+        }))
+    }
+}
+
+/// A server-side cursor.
+///
+/// Cursors which are not holdable only exist until the end of the transaction they were declared
+/// in. Holdable cursors are closed when the `Cursor` is dropped, without waiting for the server's
+/// response.
+pub struct Cursor {
+    client: proto::Client,
+    name: String,
+    hold: bool,
+    open: bool,
+}
+
+impl Drop for Cursor {
+    fn drop(&mut self) {
+        // other cursors end with their transaction, after which a CLOSE would be an error
+        if self.open && self.hold {
+            self.client
+                .batch_execute_detached(&format!("CLOSE {}", self.name));
+        }
+    }
+}
+
+impl Cursor {
+    /// Returns the name of the cursor.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Fetches up to the next `count` rows from the cursor.
+    ///
+    /// Fewer rows are returned once the end of the results is reached.
+    pub fn fetch(&mut self, count: i64) -> FetchCursor {
+        let query = format!("FETCH FORWARD {} FROM {}", count, self.name);
+        FetchCursor(proto::FetchStream::new(self.client.clone(), &query))
+    }
+
+    /// Fetches up to the previous `count` rows from the cursor, in reverse order.
+    ///
+    /// The cursor must be scrollable.
+    pub fn fetch_backward(&mut self, count: i64) -> FetchCursor {
+        let query = format!("FETCH BACKWARD {} FROM {}", count, self.name);
+        FetchCursor(proto::FetchStream::new(self.client.clone(), &query))
+    }
+
+    /// Positions the cursor on the row at the specified 1-based position without fetching it.
+    ///
+    /// Negative positions count from the end of the results, and position 0 is before the first
+    /// row. Moving backwards requires the cursor to be scrollable. Resolves to `true` if the
+    /// cursor is positioned on a row.
+    pub fn move_absolute(&mut self, position: i64) -> MoveCursor {
+        let query = format!("MOVE ABSOLUTE {} IN {}", position, self.name);
+        MoveCursor(proto::MoveFuture::new(self.client.clone(), &query))
+    }
+
+    /// Consumes the cursor, closing it.
+    ///
+    /// Unlike the `Drop` implementation, this closes the cursor even if it is not holdable.
+    pub fn close(mut self) -> CloseCursor {
+        self.open = false;
+        CloseCursor(self.client.batch_execute(&format!("CLOSE {}", self.name)))
+    }
+}
+
+#[must_use = "futures do nothing unless polled"]
+pub struct DeclareCursor {
+    future: proto::ExecuteFuture,
+    cursor: Option<Cursor>,
+}
+
+impl DeclareCursor {
+    pub(crate) fn new(
+        client: &proto::Client,
+        statement: &CursorStatement,
+        params: &[&ToSql],
+    ) -> DeclareCursor {
+        DeclareCursor {
+            future: client.execute(&statement.statement, params),
+            cursor: Some(Cursor {
+                client: client.clone(),
+                name: statement.name.clone(),
+                hold: statement.hold,
+                open: false,
+            }),
+        }
+    }
+}
+
+impl Future for DeclareCursor {
+    type Item = Cursor;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Cursor, Error> {
+        // the declaration is sent on the first poll, after which the cursor may exist even if this
+        // future is dropped before it completes, so a holdable one will be closed just in case
+        if let Some(ref mut cursor) = self.cursor {
+            cursor.open = true;
+        }
+
+        match self.future.poll() {
+            Ok(Async::Ready(_)) => {
+                let cursor = self.cursor.take().expect("future polled after completion");
+                Ok(Async::Ready(cursor))
+            }
+            Ok(Async::NotReady) => Ok(Async::NotReady),
+            Err(e) => {
+                if let Some(mut cursor) = self.cursor.take() {
+                    cursor.open = false;
+                }
+                Err(e)
+            }
+        }
+    }
+}
+
+#[must_use = "streams do nothing unless polled"]
+pub struct FetchCursor(proto::FetchStream);
+
+impl Stream for FetchCursor {
+    type Item = Row;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Option<Row>, Error> {
+        match self.0.poll() {
+            Ok(Async::Ready(Some(row))) => Ok(Async::Ready(Some(Row(row)))),
+            Ok(Async::Ready(None)) => Ok(Async::Ready(None)),
+            Ok(Async::NotReady) => Ok(Async::NotReady),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+#[must_use = "futures do nothing unless polled"]
+pub struct MoveCursor(proto::MoveFuture);
+
+impl Future for MoveCursor {
+    type Item = bool;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<bool, Error> {
+        let count = try_ready!(self.0.poll());
+        Ok(Async::Ready(count != 0))
+    }
+}
+
+#[must_use = "futures do nothing unless polled"]
+pub struct CloseCursor(proto::SimpleQueryFuture);
+
+impl Future for CloseCursor {
+    type Item = ();
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<(), Error> {
+        self.0.poll()
+    }
+}
@@ -0,0 +1,108 @@
+//! Large objects.
+//!
+//! Large object descriptors are only valid inside of the transaction which opened them.
+use futures::{Async, Future, Poll};
+use std::io::{self, Read, SeekFrom, Write};
+use tokio_io::{AsyncRead, AsyncWrite};
+
+#[doc(inline)]
+pub use postgres_shared::large_object::Mode;
+
+use error::Error;
+use proto;
+use types::Oid;
+
+/// An open large object.
+///
+/// The `Read` and `Write` implementations return `WouldBlock` errors while the server's response
+/// is pending, as required by `AsyncRead` and `AsyncWrite`. Only one operation may be in
+/// progress at a time.
+pub struct LargeObject(proto::LargeObject);
+
+impl LargeObject {
+    /// Returns the large object's file descriptor.
+    pub fn fd(&self) -> i32 {
+        self.0.fd()
+    }
+
+    /// Attempts to change the current position within the large object, returning the new
+    /// position.
+    pub fn poll_seek(&mut self, pos: SeekFrom) -> Poll<u64, Error> {
+        self.0.poll_seek(pos)
+    }
+
+    /// Attempts to close the large object.
+    pub fn poll_close(&mut self) -> Poll<(), Error> {
+        self.0.poll_close()
+    }
+}
+
+fn to_io<T>(r: Poll<T, Error>) -> io::Result<T> {
+    match r {
+        Ok(Async::Ready(t)) => Ok(t),
+        Ok(Async::NotReady) => Err(io::ErrorKind::WouldBlock.into()),
+        Err(e) => Err(io::Error::new(io::ErrorKind::Other, e)),
+    }
+}
+
+impl Read for LargeObject {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        to_io(self.0.poll_read(buf))
+    }
+}
+
+impl AsyncRead for LargeObject {}
+
+impl Write for LargeObject {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        to_io(self.0.poll_write(buf))
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+impl AsyncWrite for LargeObject {
+    fn shutdown(&mut self) -> Poll<(), io::Error> {
+        to_io(self.0.poll_close()).map(Async::Ready)
+    }
+}
+
+#[must_use = "futures do nothing unless polled"]
+pub struct CreateLargeObject(pub(crate) proto::FunctionFuture<Oid>);
+
+impl Future for CreateLargeObject {
+    type Item = Oid;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Oid, Error> {
+        self.0.poll()
+    }
+}
+
+#[must_use = "futures do nothing unless polled"]
+pub struct OpenLargeObject(pub(crate) proto::OpenFuture);
+
+impl Future for OpenLargeObject {
+    type Item = LargeObject;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<LargeObject, Error> {
+        let lo = try_ready!(self.0.poll());
+        Ok(Async::Ready(LargeObject(lo)))
+    }
+}
+
+#[must_use = "futures do nothing unless polled"]
+pub struct UnlinkLargeObject(pub(crate) proto::FunctionFuture<i32>);
+
+impl Future for UnlinkLargeObject {
+    type Item = ();
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<(), Error> {
+        try_ready!(self.0.poll());
+        Ok(Async::Ready(()))
+    }
+}
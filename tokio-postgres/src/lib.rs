@@ -2,7 +2,6 @@ extern crate antidote;
 extern crate bytes;
 extern crate fallible_iterator;
 extern crate futures_cpupool;
-extern crate phf;
 extern crate postgres_protocol;
 extern crate postgres_shared;
 extern crate tokio_codec;
@@ -23,26 +22,44 @@ extern crate state_machine_future;
 extern crate tokio_uds;
 
 use bytes::Bytes;
+use futures::sync::mpsc;
 use futures::{Async, Future, Poll, Stream};
+use postgres_shared::quote_literal;
 use postgres_shared::rows::RowIndex;
 use std::error::Error as StdError;
 use std::fmt;
+use std::mem;
 use std::sync::atomic::{AtomicUsize, Ordering};
 
 #[doc(inline)]
-pub use postgres_shared::stmt::Column;
+pub use postgres_shared::stmt::{Column, Format};
 #[doc(inline)]
-pub use postgres_shared::{params, types};
+pub use postgres_shared::{error, params, types};
 #[doc(inline)]
-pub use postgres_shared::{CancelData, Notification};
+pub use postgres_shared::{CancelData, Notification, PreparedTransaction, WithNotices};
 
+use advisory_lock::{
+    AcquireAdvisoryLock, AcquireAdvisoryXactLock, TryAcquireAdvisoryLock,
+    TryAcquireAdvisoryXactLock,
+};
+use cursor::{CursorStatement, DeclareCursor, PrepareCursor};
 use error::{DbError, Error};
+use large_object::{CreateLargeObject, OpenLargeObject, UnlinkLargeObject};
 use params::ConnectParams;
+use replication::{
+    BaseBackup, BaseBackupOptions, CreateReplicationSlot, DropReplicationSlot, IdentifySystem,
+    ReplicationStream, SlotKind,
+};
 use tls::TlsConnect;
-use types::{FromSql, ToSql, Type};
+use types::{FromSql, Oid, PgLsn, ToSql, Type};
 
-pub mod error;
+pub mod advisory_lock;
+pub mod binary_copy;
+pub mod cursor;
+pub mod large_object;
 mod proto;
+pub mod replication;
+pub mod text_copy;
 pub mod tls;
 
 fn next_statement() -> String {
@@ -55,6 +72,21 @@ fn next_portal() -> String {
     format!("p{}", ID.fetch_add(1, Ordering::SeqCst))
 }
 
+fn next_cursor() -> String {
+    static ID: AtomicUsize = AtomicUsize::new(0);
+    format!("c{}", ID.fetch_add(1, Ordering::SeqCst))
+}
+
+fn quote_identifier(s: &str) -> String {
+    format!("\"{}\"", s.replace('"', "\"\""))
+}
+
+// The replication command grammar has no escape strings, and treats
+// backslashes literally.
+fn quote_replication_literal(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
+
 pub enum TlsMode {
     None,
     Prefer(Box<TlsConnect>),
@@ -88,6 +120,45 @@ impl Client {
         Query(self.0.query(&statement.0, params))
     }
 
+    /// Like `execute`, but also collects the notices the server raises while executing the
+    /// statement.
+    ///
+    /// The notices are still reported through the `Connection` as `AsyncMessage::Notice`s.
+    pub fn execute_with_notices(
+        &mut self,
+        statement: &Statement,
+        params: &[&ToSql],
+    ) -> ExecuteWithNotices {
+        let (sender, notices) = mpsc::unbounded();
+        let future = self
+            .0
+            .execute_with_notices(&statement.0, params, Some(sender));
+        ExecuteWithNotices {
+            future: future,
+            notices: notices,
+        }
+    }
+
+    /// Like `query`, but collects the resulting rows along with the notices the server raises
+    /// while executing the statement.
+    ///
+    /// The notices are still reported through the `Connection` as `AsyncMessage::Notice`s.
+    pub fn query_with_notices(
+        &mut self,
+        statement: &Statement,
+        params: &[&ToSql],
+    ) -> QueryWithNotices {
+        let (sender, notices) = mpsc::unbounded();
+        let stream = self
+            .0
+            .query_with_notices(&statement.0, params, Some(sender));
+        QueryWithNotices {
+            stream: stream,
+            rows: vec![],
+            notices: notices,
+        }
+    }
+
     pub fn bind(&mut self, statement: &Statement, params: &[&ToSql]) -> Bind {
         Bind(self.0.bind(&statement.0, next_portal(), params))
     }
@@ -96,6 +167,22 @@ impl Client {
         QueryPortal(self.0.query_portal(&portal.0, max_rows))
     }
 
+    /// Prepares a statement declaring a server-side cursor over the results of a query.
+    ///
+    /// Unless the cursor is holdable, it must be declared inside of a transaction.
+    pub fn prepare_cursor(&mut self, query: &str, config: &cursor::Config) -> PrepareCursor {
+        PrepareCursor::new(&self.0, query, config)
+    }
+
+    /// Declares a server-side cursor with a statement prepared by `prepare_cursor`.
+    pub fn declare_cursor(
+        &mut self,
+        statement: &CursorStatement,
+        params: &[&ToSql],
+    ) -> DeclareCursor {
+        DeclareCursor::new(&self.0, statement, params)
+    }
+
     pub fn copy_in<S>(&mut self, statement: &Statement, params: &[&ToSql], stream: S) -> CopyIn<S>
     where
         S: Stream,
@@ -119,9 +206,244 @@ impl Client {
         Transaction(proto::TransactionFuture::new(self.0.clone(), future))
     }
 
+    /// Like `transaction`, but ends a successful transaction by preparing it for two-phase commit
+    /// with the specified global identifier rather than committing it.
+    ///
+    /// The prepared transaction must later be completed with `commit_prepared` or
+    /// `rollback_prepared`, possibly from another session.
+    pub fn prepare_transaction<T>(&mut self, future: T, gid: &str) -> Transaction<T>
+    where
+        T: Future,
+        T::Error: From<Error>,
+    {
+        Transaction(proto::TransactionFuture::new_prepared(
+            self.0.clone(),
+            future,
+            gid,
+        ))
+    }
+
+    /// Commits a transaction previously prepared for two-phase commit.
+    pub fn commit_prepared(&mut self, gid: &str) -> BatchExecute {
+        BatchExecute(
+            self.0
+                .batch_execute(&format!("COMMIT PREPARED {}", quote_literal(gid))),
+        )
+    }
+
+    /// Rolls back a transaction previously prepared for two-phase commit.
+    pub fn rollback_prepared(&mut self, gid: &str) -> BatchExecute {
+        BatchExecute(
+            self.0
+                .batch_execute(&format!("ROLLBACK PREPARED {}", quote_literal(gid))),
+        )
+    }
+
+    /// Returns the transactions currently prepared for two-phase commit.
+    pub fn list_prepared(&mut self) -> ListPrepared {
+        ListPrepared(proto::ListPreparedFuture::new(self.0.clone()))
+    }
+
     pub fn batch_execute(&mut self, query: &str) -> BatchExecute {
         BatchExecute(self.0.batch_execute(query))
     }
+
+    /// Creates a new, empty large object, returning its OID.
+    pub fn create_large_object(&mut self) -> CreateLargeObject {
+        CreateLargeObject(proto::FunctionFuture::new(
+            self.0.clone(),
+            "SELECT pg_catalog.lo_create(0)",
+            None,
+        ))
+    }
+
+    /// Opens the large object with the specified OID.
+    ///
+    /// This must be called inside of a transaction, and the large object can only be used until
+    /// that transaction ends. The future fails if the most recent response from the server
+    /// indicates that the session is not in a transaction, so the `BEGIN` must have completed
+    /// before it is polled.
+    pub fn open_large_object(&mut self, oid: Oid, mode: large_object::Mode) -> OpenLargeObject {
+        OpenLargeObject(proto::OpenFuture::new(self.0.clone(), oid, mode))
+    }
+
+    /// Deletes the large object with the specified OID.
+    pub fn unlink_large_object(&mut self, oid: Oid) -> UnlinkLargeObject {
+        UnlinkLargeObject(proto::FunctionFuture::new(
+            self.0.clone(),
+            "SELECT pg_catalog.lo_unlink($1)",
+            Some(oid),
+        ))
+    }
+
+    /// Acquires a session-level advisory lock, waiting until it is available.
+    ///
+    /// The key may be an `i64` or an `(i32, i32)` pair. The lock is held until the returned
+    /// `AdvisoryLock` is released or dropped.
+    pub fn advisory_lock<K>(&mut self, key: K, mode: advisory_lock::Mode) -> AcquireAdvisoryLock
+    where
+        K: Into<advisory_lock::Key>,
+    {
+        AcquireAdvisoryLock::new(self.0.clone(), key.into(), mode)
+    }
+
+    /// Like `advisory_lock`, but resolves to `None` rather than waiting if the lock is not
+    /// immediately available.
+    pub fn try_advisory_lock<K>(
+        &mut self,
+        key: K,
+        mode: advisory_lock::Mode,
+    ) -> TryAcquireAdvisoryLock
+    where
+        K: Into<advisory_lock::Key>,
+    {
+        TryAcquireAdvisoryLock::new(self.0.clone(), key.into(), mode)
+    }
+
+    /// Acquires a transaction-level advisory lock, waiting until it is available.
+    ///
+    /// The lock is held until the current transaction ends, so this should be used within a
+    /// future passed to `transaction`.
+    pub fn advisory_xact_lock<K>(
+        &mut self,
+        key: K,
+        mode: advisory_lock::Mode,
+    ) -> AcquireAdvisoryXactLock
+    where
+        K: Into<advisory_lock::Key>,
+    {
+        let query =
+            postgres_shared::advisory_lock::query("pg_advisory_xact_lock", key.into(), mode);
+        AcquireAdvisoryXactLock(self.0.batch_execute(&query))
+    }
+
+    /// Like `advisory_xact_lock`, but resolves to `false` rather than waiting if the lock is not
+    /// immediately available.
+    pub fn try_advisory_xact_lock<K>(
+        &mut self,
+        key: K,
+        mode: advisory_lock::Mode,
+    ) -> TryAcquireAdvisoryXactLock
+    where
+        K: Into<advisory_lock::Key>,
+    {
+        let query =
+            postgres_shared::advisory_lock::query("pg_try_advisory_xact_lock", key.into(), mode);
+        TryAcquireAdvisoryXactLock(proto::FunctionFuture::new(self.0.clone(), &query, None))
+    }
+
+    /// Requests identifying information about the server.
+    ///
+    /// This is only supported on replication connections.
+    pub fn identify_system(&mut self) -> IdentifySystem {
+        IdentifySystem(self.0.replication_command("IDENTIFY_SYSTEM"))
+    }
+
+    /// Creates a replication slot.
+    ///
+    /// Temporary slots are dropped when the connection closes. This is only supported on
+    /// replication connections.
+    pub fn create_replication_slot(
+        &mut self,
+        name: &str,
+        temporary: bool,
+        kind: &SlotKind,
+    ) -> CreateReplicationSlot {
+        let mut command = format!("CREATE_REPLICATION_SLOT {}", quote_identifier(name));
+        if temporary {
+            command.push_str(" TEMPORARY");
+        }
+        match *kind {
+            SlotKind::Physical => command.push_str(" PHYSICAL"),
+            SlotKind::Logical(ref plugin) => {
+                command.push_str(" LOGICAL ");
+                command.push_str(&quote_identifier(plugin));
+            }
+        }
+        CreateReplicationSlot(self.0.replication_command(&command))
+    }
+
+    /// Drops a replication slot.
+    ///
+    /// This is only supported on replication connections.
+    pub fn drop_replication_slot(&mut self, name: &str) -> DropReplicationSlot {
+        let command = format!("DROP_REPLICATION_SLOT {}", quote_identifier(name));
+        DropReplicationSlot(self.0.replication_command(&command))
+    }
+
+    /// Starts streaming changes from a logical replication slot, beginning at `start_lsn`.
+    ///
+    /// The options are passed to the slot's output plugin. For `pgoutput`, these are
+    /// `proto_version` and `publication_names`. This is only supported on logical replication
+    /// connections, which cannot be used for anything else until the stream ends.
+    pub fn start_logical_replication(
+        &mut self,
+        slot: &str,
+        start_lsn: PgLsn,
+        options: &[(&str, &str)],
+    ) -> ReplicationStream {
+        let mut command = format!(
+            "START_REPLICATION SLOT {} LOGICAL {}",
+            quote_identifier(slot),
+            start_lsn
+        );
+        if !options.is_empty() {
+            let options = options
+                .iter()
+                .map(|&(name, value)| {
+                    format!(
+                        "{} {}",
+                        quote_identifier(name),
+                        quote_replication_literal(value)
+                    )
+                })
+                .collect::<Vec<_>>();
+            command.push_str(&format!(" ({})", options.join(", ")));
+        }
+        ReplicationStream(self.0.start_replication(&command, start_lsn.into()))
+    }
+
+    /// Starts streaming WAL from the server, beginning at `start_lsn`.
+    ///
+    /// If a slot is specified, the server retains WAL until it has been reported as flushed. If no
+    /// timeline is specified, the server's current timeline is streamed. This is only supported
+    /// on physical replication connections, which cannot be used for anything else until the
+    /// stream ends.
+    pub fn start_physical_replication(
+        &mut self,
+        slot: Option<&str>,
+        start_lsn: PgLsn,
+        timeline: Option<u32>,
+    ) -> ReplicationStream {
+        let mut command = "START_REPLICATION".to_string();
+        if let Some(slot) = slot {
+            command.push_str(" SLOT ");
+            command.push_str(&quote_identifier(slot));
+        }
+        command.push_str(&format!(" PHYSICAL {}", start_lsn));
+        if let Some(timeline) = timeline {
+            command.push_str(&format!(" TIMELINE {}", timeline));
+        }
+        ReplicationStream(self.0.start_replication(&command, start_lsn.into()))
+    }
+
+    /// Takes a base backup of the server, returning a stream of tar archives.
+    ///
+    /// This is only supported on physical replication connections.
+    pub fn base_backup(&mut self, options: &BaseBackupOptions) -> BaseBackup {
+        BaseBackup(self.0.base_backup(&options.build_command()))
+    }
+
+    /// Issues a `LISTEN` for `channel`, returning a stream of the notifications sent to it.
+    ///
+    /// The connection must be polled as a future (e.g. spawned onto an executor) for notifications
+    /// to be delivered. The stream ends when the connection closes; `Listen::relisten` can be used
+    /// to resubscribe the same stream on a new client after reconnecting.
+    ///
+    /// Dropping the stream issues an `UNLISTEN` if no other stream is listening on the channel.
+    pub fn listen(&mut self, channel: &str) -> Listen {
+        Listen(self.0.listen(channel))
+    }
 }
 
 #[must_use = "futures do nothing unless polled"]
@@ -139,6 +461,15 @@ impl Connection {
     pub fn poll_message(&mut self) -> Poll<Option<AsyncMessage>, Error> {
         self.0.poll_message()
     }
+
+    /// Returns a stream of the asynchronous messages received by the connection.
+    ///
+    /// Messages are forwarded to the stream while the connection is polled as a future, so the
+    /// connection can be spawned onto an executor while notices and notifications are consumed
+    /// elsewhere. Only the most recently created stream receives messages.
+    pub fn messages(&mut self) -> AsyncMessages {
+        AsyncMessages(self.0.messages())
+    }
 }
 
 impl Future for Connection {
@@ -157,6 +488,21 @@ pub enum AsyncMessage {
     __NonExhaustive,
 }
 
+#[must_use = "streams do nothing unless polled"]
+pub struct AsyncMessages(mpsc::UnboundedReceiver<AsyncMessage>);
+
+impl Stream for AsyncMessages {
+    type Item = AsyncMessage;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Option<AsyncMessage>, Error> {
+        match self.0.poll() {
+            Ok(message) => Ok(message),
+            Err(()) => unreachable!("mpsc::Receiver doesn't return errors"),
+        }
+    }
+}
+
 #[must_use = "futures do nothing unless polled"]
 pub struct CancelQuery(proto::CancelFuture);
 
@@ -207,6 +553,16 @@ impl Statement {
     pub fn columns(&self) -> &[Column] {
         self.0.columns()
     }
+
+    /// Sets the formats in which the statement's results are retrieved.
+    ///
+    /// Results are retrieved in the binary format by default. Values of types without a `FromSql`
+    /// implementation can be retrieved in the text format and accessed with `Row::get_text`. A
+    /// single format applies to every column; otherwise one must be provided per column, and an
+    /// error is returned if the number of formats does not match the number of columns.
+    pub fn set_result_formats(&mut self, formats: &[Format]) -> Result<(), Error> {
+        self.0.set_result_formats(formats)
+    }
 }
 
 #[must_use = "futures do nothing unless polled"]
@@ -238,6 +594,57 @@ impl Stream for Query {
     }
 }
 
+fn collect_notices(receiver: &mut mpsc::UnboundedReceiver<DbError>) -> Vec<DbError> {
+    // every notice preceding the statement's completion has already been forwarded, so there's no
+    // need to wait on the rest of the channel
+    let mut notices = vec![];
+    while let Ok(Async::Ready(Some(notice))) = receiver.poll() {
+        notices.push(notice);
+    }
+    notices
+}
+
+#[must_use = "futures do nothing unless polled"]
+pub struct ExecuteWithNotices {
+    future: proto::ExecuteFuture,
+    notices: mpsc::UnboundedReceiver<DbError>,
+}
+
+impl Future for ExecuteWithNotices {
+    type Item = WithNotices<u64>;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<WithNotices<u64>, Error> {
+        let value = try_ready!(self.future.poll());
+        Ok(Async::Ready(WithNotices {
+            value: value,
+            notices: collect_notices(&mut self.notices),
+        }))
+    }
+}
+
+#[must_use = "futures do nothing unless polled"]
+pub struct QueryWithNotices {
+    stream: proto::QueryStream<proto::Statement>,
+    rows: Vec<Row>,
+    notices: mpsc::UnboundedReceiver<DbError>,
+}
+
+impl Future for QueryWithNotices {
+    type Item = WithNotices<Vec<Row>>;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<WithNotices<Vec<Row>>, Error> {
+        while let Some(row) = try_ready!(self.stream.poll()) {
+            self.rows.push(Row(row));
+        }
+        Ok(Async::Ready(WithNotices {
+            value: mem::replace(&mut self.rows, vec![]),
+            notices: collect_notices(&mut self.notices),
+        }))
+    }
+}
+
 #[must_use = "futures do nothing unless polled"]
 pub struct Bind(proto::BindFuture);
 
@@ -331,6 +738,48 @@ impl Row {
     {
         self.0.try_get(idx)
     }
+
+    /// Retrieves the raw bytes of a field of the row, without decoding them.
+    ///
+    /// The bytes are in the format the column was retrieved in, normally the binary format of the
+    /// column's type, which is available from `columns`. Returns `None` if the value is `NULL`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index does not reference a column.
+    pub fn get_raw<I>(&self, idx: I) -> Option<&[u8]>
+    where
+        I: RowIndex + fmt::Debug,
+    {
+        self.0.get_raw(idx)
+    }
+
+    /// Retrieves the server's text representation of a field of the row.
+    ///
+    /// The column must have been retrieved in the text format; see `Statement::set_result_formats`.
+    /// Returns `None` if the value is `NULL`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the index does not reference a column, the column was not retrieved in the text
+    /// format, or the value is not valid UTF-8.
+    pub fn get_text<I>(&self, idx: I) -> Option<&str>
+    where
+        I: RowIndex + fmt::Debug,
+    {
+        self.0.get_text(idx)
+    }
+
+    /// Like `get_text`, but returns an error rather than panicking if the column was not retrieved
+    /// in the text format or the value is not valid UTF-8.
+    ///
+    /// Like `try_get`, `Ok(None)` is returned if the index does not reference a column.
+    pub fn try_get_text<I>(&self, idx: I) -> Result<Option<&str>, Error>
+    where
+        I: RowIndex,
+    {
+        self.0.try_get_text(idx)
+    }
 }
 
 #[must_use = "futures do nothing unless polled"]
@@ -352,6 +801,32 @@ where
     }
 }
 
+#[must_use = "streams do nothing unless polled"]
+pub struct Listen(proto::ListenStream);
+
+impl Listen {
+    pub fn channel(&self) -> &str {
+        self.0.channel()
+    }
+
+    /// Reissues the `LISTEN` on a new client, continuing the stream after a reconnect.
+    ///
+    /// Notifications already received on the old client are still yielded before those received
+    /// on the new one.
+    pub fn relisten(&mut self, client: &mut Client) {
+        self.0.relisten(&client.0)
+    }
+}
+
+impl Stream for Listen {
+    type Item = Notification;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Option<Notification>, Error> {
+        self.0.poll()
+    }
+}
+
 #[must_use = "futures do nothing unless polled"]
 pub struct BatchExecute(proto::SimpleQueryFuture);
 
@@ -363,3 +838,15 @@ impl Future for BatchExecute {
         self.0.poll()
     }
 }
+
+#[must_use = "futures do nothing unless polled"]
+pub struct ListPrepared(proto::ListPreparedFuture);
+
+impl Future for ListPrepared {
+    type Item = Vec<PreparedTransaction>;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Vec<PreparedTransaction>, Error> {
+        self.0.poll()
+    }
+}
@@ -4,8 +4,10 @@ use futures::{AsyncSink, Sink, Stream};
 use postgres_protocol;
 use postgres_protocol::message::backend::Message;
 use postgres_protocol::message::frontend;
+use postgres_shared::stmt::result_format_codes;
 use std::collections::HashMap;
 use std::error::Error as StdError;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Weak};
 
 use proto::bind::BindFuture;
@@ -13,13 +15,16 @@ use proto::connection::{Request, RequestMessages};
 use proto::copy_in::{CopyInFuture, CopyInReceiver, CopyMessage};
 use proto::copy_out::CopyOutStream;
 use proto::execute::ExecuteFuture;
+use proto::large_object::Statements;
+use proto::listen::{ListenStream, Listeners};
 use proto::portal::Portal;
 use proto::prepare::PrepareFuture;
 use proto::query::QueryStream;
+use proto::replication::{BaseBackupStream, ReplicationCommandFuture, ReplicationStream};
 use proto::simple_query::SimpleQueryFuture;
 use proto::statement::Statement;
 use types::{IsNull, Oid, ToSql, Type};
-use Error;
+use {DbError, Error};
 
 pub struct PendingRequest(Result<RequestMessages, Error>);
 
@@ -36,26 +41,36 @@ struct State {
     typeinfo_query: Option<Statement>,
     typeinfo_enum_query: Option<Statement>,
     typeinfo_composite_query: Option<Statement>,
+    large_object_statements: Option<Statements>,
 }
 
 struct Inner {
     state: Mutex<State>,
     sender: mpsc::UnboundedSender<Request>,
+    listeners: Arc<Listeners>,
+    in_transaction: Arc<AtomicBool>,
 }
 
 #[derive(Clone)]
 pub struct Client(Arc<Inner>);
 
 impl Client {
-    pub fn new(sender: mpsc::UnboundedSender<Request>) -> Client {
+    pub fn new(
+        sender: mpsc::UnboundedSender<Request>,
+        listeners: Arc<Listeners>,
+        in_transaction: Arc<AtomicBool>,
+    ) -> Client {
         Client(Arc::new(Inner {
             state: Mutex::new(State {
                 types: HashMap::new(),
                 typeinfo_query: None,
                 typeinfo_enum_query: None,
                 typeinfo_composite_query: None,
+                large_object_statements: None,
             }),
             sender,
+            listeners,
+            in_transaction,
         }))
     }
 
@@ -95,12 +110,43 @@ impl Client {
         self.0.state.lock().typeinfo_composite_query = Some(statement.clone());
     }
 
+    pub fn large_object_statements(&self) -> Option<Statements> {
+        self.0.state.lock().large_object_statements.clone()
+    }
+
+    pub fn set_large_object_statements(&self, statements: &Statements) {
+        self.0.state.lock().large_object_statements = Some(statements.clone());
+    }
+
+    /// Determines if the session was inside of a transaction as of the most recently received
+    /// response.
+    pub fn in_transaction(&self) -> bool {
+        self.0.in_transaction.load(Ordering::SeqCst)
+    }
+
+    pub fn listeners(&self) -> &Listeners {
+        &self.0.listeners
+    }
+
     pub fn send(&self, request: PendingRequest) -> Result<mpsc::Receiver<Message>, Error> {
+        self.send_with_notices(request, None)
+    }
+
+    /// Like `send`, but also forwards the notices received while the request is in progress.
+    pub fn send_with_notices(
+        &self,
+        request: PendingRequest,
+        notices: Option<mpsc::UnboundedSender<DbError>>,
+    ) -> Result<mpsc::Receiver<Message>, Error> {
         let messages = request.0?;
         let (sender, receiver) = mpsc::channel(0);
         self.0
             .sender
-            .unbounded_send(Request { messages, sender })
+            .unbounded_send(Request {
+                messages,
+                sender,
+                notices,
+            })
             .map(|_| receiver)
             .map_err(|_| Error::closed())
     }
@@ -114,6 +160,24 @@ impl Client {
         SimpleQueryFuture::new(self.clone(), pending)
     }
 
+    /// Sends a simple query without waiting for its response.
+    pub fn batch_execute_detached(&self, query: &str) {
+        let mut buf = vec![];
+        if frontend::query(query, &mut buf).is_err() {
+            return;
+        }
+        let (sender, _) = mpsc::channel(0);
+        let _ = self.0.sender.unbounded_send(Request {
+            messages: RequestMessages::Single(buf),
+            sender,
+            notices: None,
+        });
+    }
+
+    pub fn listen(&self, channel: &str) -> ListenStream {
+        ListenStream::new(self, channel)
+    }
+
     pub fn prepare(&self, name: String, query: &str, param_types: &[Type]) -> PrepareFuture {
         let pending = self.pending(|buf| {
             frontend::parse(&name, query, param_types.iter().map(|t| t.oid()), buf)
@@ -127,19 +191,37 @@ impl Client {
     }
 
     pub fn execute(&self, statement: &Statement, params: &[&ToSql]) -> ExecuteFuture {
+        self.execute_with_notices(statement, params, None)
+    }
+
+    pub fn execute_with_notices(
+        &self,
+        statement: &Statement,
+        params: &[&ToSql],
+        notices: Option<mpsc::UnboundedSender<DbError>>,
+    ) -> ExecuteFuture {
         let pending = PendingRequest(
             self.excecute_message(statement, params)
                 .map(RequestMessages::Single),
         );
-        ExecuteFuture::new(self.clone(), pending, statement.clone())
+        ExecuteFuture::new(self.clone(), pending, statement.clone(), notices)
     }
 
     pub fn query(&self, statement: &Statement, params: &[&ToSql]) -> QueryStream<Statement> {
+        self.query_with_notices(statement, params, None)
+    }
+
+    pub fn query_with_notices(
+        &self,
+        statement: &Statement,
+        params: &[&ToSql],
+        notices: Option<mpsc::UnboundedSender<DbError>>,
+    ) -> QueryStream<Statement> {
         let pending = PendingRequest(
             self.excecute_message(statement, params)
                 .map(RequestMessages::Single),
         );
-        QueryStream::new(self.clone(), pending, statement.clone())
+        QueryStream::new(self.clone(), pending, statement.clone(), notices)
     }
 
     pub fn bind(&self, statement: &Statement, name: String, params: &[&ToSql]) -> BindFuture {
@@ -157,7 +239,7 @@ impl Client {
             frontend::sync(buf);
             Ok(())
         });
-        QueryStream::new(self.clone(), pending, portal.clone())
+        QueryStream::new(self.clone(), pending, portal.clone(), None)
     }
 
     pub fn copy_in<S>(&self, statement: &Statement, params: &[&ToSql], stream: S) -> CopyInFuture<S>
@@ -188,6 +270,42 @@ impl Client {
         CopyOutStream::new(self.clone(), pending, statement.clone())
     }
 
+    pub fn replication_command(&self, command: &str) -> ReplicationCommandFuture {
+        let pending = self.pending(|buf| {
+            frontend::query(command, buf).map_err(Error::parse)?;
+            Ok(())
+        });
+
+        ReplicationCommandFuture::new(self.clone(), pending)
+    }
+
+    pub fn base_backup(&self, command: &str) -> BaseBackupStream {
+        let pending = self.pending(|buf| {
+            frontend::query(command, buf).map_err(Error::parse)?;
+            Ok(())
+        });
+
+        BaseBackupStream::new(self.clone(), pending)
+    }
+
+    pub fn start_replication(&self, command: &str, start_lsn: u64) -> ReplicationStream {
+        let (mut sender, receiver) = mpsc::channel(0);
+        let mut buf = vec![];
+        let pending = PendingRequest(frontend::query(command, &mut buf).map_err(Error::parse).map(
+            |()| {
+                match sender.start_send(CopyMessage::Data(buf)) {
+                    Ok(AsyncSink::Ready) => {}
+                    _ => unreachable!("channel should have capacity"),
+                }
+                RequestMessages::CopyIn {
+                    receiver: CopyInReceiver::new_simple(receiver),
+                    pending_message: None,
+                }
+            },
+        ));
+        ReplicationStream::new(self.clone(), pending, sender, start_lsn)
+    }
+
     pub fn close_statement(&self, name: &str) {
         self.close(b'S', name)
     }
@@ -204,6 +322,7 @@ impl Client {
         let _ = self.0.sender.unbounded_send(Request {
             messages: RequestMessages::Single(buf),
             sender,
+            notices: None,
         });
     }
 
@@ -224,7 +343,7 @@ impl Client {
                 Ok(IsNull::Yes) => Ok(postgres_protocol::IsNull::Yes),
                 Err(e) => Err(e),
             },
-            Some(1),
+            result_format_codes(statement.columns()),
             &mut buf,
         );
         match r {
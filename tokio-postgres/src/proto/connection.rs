@@ -4,10 +4,13 @@ use postgres_protocol::message::backend::Message;
 use postgres_protocol::message::frontend;
 use std::collections::{HashMap, VecDeque};
 use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 use tokio_codec::Framed;
 
 use proto::codec::PostgresCodec;
 use proto::copy_in::CopyInReceiver;
+use proto::listen::Listeners;
 use tls::TlsStream;
 use {AsyncMessage, CancelData, Notification};
 use {DbError, Error};
@@ -23,6 +26,12 @@ pub enum RequestMessages {
 pub struct Request {
     pub messages: RequestMessages,
     pub sender: mpsc::Sender<Message>,
+    pub notices: Option<mpsc::UnboundedSender<DbError>>,
+}
+
+struct Response {
+    sender: mpsc::Sender<Message>,
+    notices: Option<mpsc::UnboundedSender<DbError>>,
 }
 
 #[derive(PartialEq, Debug)]
@@ -39,8 +48,11 @@ pub struct Connection {
     receiver: mpsc::UnboundedReceiver<Request>,
     pending_request: Option<RequestMessages>,
     pending_response: Option<Message>,
-    responses: VecDeque<mpsc::Sender<Message>>,
+    responses: VecDeque<Response>,
     state: State,
+    listeners: Arc<Listeners>,
+    in_transaction: Arc<AtomicBool>,
+    messages: Option<mpsc::UnboundedSender<AsyncMessage>>,
 }
 
 impl Connection {
@@ -49,6 +61,8 @@ impl Connection {
         cancel_data: CancelData,
         parameters: HashMap<String, String>,
         receiver: mpsc::UnboundedReceiver<Request>,
+        listeners: Arc<Listeners>,
+        in_transaction: Arc<AtomicBool>,
     ) -> Connection {
         Connection {
             stream,
@@ -59,6 +73,9 @@ impl Connection {
             pending_response: None,
             responses: VecDeque::new(),
             state: State::Active,
+            listeners,
+            in_transaction,
+            messages: None,
         }
     }
 
@@ -70,6 +87,12 @@ impl Connection {
         self.parameters.get(name).map(|s| &**s)
     }
 
+    pub fn messages(&mut self) -> mpsc::UnboundedReceiver<AsyncMessage> {
+        let (sender, receiver) = mpsc::unbounded();
+        self.messages = Some(sender);
+        receiver
+    }
+
     fn poll_response(&mut self) -> Poll<Option<Message>, io::Error> {
         if let Some(message) = self.pending_response.take() {
             trace!("retrying pending response");
@@ -100,6 +123,14 @@ impl Connection {
             let message = match message {
                 Message::NoticeResponse(body) => {
                     let error = DbError::new(&mut body.fields()).map_err(Error::parse)?;
+                    if let Some(&Response {
+                        notices: Some(ref notices),
+                        ..
+                    }) = self.responses.front()
+                    {
+                        // the receiving half may have been dropped, in which case this is a no-op
+                        let _ = notices.unbounded_send(error.clone());
+                    }
                     return Ok(Some(AsyncMessage::Notice(error)));
                 }
                 Message::NotificationResponse(body) => {
@@ -108,6 +139,7 @@ impl Connection {
                         channel: body.channel().map_err(Error::parse)?.to_string(),
                         payload: body.message().map_err(Error::parse)?.to_string(),
                     };
+                    self.listeners.dispatch(&notification);
                     return Ok(Some(AsyncMessage::Notification(notification)));
                 }
                 Message::ParameterStatus(body) => {
@@ -120,8 +152,8 @@ impl Connection {
                 m => m,
             };
 
-            let mut sender = match self.responses.pop_front() {
-                Some(sender) => sender,
+            let mut response = match self.responses.pop_front() {
+                Some(response) => response,
                 None => match message {
                     Message::ErrorResponse(error) => return Err(Error::db(error)),
                     _ => return Err(Error::unexpected_message()),
@@ -129,20 +161,25 @@ impl Connection {
             };
 
             let request_complete = match message {
-                Message::ReadyForQuery(_) => true,
+                Message::ReadyForQuery(ref body) => {
+                    // 'I' is idle, 'T' is in a transaction, and 'E' is in a failed transaction
+                    self.in_transaction
+                        .store(body.status() != b'I', Ordering::SeqCst);
+                    true
+                }
                 _ => false,
             };
 
-            match sender.start_send(message) {
+            match response.sender.start_send(message) {
                 // if the receiver's hung up we still need to page through the rest of the messages
                 // designated to it
                 Ok(AsyncSink::Ready) | Err(_) => {
                     if !request_complete {
-                        self.responses.push_front(sender);
+                        self.responses.push_front(response);
                     }
                 }
                 Ok(AsyncSink::NotReady(message)) => {
-                    self.responses.push_front(sender);
+                    self.responses.push_front(response);
                     self.pending_response = Some(message);
                     trace!("poll_read: waiting on sender");
                     return Ok(None);
@@ -160,7 +197,10 @@ impl Connection {
         match try_ready_receive!(self.receiver.poll()) {
             Some(request) => {
                 trace!("polled new request");
-                self.responses.push_back(request.sender);
+                self.responses.push_back(Response {
+                    sender: request.sender,
+                    notices: request.notices,
+                });
                 Ok(Async::Ready(Some(request.messages)))
             }
             None => Ok(Async::Ready(None)),
@@ -300,7 +340,19 @@ impl Future for Connection {
     type Error = Error;
 
     fn poll(&mut self) -> Poll<(), Error> {
-        while let Some(_) = try_ready!(self.poll_message()) {}
+        while let Some(message) = try_ready!(self.poll_message()) {
+            if let Some(ref sender) = self.messages {
+                // the receiving half may have been dropped, in which case the message is discarded
+                let _ = sender.unbounded_send(message);
+            }
+        }
         Ok(Async::Ready(()))
     }
 }
+
+impl Drop for Connection {
+    fn drop(&mut self) {
+        // terminate any outstanding notification streams along with the connection
+        self.listeners.clear();
+    }
+}
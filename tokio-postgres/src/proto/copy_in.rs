@@ -17,6 +17,7 @@ pub enum CopyMessage {
 
 pub struct CopyInReceiver {
     receiver: mpsc::Receiver<CopyMessage>,
+    sync: bool,
     done: bool,
 }
 
@@ -24,6 +25,17 @@ impl CopyInReceiver {
     pub fn new(receiver: mpsc::Receiver<CopyMessage>) -> CopyInReceiver {
         CopyInReceiver {
             receiver,
+            sync: true,
+            done: false,
+        }
+    }
+
+    // copies started by a simple query must not be followed by a Sync, since the backend would
+    // respond to it with an extra ReadyForQuery
+    pub fn new_simple(receiver: mpsc::Receiver<CopyMessage>) -> CopyInReceiver {
+        CopyInReceiver {
+            receiver,
+            sync: false,
             done: false,
         }
     }
@@ -44,14 +56,18 @@ impl Stream for CopyInReceiver {
                 self.done = true;
                 let mut buf = vec![];
                 frontend::copy_done(&mut buf);
-                frontend::sync(&mut buf);
+                if self.sync {
+                    frontend::sync(&mut buf);
+                }
                 Ok(Async::Ready(Some(buf)))
             }
             Async::Ready(None) => {
                 self.done = true;
                 let mut buf = vec![];
                 frontend::copy_fail("", &mut buf).unwrap();
-                frontend::sync(&mut buf);
+                if self.sync {
+                    frontend::sync(&mut buf);
+                }
                 Ok(Async::Ready(Some(buf)))
             }
             Async::NotReady => Ok(Async::NotReady),
@@ -0,0 +1,90 @@
+use futures::{Future, Poll, Stream};
+
+use next_statement;
+use proto::client::Client;
+use proto::execute::ExecuteFuture;
+use proto::prepare::PrepareFuture;
+use proto::query::QueryStream;
+use proto::row::Row;
+use proto::statement::Statement;
+use Error;
+
+// FETCH and MOVE counts can't be parameters, so each command is prepared separately
+
+enum FetchState {
+    Preparing {
+        future: PrepareFuture,
+        client: Client,
+    },
+    Querying(QueryStream<Statement>),
+}
+
+/// A `FETCH` from a cursor.
+pub struct FetchStream(FetchState);
+
+impl FetchStream {
+    pub fn new(client: Client, query: &str) -> FetchStream {
+        let future = client.prepare(next_statement(), query, &[]);
+        FetchStream(FetchState::Preparing { future, client })
+    }
+}
+
+impl Stream for FetchStream {
+    type Item = Row;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Option<Row>, Error> {
+        loop {
+            let stream = match self.0 {
+                FetchState::Preparing {
+                    ref mut future,
+                    ref client,
+                } => {
+                    let statement = try_ready!(future.poll());
+                    client.query(&statement, &[])
+                }
+                FetchState::Querying(ref mut stream) => return stream.poll(),
+            };
+            self.0 = FetchState::Querying(stream);
+        }
+    }
+}
+
+enum MoveState {
+    Preparing {
+        future: PrepareFuture,
+        client: Client,
+    },
+    Executing(ExecuteFuture),
+}
+
+/// A `MOVE` of a cursor, resolving to the number of rows moved over.
+pub struct MoveFuture(MoveState);
+
+impl MoveFuture {
+    pub fn new(client: Client, query: &str) -> MoveFuture {
+        let future = client.prepare(next_statement(), query, &[]);
+        MoveFuture(MoveState::Preparing { future, client })
+    }
+}
+
+impl Future for MoveFuture {
+    type Item = u64;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<u64, Error> {
+        loop {
+            let future = match self.0 {
+                MoveState::Preparing {
+                    ref mut future,
+                    ref client,
+                } => {
+                    let statement = try_ready!(future.poll());
+                    client.execute(&statement, &[])
+                }
+                MoveState::Executing(ref mut future) => return future.poll(),
+            };
+            self.0 = MoveState::Executing(future);
+        }
+    }
+}
@@ -5,7 +5,7 @@ use state_machine_future::RentToOwn;
 
 use proto::client::{Client, PendingRequest};
 use proto::statement::Statement;
-use Error;
+use {DbError, Error};
 
 #[derive(StateMachineFuture)]
 pub enum Execute {
@@ -14,6 +14,7 @@ pub enum Execute {
         client: Client,
         request: PendingRequest,
         statement: Statement,
+        notices: Option<mpsc::UnboundedSender<DbError>>,
     },
     #[state_machine_future(transitions(Finished))]
     ReadResponse { receiver: mpsc::Receiver<Message> },
@@ -26,7 +27,9 @@ pub enum Execute {
 impl PollExecute for Execute {
     fn poll_start<'a>(state: &'a mut RentToOwn<'a, Start>) -> Poll<AfterStart, Error> {
         let state = state.take();
-        let receiver = state.client.send(state.request)?;
+        let receiver = state
+            .client
+            .send_with_notices(state.request, state.notices)?;
 
         // the statement can drop after this point, since its close will queue up after the execution
         transition!(ReadResponse { receiver })
@@ -62,7 +65,12 @@ impl PollExecute for Execute {
 }
 
 impl ExecuteFuture {
-    pub fn new(client: Client, request: PendingRequest, statement: Statement) -> ExecuteFuture {
-        Execute::start(client, request, statement)
+    pub fn new(
+        client: Client,
+        request: PendingRequest,
+        statement: Statement,
+        notices: Option<mpsc::UnboundedSender<DbError>>,
+    ) -> ExecuteFuture {
+        Execute::start(client, request, statement, notices)
     }
 }
@@ -9,6 +9,8 @@ use postgres_protocol::message::frontend;
 use state_machine_future::RentToOwn;
 use std::collections::HashMap;
 use std::io;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
 use tokio_codec::Framed;
 
 use params::{ConnectParams, User};
@@ -16,6 +18,7 @@ use proto::client::Client;
 use proto::codec::PostgresCodec;
 use proto::connect::ConnectFuture;
 use proto::connection::Connection;
+use proto::listen::Listeners;
 use tls::TlsStream;
 use {CancelData, Error, TlsMode};
 
@@ -87,13 +90,18 @@ impl PollHandshake for Handshake {
             let timezone = Some(("timezone", "GMT"));
             let user = Some(("user", user.name()));
             let database = state.params.database().map(|s| ("database", s));
+            let replication = state
+                .params
+                .replication()
+                .map(|r| ("replication", r.startup_value()));
 
             frontend::startup_message(
                 options
                     .chain(client_encoding)
                     .chain(timezone)
                     .chain(user)
-                    .chain(database),
+                    .chain(database)
+                    .chain(replication),
                 &mut buf,
             ).map_err(Error::encode)?;
         }
@@ -307,9 +315,17 @@ impl PollHandshake for Handshake {
                         ))
                     })?;
                     let (sender, receiver) = mpsc::unbounded();
-                    let client = Client::new(sender);
-                    let connection =
-                        Connection::new(state.stream, cancel_data, state.parameters, receiver);
+                    let listeners = Arc::new(Listeners::new());
+                    let in_transaction = Arc::new(AtomicBool::new(false));
+                    let client = Client::new(sender, listeners.clone(), in_transaction.clone());
+                    let connection = Connection::new(
+                        state.stream,
+                        cancel_data,
+                        state.parameters,
+                        receiver,
+                        listeners,
+                        in_transaction,
+                    );
                     transition!(Finished((client, connection)))
                 }
                 Some(Message::ErrorResponse(body)) => return Err(Error::db(body)),
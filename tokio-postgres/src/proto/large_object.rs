@@ -0,0 +1,379 @@
+use futures::future::{self, JoinAll};
+use futures::{Async, Future, Poll, Stream};
+use state_machine_future::RentToOwn;
+use std::cmp;
+use std::i32;
+use std::i64;
+use std::io::{self, SeekFrom};
+
+use large_object::Mode;
+use next_statement;
+use proto::client::Client;
+use proto::prepare::PrepareFuture;
+use proto::query::QueryStream;
+use proto::statement::Statement;
+use types::{FromSql, Oid, ToSql};
+use Error;
+
+const OPEN: &'static str = "SELECT pg_catalog.lo_open($1, $2)";
+const READ: &'static str = "SELECT pg_catalog.loread($1, $2)";
+const WRITE: &'static str = "SELECT pg_catalog.lowrite($1, $2)";
+const SEEK: &'static str = "SELECT pg_catalog.lo_lseek64($1, $2, $3)";
+const CLOSE: &'static str = "SELECT pg_catalog.lo_close($1)";
+
+/// A query returning a single value.
+pub struct Call<T> {
+    stream: QueryStream<Statement>,
+    value: Option<T>,
+}
+
+impl<T> Call<T>
+where
+    T: for<'a> FromSql<'a>,
+{
+    fn new(client: &Client, statement: &Statement, params: &[&ToSql]) -> Call<T> {
+        Call {
+            stream: client.query(statement, params),
+            value: None,
+        }
+    }
+}
+
+impl<T> Future for Call<T>
+where
+    T: for<'a> FromSql<'a>,
+{
+    type Item = T;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<T, Error> {
+        loop {
+            match try_ready!(self.stream.poll()) {
+                Some(row) => match row.try_get(0)? {
+                    Some(value) => self.value = Some(value),
+                    None => return Err(Error::unexpected_message()),
+                },
+                None => match self.value.take() {
+                    Some(value) => return Ok(Async::Ready(value)),
+                    None => return Err(Error::unexpected_message()),
+                },
+            }
+        }
+    }
+}
+
+enum FunctionState<T> {
+    Preparing {
+        future: PrepareFuture,
+        client: Client,
+        oid: Option<Oid>,
+    },
+    Calling(Call<T>),
+}
+
+/// A call to a large object function taking either no arguments or an OID.
+pub struct FunctionFuture<T>(FunctionState<T>);
+
+impl<T> FunctionFuture<T>
+where
+    T: for<'a> FromSql<'a>,
+{
+    pub fn new(client: Client, query: &str, oid: Option<Oid>) -> FunctionFuture<T> {
+        let future = client.prepare(next_statement(), query, &[]);
+        FunctionFuture(FunctionState::Preparing { future, client, oid })
+    }
+
+    /// Determines if the call itself has been sent, as opposed to the statement's preparation.
+    pub fn is_calling(&self) -> bool {
+        match self.0 {
+            FunctionState::Preparing { .. } => false,
+            FunctionState::Calling(_) => true,
+        }
+    }
+}
+
+impl<T> Future for FunctionFuture<T>
+where
+    T: for<'a> FromSql<'a>,
+{
+    type Item = T;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<T, Error> {
+        loop {
+            let call = match self.0 {
+                FunctionState::Preparing {
+                    ref mut future,
+                    ref client,
+                    ref oid,
+                } => {
+                    let statement = try_ready!(future.poll());
+                    match *oid {
+                        Some(ref oid) => Call::new(client, &statement, &[oid]),
+                        None => Call::new(client, &statement, &[]),
+                    }
+                }
+                FunctionState::Calling(ref mut call) => return call.poll(),
+            };
+            self.0 = FunctionState::Calling(call);
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct Statements {
+    open: Statement,
+    read: Statement,
+    write: Statement,
+    seek: Statement,
+    close: Statement,
+}
+
+#[derive(StateMachineFuture)]
+pub enum Open {
+    #[state_machine_future(start, transitions(Preparing, Opening))]
+    Start {
+        client: Client,
+        oid: Oid,
+        mode: Mode,
+    },
+    #[state_machine_future(transitions(Opening))]
+    Preparing {
+        future: JoinAll<Vec<PrepareFuture>>,
+        client: Client,
+        oid: Oid,
+        mode: Mode,
+    },
+    #[state_machine_future(transitions(Finished))]
+    Opening {
+        future: Call<i32>,
+        client: Client,
+        statements: Statements,
+    },
+    #[state_machine_future(ready)]
+    Finished(LargeObject),
+    #[state_machine_future(error)]
+    Failed(Error),
+}
+
+impl PollOpen for Open {
+    fn poll_start<'a>(state: &'a mut RentToOwn<'a, Start>) -> Poll<AfterStart, Error> {
+        let state = state.take();
+
+        // the descriptor would be closed as soon as the implicit transaction wrapping lo_open
+        // ended, so fail early rather than on the first read or write
+        if !state.client.in_transaction() {
+            return Err(Error::io(io::Error::new(
+                io::ErrorKind::Other,
+                "large objects can only be opened inside of a transaction",
+            )));
+        }
+
+        match state.client.large_object_statements() {
+            Some(statements) => transition!(Opening {
+                future: Call::new(
+                    &state.client,
+                    &statements.open,
+                    &[&state.oid, &state.mode.to_flags()]
+                ),
+                client: state.client,
+                statements,
+            }),
+            None => {
+                let futures = [OPEN, READ, WRITE, SEEK, CLOSE]
+                    .iter()
+                    .map(|query| state.client.prepare(next_statement(), query, &[]))
+                    .collect();
+                transition!(Preparing {
+                    future: future::join_all(futures),
+                    client: state.client,
+                    oid: state.oid,
+                    mode: state.mode,
+                })
+            }
+        }
+    }
+
+    fn poll_preparing<'a>(state: &'a mut RentToOwn<'a, Preparing>) -> Poll<AfterPreparing, Error> {
+        let mut statements = try_ready!(state.future.poll()).into_iter();
+        let state = state.take();
+
+        let statements = Statements {
+            open: statements.next().unwrap(),
+            read: statements.next().unwrap(),
+            write: statements.next().unwrap(),
+            seek: statements.next().unwrap(),
+            close: statements.next().unwrap(),
+        };
+        state.client.set_large_object_statements(&statements);
+
+        transition!(Opening {
+            future: Call::new(
+                &state.client,
+                &statements.open,
+                &[&state.oid, &state.mode.to_flags()]
+            ),
+            client: state.client,
+            statements,
+        })
+    }
+
+    fn poll_opening<'a>(state: &'a mut RentToOwn<'a, Opening>) -> Poll<AfterOpening, Error> {
+        let fd = try_ready!(state.future.poll());
+        let state = state.take();
+        transition!(Finished(LargeObject {
+            client: state.client,
+            statements: state.statements,
+            fd,
+            state: State::Idle,
+            buf: vec![],
+            pos: 0,
+        }))
+    }
+}
+
+impl OpenFuture {
+    pub fn new(client: Client, oid: Oid, mode: Mode) -> OpenFuture {
+        Open::start(client, oid, mode)
+    }
+}
+
+enum State {
+    Idle,
+    Reading(Call<Vec<u8>>),
+    Writing(Call<i32>),
+    Seeking(Call<i64>),
+    Closing(Call<i32>),
+    Closed,
+}
+
+pub struct LargeObject {
+    client: Client,
+    statements: Statements,
+    fd: i32,
+    state: State,
+    // data returned by the server which did not fit in the caller's buffer
+    buf: Vec<u8>,
+    pos: usize,
+}
+
+impl LargeObject {
+    pub fn fd(&self) -> i32 {
+        self.fd
+    }
+
+    pub fn poll_read(&mut self, buf: &mut [u8]) -> Poll<usize, Error> {
+        if self.pos < self.buf.len() {
+            return Ok(Async::Ready(self.copy_buffered(buf)));
+        }
+
+        loop {
+            match self.state {
+                State::Idle => {
+                    let len = cmp::min(buf.len(), i32::MAX as usize) as i32;
+                    let call = Call::new(&self.client, &self.statements.read, &[&self.fd, &len]);
+                    self.state = State::Reading(call);
+                }
+                State::Reading(ref mut call) => {
+                    self.buf = try_ready!(call.poll());
+                    self.pos = 0;
+                    break;
+                }
+                _ => return Err(busy()),
+            }
+        }
+
+        self.state = State::Idle;
+        Ok(Async::Ready(self.copy_buffered(buf)))
+    }
+
+    fn copy_buffered(&mut self, buf: &mut [u8]) -> usize {
+        let len = cmp::min(buf.len(), self.buf.len() - self.pos);
+        buf[..len].copy_from_slice(&self.buf[self.pos..self.pos + len]);
+        self.pos += len;
+        len
+    }
+
+    pub fn poll_write(&mut self, buf: &[u8]) -> Poll<usize, Error> {
+        loop {
+            match self.state {
+                // move the server's position back to the end of the data read by the caller
+                State::Idle | State::Seeking(_) if self.pos < self.buf.len() => {
+                    try_ready!(self.poll_seek(SeekFrom::Current(0)));
+                }
+                State::Idle => {
+                    let buf = &buf[..cmp::min(buf.len(), i32::MAX as usize)];
+                    let call = Call::new(&self.client, &self.statements.write, &[&self.fd, &buf]);
+                    self.state = State::Writing(call);
+                }
+                State::Writing(ref mut call) => {
+                    let len = try_ready!(call.poll());
+                    self.state = State::Idle;
+                    return Ok(Async::Ready(len as usize));
+                }
+                _ => return Err(busy()),
+            }
+        }
+    }
+
+    pub fn poll_seek(&mut self, pos: SeekFrom) -> Poll<u64, Error> {
+        loop {
+            match self.state {
+                State::Idle => {
+                    // SEEK_SET, SEEK_CUR and SEEK_END
+                    let (pos, whence) = match pos {
+                        SeekFrom::Start(pos) if pos > i64::MAX as u64 => {
+                            return Err(Error::io(io::Error::new(
+                                io::ErrorKind::InvalidInput,
+                                "seek position out of range",
+                            )));
+                        }
+                        SeekFrom::Start(pos) => (pos as i64, 0),
+                        // the server's position is past any data we've buffered
+                        SeekFrom::Current(pos) => (pos - (self.buf.len() - self.pos) as i64, 1),
+                        SeekFrom::End(pos) => (pos, 2),
+                    };
+                    let call = Call::new(
+                        &self.client,
+                        &self.statements.seek,
+                        &[&self.fd, &pos, &whence],
+                    );
+                    self.state = State::Seeking(call);
+                }
+                State::Seeking(ref mut call) => {
+                    let pos = try_ready!(call.poll());
+                    self.state = State::Idle;
+                    // buffered data refers to the old position
+                    self.buf.clear();
+                    self.pos = 0;
+                    return Ok(Async::Ready(pos as u64));
+                }
+                _ => return Err(busy()),
+            }
+        }
+    }
+
+    pub fn poll_close(&mut self) -> Poll<(), Error> {
+        loop {
+            match self.state {
+                State::Idle => {
+                    let call = Call::new(&self.client, &self.statements.close, &[&self.fd]);
+                    self.state = State::Closing(call);
+                }
+                State::Closing(ref mut call) => {
+                    try_ready!(call.poll());
+                    self.state = State::Closed;
+                }
+                State::Closed => return Ok(Async::Ready(())),
+                _ => return Err(busy()),
+            }
+        }
+    }
+}
+
+fn busy() -> Error {
+    Error::io(io::Error::new(
+        io::ErrorKind::Other,
+        "another large object operation is in progress or the large object is closed",
+    ))
+}
@@ -0,0 +1,63 @@
+use futures::stream::Collect;
+use futures::{Async, Future, Poll, Stream};
+
+use next_statement;
+use proto::client::Client;
+use proto::prepare::PrepareFuture;
+use proto::query::QueryStream;
+use proto::statement::Statement;
+use {Error, PreparedTransaction};
+
+const QUERY: &'static str = "SELECT transaction::text::int8, gid, prepared, owner, database \
+                             FROM pg_catalog.pg_prepared_xacts ORDER BY prepared";
+
+enum State {
+    Preparing {
+        future: PrepareFuture,
+        client: Client,
+    },
+    Querying(Collect<QueryStream<Statement>>),
+}
+
+pub struct ListPreparedFuture(State);
+
+impl ListPreparedFuture {
+    pub fn new(client: Client) -> ListPreparedFuture {
+        let future = client.prepare(next_statement(), QUERY, &[]);
+        ListPreparedFuture(State::Preparing { future, client })
+    }
+}
+
+impl Future for ListPreparedFuture {
+    type Item = Vec<PreparedTransaction>;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Vec<PreparedTransaction>, Error> {
+        loop {
+            let stream = match self.0 {
+                State::Preparing {
+                    ref mut future,
+                    ref client,
+                } => {
+                    let statement = try_ready!(future.poll());
+                    client.query(&statement, &[]).collect()
+                }
+                State::Querying(ref mut future) => {
+                    let rows = try_ready!(future.poll());
+                    let mut xacts = Vec::with_capacity(rows.len());
+                    for row in rows {
+                        xacts.push(PreparedTransaction {
+                            transaction: row.try_get::<_, i64>(0)?.unwrap() as u32,
+                            gid: row.try_get(1)?.unwrap(),
+                            prepared: row.try_get(2)?.unwrap(),
+                            owner: row.try_get(3)?.unwrap(),
+                            database: row.try_get(4)?.unwrap(),
+                        });
+                    }
+                    return Ok(Async::Ready(xacts));
+                }
+            };
+            self.0 = State::Querying(stream);
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use antidote::Mutex;
+use futures::sync::mpsc::{self, UnboundedReceiver};
+use futures::{Async, Future, Poll, Stream};
+use std::collections::VecDeque;
+use std::mem;
+
+use proto::client::{Client, WeakClient};
+use proto::simple_query::SimpleQueryFuture;
+use {quote_identifier, Error, Notification};
+
+struct Listener {
+    id: u64,
+    channel: String,
+    sender: mpsc::UnboundedSender<Notification>,
+}
+
+struct State {
+    next_id: u64,
+    listeners: Vec<Listener>,
+}
+
+/// The set of `LISTEN` registrations shared between a client and its connection.
+pub struct Listeners(Mutex<State>);
+
+impl Listeners {
+    pub fn new() -> Listeners {
+        Listeners(Mutex::new(State {
+            next_id: 0,
+            listeners: vec![],
+        }))
+    }
+
+    pub fn register(&self, channel: &str, sender: mpsc::UnboundedSender<Notification>) -> u64 {
+        let mut state = self.0.lock();
+        let id = state.next_id;
+        state.next_id += 1;
+        state.listeners.push(Listener {
+            id,
+            channel: channel.to_string(),
+            sender,
+        });
+        id
+    }
+
+    /// Removes a registration, returning `true` if it was the last one for its channel.
+    pub fn unregister(&self, id: u64) -> bool {
+        let mut state = self.0.lock();
+        let channel = match state.listeners.iter().position(|l| l.id == id) {
+            Some(idx) => state.listeners.remove(idx).channel,
+            None => return false,
+        };
+        !state.listeners.iter().any(|l| l.channel == channel)
+    }
+
+    pub fn dispatch(&self, notification: &Notification) {
+        self.0.lock().listeners.retain(|listener| {
+            listener.channel != notification.channel
+                || listener.sender.unbounded_send(notification.clone()).is_ok()
+        });
+    }
+
+    pub fn clear(&self) {
+        self.0.lock().listeners.clear();
+    }
+}
+
+fn listen(
+    client: &Client,
+    channel: &str,
+) -> (u64, SimpleQueryFuture, UnboundedReceiver<Notification>) {
+    let (sender, receiver) = mpsc::unbounded();
+    // register before issuing the LISTEN so nothing sent after it completes can be missed
+    let id = client.listeners().register(channel, sender);
+    let future = client.batch_execute(&format!("LISTEN {}", quote_identifier(channel)));
+    (id, future, receiver)
+}
+
+pub struct ListenStream {
+    channel: String,
+    client: WeakClient,
+    id: u64,
+    future: Option<SimpleQueryFuture>,
+    receiver: UnboundedReceiver<Notification>,
+    // receivers from before a relisten, which are drained before the current one
+    previous: VecDeque<UnboundedReceiver<Notification>>,
+}
+
+impl Drop for ListenStream {
+    fn drop(&mut self) {
+        self.unlisten();
+    }
+}
+
+impl ListenStream {
+    pub fn new(client: &Client, channel: &str) -> ListenStream {
+        let (id, future, receiver) = listen(client, channel);
+
+        ListenStream {
+            channel: channel.to_string(),
+            client: client.downgrade(),
+            id,
+            future: Some(future),
+            receiver,
+            previous: VecDeque::new(),
+        }
+    }
+
+    pub fn channel(&self) -> &str {
+        &self.channel
+    }
+
+    pub fn relisten(&mut self, client: &Client) {
+        self.unlisten();
+
+        let (id, future, receiver) = listen(client, &self.channel);
+        let mut previous = mem::replace(&mut self.receiver, receiver);
+        // notifications already queued on the old registration are still delivered
+        previous.close();
+        self.previous.push_back(previous);
+        self.client = client.downgrade();
+        self.id = id;
+        self.future = Some(future);
+    }
+
+    fn unlisten(&self) {
+        let client = match self.client.upgrade() {
+            Some(client) => client,
+            None => return,
+        };
+        if client.listeners().unregister(self.id) {
+            client.batch_execute_detached(&format!("UNLISTEN {}", quote_identifier(&self.channel)));
+        }
+    }
+}
+
+impl Stream for ListenStream {
+    type Item = Notification;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Option<Notification>, Error> {
+        if let Some(mut future) = self.future.take() {
+            if let Async::NotReady = future.poll()? {
+                self.future = Some(future);
+                return Ok(Async::NotReady);
+            }
+        }
+
+        while let Some(mut receiver) = self.previous.pop_front() {
+            match receiver.poll() {
+                Ok(Async::Ready(Some(notification))) => {
+                    self.previous.push_front(receiver);
+                    return Ok(Async::Ready(Some(notification)));
+                }
+                Ok(Async::Ready(None)) => {}
+                Ok(Async::NotReady) => {
+                    self.previous.push_front(receiver);
+                    return Ok(Async::NotReady);
+                }
+                Err(()) => unreachable!("mpsc::Receiver doesn't return errors"),
+            }
+        }
+
+        match self.receiver.poll() {
+            Ok(Async::Ready(notification)) => Ok(Async::Ready(notification)),
+            Ok(Async::NotReady) => Ok(Async::NotReady),
+            Err(()) => unreachable!("mpsc::Receiver doesn't return errors"),
+        }
+    }
+}
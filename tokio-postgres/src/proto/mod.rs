@@ -26,11 +26,16 @@ mod connect;
 mod connection;
 mod copy_in;
 mod copy_out;
+mod cursor;
 mod execute;
 mod handshake;
+mod large_object;
+mod list_prepared;
+mod listen;
 mod portal;
 mod prepare;
 mod query;
+mod replication;
 mod row;
 mod simple_query;
 mod socket;
@@ -47,11 +52,18 @@ pub use proto::codec::PostgresCodec;
 pub use proto::connection::Connection;
 pub use proto::copy_in::CopyInFuture;
 pub use proto::copy_out::CopyOutStream;
+pub use proto::cursor::{FetchStream, MoveFuture};
 pub use proto::execute::ExecuteFuture;
 pub use proto::handshake::HandshakeFuture;
+pub use proto::large_object::{FunctionFuture, LargeObject, OpenFuture};
+pub use proto::list_prepared::ListPreparedFuture;
+pub use proto::listen::ListenStream;
 pub use proto::portal::Portal;
 pub use proto::prepare::PrepareFuture;
 pub use proto::query::QueryStream;
+pub use proto::replication::{
+    parse_opt_text, parse_text, BaseBackupStream, ReplicationCommandFuture, ReplicationStream,
+};
 pub use proto::row::Row;
 pub use proto::simple_query::SimpleQueryFuture;
 pub use proto::socket::Socket;
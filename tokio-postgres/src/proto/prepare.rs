@@ -2,6 +2,7 @@ use fallible_iterator::FallibleIterator;
 use futures::sync::mpsc;
 use futures::{Future, Poll, Stream};
 use postgres_protocol::message::backend::Message;
+use postgres_shared::stmt::ColumnDescription;
 use state_machine_future::RentToOwn;
 use std::mem;
 use std::vec;
@@ -45,13 +46,13 @@ pub enum Prepare {
         remaining_parameters: vec::IntoIter<Oid>,
         name: String,
         parameters: Vec<Type>,
-        columns: Vec<(String, Oid)>,
+        columns: Vec<ColumnDescription>,
     },
     #[state_machine_future(transitions(Finished))]
     GetColumnTypes {
         future: TypeinfoFuture,
-        cur_column_name: String,
-        remaining_columns: vec::IntoIter<(String, Oid)>,
+        cur_column: ColumnDescription,
+        remaining_columns: vec::IntoIter<ColumnDescription>,
         name: String,
         parameters: Vec<Type>,
         columns: Vec<Column>,
@@ -119,7 +120,7 @@ impl PollPrepare for Prepare {
         let columns = match message {
             Some(Message::RowDescription(body)) => body
                 .fields()
-                .map(|f| (f.name().to_string(), f.type_oid()))
+                .map(|f| ColumnDescription::new(&f))
                 .collect()
                 .map_err(Error::parse)?,
             Some(Message::NoData) => vec![],
@@ -139,10 +140,10 @@ impl PollPrepare for Prepare {
         }
 
         let mut columns = columns.into_iter();
-        if let Some((name, oid)) = columns.next() {
+        if let Some(column) = columns.next() {
             transition!(GetColumnTypes {
-                future: TypeinfoFuture::new(oid, state.client),
-                cur_column_name: name,
+                future: TypeinfoFuture::new(column.type_oid(), state.client),
+                cur_column: column,
                 remaining_columns: columns,
                 name: state.name,
                 parameters: vec![],
@@ -173,10 +174,10 @@ impl PollPrepare for Prepare {
         let state = state.take();
 
         let mut columns = state.columns.into_iter();
-        if let Some((name, oid)) = columns.next() {
+        if let Some(column) = columns.next() {
             transition!(GetColumnTypes {
-                future: TypeinfoFuture::new(oid, client),
-                cur_column_name: name,
+                future: TypeinfoFuture::new(column.type_oid(), client),
+                cur_column: column,
                 remaining_columns: columns,
                 name: state.name,
                 parameters: state.parameters,
@@ -195,20 +196,20 @@ impl PollPrepare for Prepare {
     fn poll_get_column_types<'a>(
         state: &'a mut RentToOwn<'a, GetColumnTypes>,
     ) -> Poll<AfterGetColumnTypes, Error> {
-        let client = loop {
+        let (ty, client) = loop {
             let (ty, client) = try_ready!(state.future.poll());
-            let name = mem::replace(&mut state.cur_column_name, String::new());
-            state.columns.push(Column::new(name, ty));
 
             match state.remaining_columns.next() {
-                Some((name, oid)) => {
-                    state.cur_column_name = name;
-                    state.future = TypeinfoFuture::new(oid, client);
+                Some(column) => {
+                    state.future = TypeinfoFuture::new(column.type_oid(), client);
+                    let column = mem::replace(&mut state.cur_column, column);
+                    state.columns.push(column.into_column(ty));
                 }
-                None => break client,
+                None => break (ty, client),
             }
         };
-        let state = state.take();
+        let mut state = state.take();
+        state.columns.push(state.cur_column.into_column(ty));
 
         transition!(Finished(Statement::new(
             client.downgrade(),
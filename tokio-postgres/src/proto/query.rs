@@ -7,7 +7,7 @@ use proto::client::{Client, PendingRequest};
 use proto::portal::Portal;
 use proto::row::Row;
 use proto::statement::Statement;
-use Error;
+use {DbError, Error};
 
 pub trait StatementHolder {
     fn statement(&self) -> &Statement;
@@ -30,6 +30,7 @@ enum State<T> {
         client: Client,
         request: PendingRequest,
         statement: T,
+        notices: Option<mpsc::UnboundedSender<DbError>>,
     },
     ReadingResponse {
         receiver: mpsc::Receiver<Message>,
@@ -54,8 +55,9 @@ where
                     client,
                     request,
                     statement,
+                    notices,
                 } => {
-                    let receiver = client.send(request)?;
+                    let receiver = client.send_with_notices(request, notices)?;
                     self.0 = State::ReadingResponse {
                         receiver,
                         statement,
@@ -112,11 +114,17 @@ impl<T> QueryStream<T>
 where
     T: StatementHolder,
 {
-    pub fn new(client: Client, request: PendingRequest, statement: T) -> QueryStream<T> {
+    pub fn new(
+        client: Client,
+        request: PendingRequest,
+        statement: T,
+        notices: Option<mpsc::UnboundedSender<DbError>>,
+    ) -> QueryStream<T> {
         QueryStream(State::Start {
             client,
             request,
             statement,
+            notices,
         })
     }
 }
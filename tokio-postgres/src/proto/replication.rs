@@ -0,0 +1,499 @@
+use futures::sync::mpsc;
+use futures::{Async, AsyncSink, Future, Poll, Sink, Stream};
+use postgres_protocol::message::backend::{self, Message};
+use postgres_protocol::message::frontend;
+use postgres_shared::rows::RowData;
+use state_machine_future::RentToOwn;
+use std::collections::VecDeque;
+use std::io;
+use std::mem;
+use std::str::{self, FromStr};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+use tokio_timer::Delay;
+
+use proto::client::{Client, PendingRequest};
+use proto::copy_in::CopyMessage;
+use replication::{
+    BaseBackupMessage, PrimaryKeepAlive, ReplicationMessage, Tablespace, XLogData,
+};
+use types::PgLsn;
+use Error;
+
+// seconds from the Unix epoch to the Postgres epoch of 2000-01-01
+const POSTGRES_EPOCH: u64 = 946_684_800;
+
+// the default of pg_recvlogical and pg_receivewal
+const DEFAULT_STATUS_INTERVAL_SECS: u64 = 10;
+
+#[derive(StateMachineFuture)]
+pub enum ReplicationCommand {
+    #[state_machine_future(start, transitions(ReadResponse))]
+    Start {
+        client: Client,
+        request: PendingRequest,
+    },
+    #[state_machine_future(transitions(Finished))]
+    ReadResponse {
+        receiver: mpsc::Receiver<Message>,
+        rows: Vec<RowData>,
+    },
+    #[state_machine_future(ready)]
+    Finished(Vec<RowData>),
+    #[state_machine_future(error)]
+    Failed(Error),
+}
+
+impl PollReplicationCommand for ReplicationCommand {
+    fn poll_start<'a>(state: &'a mut RentToOwn<'a, Start>) -> Poll<AfterStart, Error> {
+        let state = state.take();
+        let receiver = state.client.send(state.request)?;
+
+        transition!(ReadResponse {
+            receiver,
+            rows: vec![],
+        })
+    }
+
+    fn poll_read_response<'a>(
+        state: &'a mut RentToOwn<'a, ReadResponse>,
+    ) -> Poll<AfterReadResponse, Error> {
+        loop {
+            let message = try_ready_receive!(state.receiver.poll());
+
+            match message {
+                Some(Message::DataRow(body)) => {
+                    let row = RowData::new(body).map_err(Error::parse)?;
+                    state.rows.push(row);
+                }
+                Some(Message::RowDescription(_))
+                | Some(Message::CommandComplete(_))
+                | Some(Message::EmptyQueryResponse) => {}
+                Some(Message::ErrorResponse(body)) => return Err(Error::db(body)),
+                Some(Message::ReadyForQuery(_)) => {
+                    let state = state.take();
+                    transition!(Finished(state.rows))
+                }
+                Some(_) => return Err(Error::unexpected_message()),
+                None => return Err(Error::closed()),
+            }
+        }
+    }
+}
+
+impl ReplicationCommandFuture {
+    pub fn new(client: Client, request: PendingRequest) -> ReplicationCommandFuture {
+        ReplicationCommand::start(client, request)
+    }
+}
+
+fn text_column(row: &RowData, idx: usize) -> Result<Option<&str>, Error> {
+    match row.get(idx) {
+        Some(buf) => str::from_utf8(buf)
+            .map(Some)
+            .map_err(|e| Error::from_sql(Box::new(e))),
+        None => Ok(None),
+    }
+}
+
+/// Parses the text value of a column of a replication command's response.
+pub fn parse_text<T>(row: &RowData, idx: usize) -> Result<T, Error>
+where
+    T: FromStr,
+{
+    parse_opt_text(row, idx)?.ok_or_else(|| invalid_data("unexpected null value".to_string()))
+}
+
+/// Like `parse_text`, but accepts null values.
+pub fn parse_opt_text<T>(row: &RowData, idx: usize) -> Result<Option<T>, Error>
+where
+    T: FromStr,
+{
+    match text_column(row, idx)? {
+        Some(value) => value
+            .parse()
+            .map(Some)
+            .map_err(|_| invalid_data(format!("invalid value `{}`", value))),
+        None => Ok(None),
+    }
+}
+
+fn invalid_data(message: String) -> Error {
+    Error::parse(io::Error::new(io::ErrorKind::InvalidData, message))
+}
+
+enum State {
+    Start {
+        client: Client,
+        request: PendingRequest,
+    },
+    ReadingCopyBothResponse {
+        receiver: mpsc::Receiver<Message>,
+    },
+    Streaming {
+        receiver: mpsc::Receiver<Message>,
+    },
+    // the backend has left copy-both mode; any further responses are read until ReadyForQuery
+    Finishing {
+        receiver: mpsc::Receiver<Message>,
+    },
+    Done,
+}
+
+pub struct ReplicationStream {
+    state: State,
+    sender: mpsc::Sender<CopyMessage>,
+    pending: VecDeque<CopyMessage>,
+    written_lsn: u64,
+    flushed_lsn: u64,
+    applied_lsn: u64,
+    stopping: bool,
+    next_timeline: Option<(u32, PgLsn)>,
+    status_interval: Option<Duration>,
+    status_delay: Option<Delay>,
+}
+
+impl ReplicationStream {
+    pub fn new(
+        client: Client,
+        request: PendingRequest,
+        sender: mpsc::Sender<CopyMessage>,
+        start_lsn: u64,
+    ) -> ReplicationStream {
+        ReplicationStream {
+            state: State::Start { client, request },
+            sender,
+            pending: VecDeque::new(),
+            written_lsn: start_lsn,
+            flushed_lsn: start_lsn,
+            applied_lsn: start_lsn,
+            stopping: false,
+            next_timeline: None,
+            status_interval: Some(Duration::from_secs(DEFAULT_STATUS_INTERVAL_SECS)),
+            status_delay: None,
+        }
+    }
+
+    pub fn next_timeline(&self) -> Option<(u32, PgLsn)> {
+        self.next_timeline
+    }
+
+    pub fn standby_status_update(&mut self, written: u64, flushed: u64, applied: u64) {
+        self.written_lsn = written;
+        self.flushed_lsn = flushed;
+        self.applied_lsn = applied;
+        self.queue_status_update(false);
+    }
+
+    pub fn status_interval(&mut self, interval: Option<Duration>) {
+        self.status_interval = interval;
+        self.status_delay = None;
+    }
+
+    pub fn stop(&mut self) {
+        if !self.stopping {
+            self.stopping = true;
+            self.pending.push_back(CopyMessage::Done);
+        }
+    }
+
+    fn queue_status_update(&mut self, reply: bool) {
+        if self.stopping {
+            return;
+        }
+
+        let mut buf = vec![];
+        frontend::standby_status_update(
+            self.written_lsn,
+            self.flushed_lsn,
+            self.applied_lsn,
+            postgres_timestamp(SystemTime::now()),
+            reply,
+            &mut buf,
+        );
+        self.pending.push_back(CopyMessage::Data(buf));
+        // the next periodic update is due a full interval after this one
+        self.status_delay = None;
+    }
+
+    fn poll_status_interval(&mut self) -> Result<(), Error> {
+        let interval = match self.status_interval {
+            Some(interval) => interval,
+            None => return Ok(()),
+        };
+
+        while !self.stopping {
+            let due = self
+                .status_delay
+                .get_or_insert_with(|| Delay::new(Instant::now() + interval))
+                .poll()
+                .map_err(Error::timer)?
+                .is_ready();
+            if !due {
+                break;
+            }
+            self.queue_status_update(false);
+        }
+        Ok(())
+    }
+
+    fn poll_send(&mut self) -> Result<(), Error> {
+        while let Some(message) = self.pending.pop_front() {
+            match self.sender.start_send(message) {
+                Ok(AsyncSink::Ready) => {}
+                Ok(AsyncSink::NotReady(message)) => {
+                    self.pending.push_front(message);
+                    return Ok(());
+                }
+                Err(_) => return Err(Error::closed()),
+            }
+        }
+
+        self.sender.poll_complete().map_err(|_| Error::closed())?;
+        Ok(())
+    }
+}
+
+impl Stream for ReplicationStream {
+    type Item = ReplicationMessage;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Option<ReplicationMessage>, Error> {
+        loop {
+            match mem::replace(&mut self.state, State::Done) {
+                State::Start { client, request } => {
+                    let receiver = client.send(request)?;
+                    self.state = State::ReadingCopyBothResponse { receiver };
+                }
+                State::ReadingCopyBothResponse { mut receiver } => {
+                    let message = match receiver.poll() {
+                        Ok(Async::Ready(message)) => message,
+                        Ok(Async::NotReady) => {
+                            self.state = State::ReadingCopyBothResponse { receiver };
+                            return Ok(Async::NotReady);
+                        }
+                        Err(()) => unreachable!("mpsc::Receiver doesn't return errors"),
+                    };
+
+                    match message {
+                        Some(Message::CopyBothResponse(_)) => {
+                            self.state = State::Streaming { receiver };
+                        }
+                        Some(Message::ErrorResponse(body)) => return Err(Error::db(body)),
+                        Some(_) => return Err(Error::unexpected_message()),
+                        None => return Err(Error::closed()),
+                    }
+                }
+                State::Streaming { mut receiver } => {
+                    self.poll_status_interval()?;
+                    self.poll_send()?;
+
+                    let message = match receiver.poll() {
+                        Ok(Async::Ready(message)) => message,
+                        Ok(Async::NotReady) => {
+                            self.state = State::Streaming { receiver };
+                            return Ok(Async::NotReady);
+                        }
+                        Err(()) => unreachable!("mpsc::Receiver doesn't return errors"),
+                    };
+
+                    match message {
+                        Some(Message::CopyData(body)) => {
+                            let message = backend::ReplicationMessage::parse(&body.into_bytes())
+                                .map_err(Error::parse)?;
+                            self.state = State::Streaming { receiver };
+
+                            let message = match message {
+                                backend::ReplicationMessage::XLogData(body) => {
+                                    ReplicationMessage::XLogData(XLogData {
+                                        wal_start: body.wal_start().into(),
+                                        wal_end: body.wal_end().into(),
+                                        timestamp: body.timestamp(),
+                                        data: body.into_bytes(),
+                                    })
+                                }
+                                backend::ReplicationMessage::PrimaryKeepAlive(body) => {
+                                    if body.reply() {
+                                        self.queue_status_update(false);
+                                        self.poll_send()?;
+                                    }
+                                    ReplicationMessage::PrimaryKeepAlive(PrimaryKeepAlive {
+                                        wal_end: body.wal_end().into(),
+                                        timestamp: body.timestamp(),
+                                        reply: body.reply(),
+                                    })
+                                }
+                                _ => return Err(Error::unexpected_message()),
+                            };
+                            return Ok(Async::Ready(Some(message)));
+                        }
+                        Some(Message::CopyDone) => {
+                            self.stop();
+                            self.poll_send()?;
+                            self.state = State::Finishing { receiver };
+                        }
+                        Some(Message::ErrorResponse(body)) => return Err(Error::db(body)),
+                        Some(_) => return Err(Error::unexpected_message()),
+                        None => return Err(Error::closed()),
+                    }
+                }
+                State::Finishing { mut receiver } => {
+                    self.poll_send()?;
+
+                    let message = match receiver.poll() {
+                        Ok(Async::Ready(message)) => message,
+                        Ok(Async::NotReady) => {
+                            self.state = State::Finishing { receiver };
+                            return Ok(Async::NotReady);
+                        }
+                        Err(()) => unreachable!("mpsc::Receiver doesn't return errors"),
+                    };
+
+                    match message {
+                        // data still in flight when we asked the backend to stop is discarded
+                        Some(Message::CopyData(_))
+                        | Some(Message::CopyDone)
+                        | Some(Message::RowDescription(_))
+                        | Some(Message::CommandComplete(_)) => {
+                            self.state = State::Finishing { receiver };
+                        }
+                        // physical replication reports the next timeline when the streamed one ends
+                        Some(Message::DataRow(body)) => {
+                            let row = RowData::new(body).map_err(Error::parse)?;
+                            let timeline = parse_text(&row, 0)?;
+                            let lsn = parse_text(&row, 1)?;
+                            self.next_timeline = Some((timeline, lsn));
+                            self.state = State::Finishing { receiver };
+                        }
+                        Some(Message::ReadyForQuery(_)) => return Ok(Async::Ready(None)),
+                        Some(Message::ErrorResponse(body)) => return Err(Error::db(body)),
+                        Some(_) => return Err(Error::unexpected_message()),
+                        None => return Err(Error::closed()),
+                    }
+                }
+                State::Done => return Ok(Async::Ready(None)),
+            }
+        }
+    }
+}
+
+enum BaseBackupState {
+    Start {
+        client: Client,
+        request: PendingRequest,
+    },
+    Reading {
+        receiver: mpsc::Receiver<Message>,
+    },
+    Done,
+}
+
+pub struct BaseBackupStream {
+    state: BaseBackupState,
+    rows: Vec<RowData>,
+    results: usize,
+}
+
+impl BaseBackupStream {
+    pub fn new(client: Client, request: PendingRequest) -> BaseBackupStream {
+        BaseBackupStream {
+            state: BaseBackupState::Start { client, request },
+            rows: vec![],
+            results: 0,
+        }
+    }
+
+    fn finish_result(&mut self) -> Result<BaseBackupMessage, Error> {
+        let rows = mem::replace(&mut self.rows, vec![]);
+        let results = self.results;
+        self.results += 1;
+
+        // the backup is bracketed by the WAL positions at its start and end, with the list of
+        // tablespaces to be archived following the start position
+        if results == 1 {
+            let mut tablespaces = vec![];
+            for row in &rows {
+                tablespaces.push(Tablespace {
+                    oid: parse_opt_text(row, 0)?,
+                    location: parse_opt_text(row, 1)?,
+                    size: parse_opt_text(row, 2)?,
+                });
+            }
+            return Ok(BaseBackupMessage::Tablespaces(tablespaces));
+        }
+
+        let row = rows
+            .first()
+            .ok_or_else(|| invalid_data("missing WAL position".to_string()))?;
+        let lsn = parse_text(row, 0)?;
+        let timeline = parse_text(row, 1)?;
+        if results == 0 {
+            Ok(BaseBackupMessage::Start { lsn, timeline })
+        } else {
+            Ok(BaseBackupMessage::End { lsn, timeline })
+        }
+    }
+}
+
+impl Stream for BaseBackupStream {
+    type Item = BaseBackupMessage;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Option<BaseBackupMessage>, Error> {
+        loop {
+            match mem::replace(&mut self.state, BaseBackupState::Done) {
+                BaseBackupState::Start { client, request } => {
+                    let receiver = client.send(request)?;
+                    self.state = BaseBackupState::Reading { receiver };
+                }
+                BaseBackupState::Reading { mut receiver } => {
+                    let message = match receiver.poll() {
+                        Ok(Async::Ready(message)) => message,
+                        Ok(Async::NotReady) => {
+                            self.state = BaseBackupState::Reading { receiver };
+                            return Ok(Async::NotReady);
+                        }
+                        Err(()) => unreachable!("mpsc::Receiver doesn't return errors"),
+                    };
+
+                    let message = match message {
+                        Some(Message::RowDescription(_)) => {
+                            self.rows.clear();
+                            None
+                        }
+                        Some(Message::DataRow(body)) => {
+                            self.rows.push(RowData::new(body).map_err(Error::parse)?);
+                            None
+                        }
+                        Some(Message::CommandComplete(_)) => Some(self.finish_result()?),
+                        Some(Message::CopyOutResponse(_)) => Some(BaseBackupMessage::ArchiveStart),
+                        Some(Message::CopyData(body)) => {
+                            Some(BaseBackupMessage::ArchiveData(body.into_bytes()))
+                        }
+                        Some(Message::CopyDone) => Some(BaseBackupMessage::ArchiveEnd),
+                        Some(Message::ReadyForQuery(_)) => return Ok(Async::Ready(None)),
+                        Some(Message::ErrorResponse(body)) => return Err(Error::db(body)),
+                        Some(_) => return Err(Error::unexpected_message()),
+                        None => return Err(Error::closed()),
+                    };
+
+                    self.state = BaseBackupState::Reading { receiver };
+                    if let Some(message) = message {
+                        return Ok(Async::Ready(Some(message)));
+                    }
+                }
+                BaseBackupState::Done => return Ok(Async::Ready(None)),
+            }
+        }
+    }
+}
+
+fn postgres_timestamp(time: SystemTime) -> i64 {
+    let epoch = UNIX_EPOCH + Duration::from_secs(POSTGRES_EPOCH);
+    match time.duration_since(epoch) {
+        Ok(d) => (d.as_secs() * 1_000_000 + u64::from(d.subsec_nanos() / 1_000)) as i64,
+        Err(e) => {
+            let d = e.duration();
+            -((d.as_secs() * 1_000_000 + u64::from(d.subsec_nanos() / 1_000)) as i64)
+        }
+    }
+}
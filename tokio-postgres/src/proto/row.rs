@@ -1,10 +1,11 @@
 use postgres_protocol::message::backend::DataRowBody;
 use postgres_shared::rows::{RowData, RowIndex};
 use std::fmt;
+use std::str;
 
 use proto::statement::Statement;
-use types::{FromSql, WrongType};
-use {Column, Error};
+use types::{__from_sql_column, FromSql};
+use {Column, Error, Format};
 
 pub struct Row {
     statement: Statement,
@@ -55,11 +56,52 @@ impl Row {
             None => return Ok(None),
         };
 
-        let ty = self.statement.columns()[idx].type_();
-        if !<T as FromSql>::accepts(ty) {
-            return Err(Error::from_sql(Box::new(WrongType::new(ty.clone()))));
-        }
-        let value = FromSql::from_sql_nullable(ty, self.data.get(idx));
+        let value = __from_sql_column(&self.statement.columns()[idx], self.data.get(idx));
         value.map(Some).map_err(Error::from_sql)
     }
+
+    pub fn get_raw<I>(&self, idx: I) -> Option<&[u8]>
+    where
+        I: RowIndex + fmt::Debug,
+    {
+        match idx.__idx(self.columns()) {
+            Some(idx) => self.data.get(idx),
+            None => panic!("no such column {:?}", idx),
+        }
+    }
+
+    pub fn get_text<I>(&self, idx: I) -> Option<&str>
+    where
+        I: RowIndex + fmt::Debug,
+    {
+        if idx.__idx(self.columns()).is_none() {
+            panic!("no such column {:?}", idx);
+        }
+        match self.try_get_text(&idx) {
+            Ok(value) => value,
+            Err(e) => panic!("error retrieving column {:?}: {}", idx, e),
+        }
+    }
+
+    pub fn try_get_text<I>(&self, idx: I) -> Result<Option<&str>, Error>
+    where
+        I: RowIndex,
+    {
+        let idx = match idx.__idx(self.columns()) {
+            Some(idx) => idx,
+            None => return Ok(None),
+        };
+
+        if self.columns()[idx].format() != Format::Text {
+            return Err(Error::from_sql(
+                "the column was not retrieved in the text format".into(),
+            ));
+        }
+        match self.data.get(idx) {
+            Some(value) => str::from_utf8(value)
+                .map(Some)
+                .map_err(|e| Error::from_sql(Box::new(e))),
+            None => Ok(None),
+        }
+    }
 }
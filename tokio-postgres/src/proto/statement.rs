@@ -1,14 +1,14 @@
-use postgres_shared::stmt::Column;
+use postgres_shared::stmt::{with_result_formats, Column, Format};
 use std::sync::Arc;
 
 use proto::client::WeakClient;
 use types::Type;
+use Error;
 
 pub struct StatementInner {
     client: WeakClient,
     name: String,
     params: Vec<Type>,
-    columns: Vec<Column>,
 }
 
 impl Drop for StatementInner {
@@ -19,8 +19,13 @@ impl Drop for StatementInner {
     }
 }
 
+// the columns are stored separately so statements configured with different result formats can
+// share the server-side statement
 #[derive(Clone)]
-pub struct Statement(Arc<StatementInner>);
+pub struct Statement {
+    inner: Arc<StatementInner>,
+    columns: Arc<Vec<Column>>,
+}
 
 impl Statement {
     pub fn new(
@@ -29,23 +34,31 @@ impl Statement {
         params: Vec<Type>,
         columns: Vec<Column>,
     ) -> Statement {
-        Statement(Arc::new(StatementInner {
-            client,
-            name,
-            params,
-            columns,
-        }))
+        Statement {
+            inner: Arc::new(StatementInner {
+                client,
+                name,
+                params,
+            }),
+            columns: Arc::new(columns),
+        }
     }
 
     pub fn name(&self) -> &str {
-        &self.0.name
+        &self.inner.name
     }
 
     pub fn params(&self) -> &[Type] {
-        &self.0.params
+        &self.inner.params
     }
 
     pub fn columns(&self) -> &[Column] {
-        &self.0.columns
+        &self.columns
+    }
+
+    pub fn set_result_formats(&mut self, formats: &[Format]) -> Result<(), Error> {
+        let columns = with_result_formats(&self.columns, formats).map_err(Error::io)?;
+        self.columns = Arc::new(columns);
+        Ok(())
     }
 }
@@ -3,7 +3,7 @@ use proto::client::Client;
 use proto::simple_query::SimpleQueryFuture;
 use state_machine_future::RentToOwn;
 
-use Error;
+use {quote_literal, Error};
 
 #[derive(StateMachineFuture)]
 pub enum Transaction<F, T, E>
@@ -12,15 +12,24 @@ where
     E: From<Error>,
 {
     #[state_machine_future(start, transitions(Beginning))]
-    Start { client: Client, future: F },
+    Start {
+        client: Client,
+        future: F,
+        commit: String,
+    },
     #[state_machine_future(transitions(Running))]
     Beginning {
         client: Client,
         begin: SimpleQueryFuture,
         future: F,
+        commit: String,
     },
     #[state_machine_future(transitions(Finishing))]
-    Running { client: Client, future: F },
+    Running {
+        client: Client,
+        future: F,
+        commit: String,
+    },
     #[state_machine_future(transitions(Finished))]
     Finishing {
         future: SimpleQueryFuture,
@@ -45,6 +54,7 @@ where
             begin: state.client.batch_execute("BEGIN"),
             client: state.client,
             future: state.future,
+            commit: state.commit,
         })
     }
 
@@ -56,6 +66,7 @@ where
         transition!(Running {
             client: state.client,
             future: state.future,
+            commit: state.commit,
         })
     }
 
@@ -65,7 +76,7 @@ where
         match state.future.poll() {
             Ok(Async::NotReady) => return Ok(Async::NotReady),
             Ok(Async::Ready(t)) => transition!(Finishing {
-                future: state.client.batch_execute("COMMIT"),
+                future: state.client.batch_execute(&state.commit),
                 result: Ok(t),
             }),
             Err(e) => transition!(Finishing {
@@ -99,6 +110,13 @@ where
     E: From<Error>,
 {
     pub fn new(client: Client, future: F) -> TransactionFuture<F, T, E> {
-        Transaction::start(client, future)
+        Transaction::start(client, future, "COMMIT".to_string())
+    }
+
+    /// Like `new`, but ends a successful transaction with `PREPARE TRANSACTION` rather than
+    /// `COMMIT`.
+    pub fn new_prepared(client: Client, future: F, gid: &str) -> TransactionFuture<F, T, E> {
+        let commit = format!("PREPARE TRANSACTION {}", quote_literal(gid));
+        Transaction::start(client, future, commit)
     }
 }
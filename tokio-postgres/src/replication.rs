@@ -0,0 +1,431 @@
+//! Streaming replication support.
+//!
+//! Replication commands are only accepted by connections opened with the `replication` connection
+//! parameter set.
+use bytes::Bytes;
+use futures::{Async, Future, Poll, Stream};
+use std::time::Duration;
+
+use error::Error;
+use proto;
+use types::{Oid, PgLsn};
+
+/// The kind of replication slot to create.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SlotKind {
+    /// A physical replication slot.
+    Physical,
+    /// A logical replication slot using the specified output plugin (e.g. `pgoutput`).
+    Logical(String),
+}
+
+/// Information about the server returned by `IDENTIFY_SYSTEM`.
+#[derive(Debug, Clone)]
+pub struct SystemIdentification {
+    systemid: String,
+    timeline: u32,
+    xlogpos: PgLsn,
+    dbname: Option<String>,
+}
+
+impl SystemIdentification {
+    /// The unique system identifier of the cluster.
+    pub fn systemid(&self) -> &str {
+        &self.systemid
+    }
+
+    /// The current timeline ID.
+    pub fn timeline(&self) -> u32 {
+        self.timeline
+    }
+
+    /// The current WAL flush location.
+    pub fn xlogpos(&self) -> PgLsn {
+        self.xlogpos
+    }
+
+    /// The database connected to, if this is a logical replication connection.
+    pub fn dbname(&self) -> Option<&str> {
+        self.dbname.as_ref().map(|s| &**s)
+    }
+}
+
+/// A replication slot created by `CREATE_REPLICATION_SLOT`.
+#[derive(Debug, Clone)]
+pub struct ReplicationSlot {
+    slot_name: String,
+    consistent_point: PgLsn,
+    snapshot_name: Option<String>,
+    output_plugin: Option<String>,
+}
+
+impl ReplicationSlot {
+    /// The name of the slot.
+    pub fn slot_name(&self) -> &str {
+        &self.slot_name
+    }
+
+    /// The WAL location at which the slot became consistent.
+    ///
+    /// This is the earliest location from which streaming can start on the slot.
+    pub fn consistent_point(&self) -> PgLsn {
+        self.consistent_point
+    }
+
+    /// The identifier of the snapshot exported by the command, if any.
+    pub fn snapshot_name(&self) -> Option<&str> {
+        self.snapshot_name.as_ref().map(|s| &**s)
+    }
+
+    /// The output plugin used by a logical slot.
+    pub fn output_plugin(&self) -> Option<&str> {
+        self.output_plugin.as_ref().map(|s| &**s)
+    }
+}
+
+/// Options for a `BASE_BACKUP` command.
+#[derive(Debug, Clone, Default)]
+pub struct BaseBackupOptions {
+    label: Option<String>,
+    progress: bool,
+    fast: bool,
+    wal: bool,
+    nowait: bool,
+    max_rate: Option<u32>,
+    tablespace_map: bool,
+}
+
+impl BaseBackupOptions {
+    /// Creates a new set of options with the server's defaults.
+    pub fn new() -> BaseBackupOptions {
+        BaseBackupOptions::default()
+    }
+
+    /// Sets the label of the backup.
+    pub fn label(&mut self, label: &str) -> &mut BaseBackupOptions {
+        self.label = Some(label.to_string());
+        self
+    }
+
+    /// Requests that the size of each tablespace be reported before the backup starts.
+    pub fn progress(&mut self, progress: bool) -> &mut BaseBackupOptions {
+        self.progress = progress;
+        self
+    }
+
+    /// Requests an immediate checkpoint rather than a spread one.
+    pub fn fast(&mut self, fast: bool) -> &mut BaseBackupOptions {
+        self.fast = fast;
+        self
+    }
+
+    /// Requests that the WAL needed to make the backup consistent be included in the archive.
+    pub fn wal(&mut self, wal: bool) -> &mut BaseBackupOptions {
+        self.wal = wal;
+        self
+    }
+
+    /// Requests that the server not wait for the required WAL to be archived.
+    pub fn nowait(&mut self, nowait: bool) -> &mut BaseBackupOptions {
+        self.nowait = nowait;
+        self
+    }
+
+    /// Limits the transfer rate of the backup, in kilobytes per second.
+    pub fn max_rate(&mut self, max_rate: Option<u32>) -> &mut BaseBackupOptions {
+        self.max_rate = max_rate;
+        self
+    }
+
+    /// Requests that a `tablespace_map` file be included in the archive.
+    pub fn tablespace_map(&mut self, tablespace_map: bool) -> &mut BaseBackupOptions {
+        self.tablespace_map = tablespace_map;
+        self
+    }
+
+    pub(crate) fn build_command(&self) -> String {
+        let mut command = "BASE_BACKUP".to_string();
+        if let Some(ref label) = self.label {
+            command.push_str(" LABEL ");
+            command.push_str(&::quote_replication_literal(label));
+        }
+        if self.progress {
+            command.push_str(" PROGRESS");
+        }
+        if self.fast {
+            command.push_str(" FAST");
+        }
+        if self.wal {
+            command.push_str(" WAL");
+        }
+        if self.nowait {
+            command.push_str(" NOWAIT");
+        }
+        if let Some(max_rate) = self.max_rate {
+            command.push_str(&format!(" MAX_RATE {}", max_rate));
+        }
+        if self.tablespace_map {
+            command.push_str(" TABLESPACE_MAP");
+        }
+        command
+    }
+}
+
+/// A tablespace included in a base backup.
+#[derive(Debug, Clone)]
+pub struct Tablespace {
+    pub(crate) oid: Option<Oid>,
+    pub(crate) location: Option<String>,
+    pub(crate) size: Option<i64>,
+}
+
+impl Tablespace {
+    /// The OID of the tablespace, or `None` for the main data directory.
+    pub fn oid(&self) -> Option<Oid> {
+        self.oid
+    }
+
+    /// The full path of the tablespace directory, or `None` for the main data directory.
+    pub fn location(&self) -> Option<&str> {
+        self.location.as_ref().map(|s| &**s)
+    }
+
+    /// The approximate size of the tablespace in kilobytes, if progress reporting was requested.
+    pub fn size(&self) -> Option<i64> {
+        self.size
+    }
+}
+
+/// A message received from the server while taking a base backup.
+///
+/// A backup consists of a `Start` message, a `Tablespaces` message, and then a tar archive for
+/// each tablespace in the order listed, each delimited by `ArchiveStart` and `ArchiveEnd`. An
+/// `End` message follows the last archive.
+#[derive(Debug, Clone)]
+pub enum BaseBackupMessage {
+    /// The WAL position and timeline at which the backup started.
+    Start {
+        /// The starting WAL position.
+        lsn: PgLsn,
+        /// The timeline.
+        timeline: u32,
+    },
+    /// The tablespaces included in the backup.
+    Tablespaces(Vec<Tablespace>),
+    /// The start of the tar archive of a tablespace.
+    ArchiveStart,
+    /// A chunk of the current tar archive.
+    ArchiveData(Bytes),
+    /// The end of the tar archive of a tablespace.
+    ArchiveEnd,
+    /// The WAL position and timeline at which the backup ended.
+    End {
+        /// The ending WAL position.
+        lsn: PgLsn,
+        /// The timeline.
+        timeline: u32,
+    },
+    #[doc(hidden)]
+    __NonExhaustive,
+}
+
+/// A stream of the contents of a base backup.
+#[must_use = "streams do nothing unless polled"]
+pub struct BaseBackup(pub(crate) proto::BaseBackupStream);
+
+impl Stream for BaseBackup {
+    type Item = BaseBackupMessage;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Option<BaseBackupMessage>, Error> {
+        self.0.poll()
+    }
+}
+
+/// A message received from the server while streaming replication.
+pub enum ReplicationMessage {
+    /// A chunk of WAL data (or, for logical replication, output plugin data).
+    XLogData(XLogData),
+    /// A keepalive message.
+    PrimaryKeepAlive(PrimaryKeepAlive),
+    #[doc(hidden)]
+    __NonExhaustive,
+}
+
+/// A chunk of WAL data.
+pub struct XLogData {
+    pub(crate) wal_start: PgLsn,
+    pub(crate) wal_end: PgLsn,
+    pub(crate) timestamp: i64,
+    pub(crate) data: Bytes,
+}
+
+impl XLogData {
+    /// The starting point of the WAL data in this message.
+    pub fn wal_start(&self) -> PgLsn {
+        self.wal_start
+    }
+
+    /// The current end of WAL on the server.
+    pub fn wal_end(&self) -> PgLsn {
+        self.wal_end
+    }
+
+    /// The server's system clock at the time of transmission, as microseconds since midnight on
+    /// 2000-01-01.
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    /// The data itself.
+    pub fn data(&self) -> &Bytes {
+        &self.data
+    }
+
+    /// Consumes the message, returning the data.
+    pub fn into_data(self) -> Bytes {
+        self.data
+    }
+}
+
+/// A keepalive message sent by the server.
+pub struct PrimaryKeepAlive {
+    pub(crate) wal_end: PgLsn,
+    pub(crate) timestamp: i64,
+    pub(crate) reply: bool,
+}
+
+impl PrimaryKeepAlive {
+    /// The current end of WAL on the server.
+    pub fn wal_end(&self) -> PgLsn {
+        self.wal_end
+    }
+
+    /// The server's system clock at the time of transmission, as microseconds since midnight on
+    /// 2000-01-01.
+    pub fn timestamp(&self) -> i64 {
+        self.timestamp
+    }
+
+    /// Whether the server requested an immediate status update.
+    ///
+    /// The stream responds to such requests itself with the positions last passed to
+    /// `ReplicationStream::standby_status_update`.
+    pub fn reply(&self) -> bool {
+        self.reply
+    }
+}
+
+#[must_use = "futures do nothing unless polled"]
+pub struct IdentifySystem(pub(crate) proto::ReplicationCommandFuture);
+
+impl Future for IdentifySystem {
+    type Item = SystemIdentification;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<SystemIdentification, Error> {
+        let rows = try_ready!(self.0.poll());
+        let row = match rows.first() {
+            Some(row) => row,
+            None => return Err(Error::unexpected_message()),
+        };
+
+        Ok(Async::Ready(SystemIdentification {
+            systemid: proto::parse_text(row, 0)?,
+            timeline: proto::parse_text(row, 1)?,
+            xlogpos: proto::parse_text(row, 2)?,
+            dbname: proto::parse_opt_text(row, 3)?,
+        }))
+    }
+}
+
+#[must_use = "futures do nothing unless polled"]
+pub struct CreateReplicationSlot(pub(crate) proto::ReplicationCommandFuture);
+
+impl Future for CreateReplicationSlot {
+    type Item = ReplicationSlot;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<ReplicationSlot, Error> {
+        let rows = try_ready!(self.0.poll());
+        let row = match rows.first() {
+            Some(row) => row,
+            None => return Err(Error::unexpected_message()),
+        };
+
+        Ok(Async::Ready(ReplicationSlot {
+            slot_name: proto::parse_text(row, 0)?,
+            consistent_point: proto::parse_text(row, 1)?,
+            snapshot_name: proto::parse_opt_text(row, 2)?,
+            output_plugin: proto::parse_opt_text(row, 3)?,
+        }))
+    }
+}
+
+#[must_use = "futures do nothing unless polled"]
+pub struct DropReplicationSlot(pub(crate) proto::ReplicationCommandFuture);
+
+impl Future for DropReplicationSlot {
+    type Item = ();
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<(), Error> {
+        try_ready!(self.0.poll());
+        Ok(Async::Ready(()))
+    }
+}
+
+/// A stream of replication messages.
+///
+/// Standby status updates are sent back to the server whenever it requests one, and every 10
+/// seconds by default, reporting the positions most recently passed to `standby_status_update`.
+/// Updates are only sent while the stream is being polled. The stream ends once the server has
+/// finished streaming, either on its own or after `stop` is called.
+#[must_use = "streams do nothing unless polled"]
+pub struct ReplicationStream(pub(crate) proto::ReplicationStream);
+
+impl ReplicationStream {
+    /// Reports the WAL positions that have been written, flushed, and applied by the client.
+    ///
+    /// The server may discard WAL up to the flushed position, so it should only be advanced once
+    /// the corresponding data has been durably processed.
+    pub fn standby_status_update(&mut self, written: PgLsn, flushed: PgLsn, applied: PgLsn) {
+        self.0
+            .standby_status_update(written.into(), flushed.into(), applied.into())
+    }
+
+    /// Sets the interval at which standby status updates are sent to the server.
+    ///
+    /// This should be shorter than the server's `wal_sender_timeout`, or the server will close
+    /// the connection. `None` disables periodic updates, in which case updates are only sent when
+    /// requested by the server or by `standby_status_update`.
+    ///
+    /// Defaults to 10 seconds.
+    pub fn status_interval(&mut self, interval: Option<Duration>) {
+        self.0.status_interval(interval)
+    }
+
+    /// Asks the server to stop streaming.
+    ///
+    /// Messages already in flight will still be returned before the stream ends.
+    pub fn stop(&mut self) {
+        self.0.stop()
+    }
+
+    /// Returns the timeline following the one streamed and the LSN at which it begins.
+    ///
+    /// The server ends physical replication when it reaches the end of a historic timeline, at
+    /// which point this will return `Some` once the stream has ended.
+    pub fn next_timeline(&self) -> Option<(u32, PgLsn)> {
+        self.0.next_timeline()
+    }
+}
+
+impl Stream for ReplicationStream {
+    type Item = ReplicationMessage;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Option<ReplicationMessage>, Error> {
+        self.0.poll()
+    }
+}
@@ -0,0 +1,123 @@
+//! Utilities for working with the `text` and `csv` `COPY` formats.
+
+use futures::{Async, Poll, Stream};
+use postgres_shared::text_copy::{TextCopyDecoder, TextCopyEncoder};
+use std::error::Error as StdError;
+
+#[doc(inline)]
+pub use postgres_shared::text_copy::{TextCopyFormat, TextCopyOptions, TextCopyRow};
+
+use Error;
+
+/// A stream adaptor which encodes rows in a textual `COPY` format.
+///
+/// The adaptor yields chunks of data which can be passed to `Client::copy_in`.
+#[must_use = "streams do nothing unless polled"]
+pub struct TextCopyWriter<S> {
+    stream: S,
+    encoder: TextCopyEncoder,
+    header: Option<Vec<u8>>,
+}
+
+impl<S, T> TextCopyWriter<S>
+where
+    S: Stream<Item = Vec<Option<T>>>,
+    S::Error: Into<Box<StdError + Sync + Send>>,
+    T: AsRef<str>,
+{
+    /// Creates a new adaptor for a stream of rows.
+    pub fn new(stream: S, options: &TextCopyOptions) -> TextCopyWriter<S> {
+        TextCopyWriter {
+            stream: stream,
+            encoder: TextCopyEncoder::new(options),
+            header: None,
+        }
+    }
+
+    /// Writes a header line containing the specified column names before the rows.
+    ///
+    /// This should only be used if the header option is set, and before the adaptor is polled.
+    pub fn set_header(&mut self, names: &[&str]) {
+        let mut buf = vec![];
+        self.encoder.encode_header(names, &mut buf);
+        self.header = Some(buf);
+    }
+}
+
+impl<S, T> Stream for TextCopyWriter<S>
+where
+    S: Stream<Item = Vec<Option<T>>>,
+    S::Error: Into<Box<StdError + Sync + Send>>,
+    T: AsRef<str>,
+{
+    type Item = Vec<u8>;
+    type Error = Box<StdError + Sync + Send>;
+
+    fn poll(&mut self) -> Poll<Option<Vec<u8>>, Box<StdError + Sync + Send>> {
+        if let Some(header) = self.header.take() {
+            return Ok(Async::Ready(Some(header)));
+        }
+
+        match try_ready!(self.stream.poll().map_err(Into::into)) {
+            Some(row) => {
+                let mut buf = vec![];
+                self.encoder.encode_row(&row, &mut buf)?;
+                Ok(Async::Ready(Some(buf)))
+            }
+            None => Ok(Async::Ready(None)),
+        }
+    }
+}
+
+/// A stream adaptor which decodes rows in a textual `COPY` format.
+///
+/// The adaptor wraps the stream of data returned by `Client::copy_out`.
+#[must_use = "streams do nothing unless polled"]
+pub struct TextCopyReader<S> {
+    stream: S,
+    decoder: TextCopyDecoder,
+    done: bool,
+}
+
+impl<S> TextCopyReader<S>
+where
+    S: Stream<Error = Error>,
+    S::Item: AsRef<[u8]>,
+{
+    /// Creates a new adaptor for a stream of data.
+    pub fn new(stream: S, options: &TextCopyOptions) -> TextCopyReader<S> {
+        TextCopyReader {
+            stream: stream,
+            decoder: TextCopyDecoder::new(options),
+            done: false,
+        }
+    }
+}
+
+impl<S> Stream for TextCopyReader<S>
+where
+    S: Stream<Error = Error>,
+    S::Item: AsRef<[u8]>,
+{
+    type Item = TextCopyRow;
+    type Error = Error;
+
+    fn poll(&mut self) -> Poll<Option<TextCopyRow>, Error> {
+        loop {
+            if let Some(row) = self.decoder.next_row().map_err(Error::parse)? {
+                return Ok(Async::Ready(Some(row)));
+            }
+            if self.done {
+                return Ok(Async::Ready(None));
+            }
+
+            match try_ready!(self.stream.poll()) {
+                Some(data) => self.decoder.push(data.as_ref()),
+                None => {
+                    self.done = true;
+                    self.decoder.end_of_input();
+                }
+            }
+        }
+    }
+}
@@ -1,4 +1,5 @@
 extern crate env_logger;
+extern crate postgres_pgoutput;
 extern crate tokio;
 extern crate tokio_postgres;
 
@@ -10,14 +11,24 @@ extern crate log;
 use futures::future;
 use futures::stream;
 use futures::sync::mpsc;
+use postgres_pgoutput::LogicalReplicationMessage;
 use std::error::Error;
+use std::io::{Read, SeekFrom};
 use std::time::{Duration, Instant};
 use tokio::prelude::*;
 use tokio::runtime::current_thread::Runtime;
 use tokio::timer::Delay;
+use tokio_postgres::advisory_lock;
+use tokio_postgres::binary_copy::{BinaryCopyReader, BinaryCopyWriter};
+use tokio_postgres::cursor;
 use tokio_postgres::error::SqlState;
-use tokio_postgres::types::{Kind, Type};
-use tokio_postgres::{AsyncMessage, TlsMode};
+use tokio_postgres::large_object::Mode;
+use tokio_postgres::replication::{
+    BaseBackupMessage, BaseBackupOptions, ReplicationMessage, SlotKind,
+};
+use tokio_postgres::text_copy::{TextCopyOptions, TextCopyReader, TextCopyWriter};
+use tokio_postgres::types::{Kind, RawValue, ToSql, Type};
+use tokio_postgres::{AsyncMessage, Format, TlsMode};
 
 fn smoke_test(url: &str) {
     let _ = env_logger::try_init();
@@ -241,6 +252,48 @@ fn query_portal() {
     assert_eq!(r3.len(), 0);
 }
 
+#[test]
+fn query_notices() {
+    let _ = env_logger::try_init();
+    let mut runtime = Runtime::new().unwrap();
+
+    let handshake = tokio_postgres::connect(
+        "postgres://postgres@localhost:5433".parse().unwrap(),
+        TlsMode::None,
+    );
+    let (mut client, connection) = runtime.block_on(handshake).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.handle().spawn(connection).unwrap();
+
+    runtime
+        .block_on(client.batch_execute(
+            "CREATE FUNCTION pg_temp.note(msg TEXT) RETURNS INT AS $$
+             BEGIN
+                RAISE NOTICE '%', msg;
+                RETURN 1;
+             END; $$ LANGUAGE plpgsql",
+        )).unwrap();
+
+    let statement = runtime
+        .block_on(client.prepare("SELECT pg_temp.note($1)"))
+        .unwrap();
+
+    let result = runtime
+        .block_on(client.query_with_notices(&statement, &[&"a"]))
+        .unwrap();
+    assert_eq!(result.value.len(), 1);
+    assert_eq!(result.value[0].get::<_, i32>(0), 1);
+    assert_eq!(result.notices.len(), 1);
+    assert_eq!(result.notices[0].message(), "a");
+
+    let result = runtime
+        .block_on(client.execute_with_notices(&statement, &[&"b"]))
+        .unwrap();
+    assert_eq!(result.value, 1);
+    assert_eq!(result.notices.len(), 1);
+    assert_eq!(result.notices[0].message(), "b");
+}
+
 #[test]
 fn cancel_query() {
     let _ = env_logger::try_init();
@@ -506,6 +559,346 @@ fn notifications() {
     assert_eq!(notifications[1].payload, "world");
 }
 
+#[test]
+fn listen() {
+    let _ = env_logger::try_init();
+    let mut runtime = Runtime::new().unwrap();
+
+    let handshake = tokio_postgres::connect(
+        "postgres://postgres@localhost:5433".parse().unwrap(),
+        TlsMode::None,
+    );
+    let (mut client, connection) = runtime.block_on(handshake).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.handle().spawn(connection).unwrap();
+
+    let mut listen = client.listen("test_listen");
+    let mut other = client.listen("test_listen_other");
+    runtime
+        .block_on(future::poll_fn(|| {
+            listen.poll()?;
+            other.poll()?;
+            Ok::<_, tokio_postgres::error::Error>(Async::Ready(()))
+        })).unwrap();
+
+    runtime
+        .block_on(client.batch_execute(
+            "NOTIFY test_listen, 'hello'; NOTIFY test_listen_other, 'skipped'; \
+             NOTIFY test_listen, 'world'",
+        )).unwrap();
+
+    let notifications = runtime.block_on(listen.take(2).collect()).unwrap();
+    assert_eq!(notifications.len(), 2);
+    assert_eq!(notifications[0].channel, "test_listen");
+    assert_eq!(notifications[0].payload, "hello");
+    assert_eq!(notifications[1].channel, "test_listen");
+    assert_eq!(notifications[1].payload, "world");
+}
+
+#[test]
+fn listen_relisten() {
+    let _ = env_logger::try_init();
+    let mut runtime = Runtime::new().unwrap();
+
+    let handshake = tokio_postgres::connect(
+        "postgres://postgres@localhost:5433".parse().unwrap(),
+        TlsMode::None,
+    );
+    let (mut client, connection) = runtime.block_on(handshake).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.handle().spawn(connection).unwrap();
+
+    let mut listen = client.listen("test_relisten");
+    runtime
+        .block_on(future::poll_fn(|| {
+            listen.poll()?;
+            Ok::<_, tokio_postgres::error::Error>(Async::Ready(()))
+        })).unwrap();
+    runtime
+        .block_on(client.batch_execute("NOTIFY test_relisten, 'before'"))
+        .unwrap();
+    drop(client);
+
+    let handshake = tokio_postgres::connect(
+        "postgres://postgres@localhost:5433".parse().unwrap(),
+        TlsMode::None,
+    );
+    let (mut client, connection) = runtime.block_on(handshake).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.handle().spawn(connection).unwrap();
+
+    listen.relisten(&mut client);
+    // the queued notification is delivered once the new LISTEN completes
+    let notifications = runtime.block_on(listen.by_ref().take(1).collect()).unwrap();
+    assert_eq!(notifications[0].payload, "before");
+
+    runtime
+        .block_on(client.batch_execute("NOTIFY test_relisten, 'after'"))
+        .unwrap();
+    let notifications = runtime.block_on(listen.by_ref().take(1).collect()).unwrap();
+    assert_eq!(notifications[0].payload, "after");
+
+    drop(listen);
+    let statement = runtime
+        .block_on(client.prepare("SELECT pg_catalog.pg_listening_channels()"))
+        .unwrap();
+    let channels = runtime
+        .block_on(client.query(&statement, &[]).collect())
+        .unwrap();
+    assert!(channels.is_empty());
+}
+
+#[test]
+fn connection_messages() {
+    let _ = env_logger::try_init();
+    let mut runtime = Runtime::new().unwrap();
+
+    let handshake = tokio_postgres::connect(
+        "postgres://postgres@localhost:5433".parse().unwrap(),
+        TlsMode::None,
+    );
+    let (mut client, mut connection) = runtime.block_on(handshake).unwrap();
+    let messages = connection.messages();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.handle().spawn(connection).unwrap();
+
+    runtime
+        .block_on(client.batch_execute(
+            "LISTEN test_connection_messages; NOTIFY test_connection_messages, 'hello'",
+        )).unwrap();
+
+    drop(client);
+    runtime.run().unwrap();
+
+    let notifications = messages
+        .filter_map(|m| match m {
+            AsyncMessage::Notification(n) => Some(n),
+            _ => None,
+        }).collect()
+        .wait()
+        .unwrap();
+    assert_eq!(notifications.len(), 1);
+    assert_eq!(notifications[0].channel, "test_connection_messages");
+    assert_eq!(notifications[0].payload, "hello");
+}
+
+#[test]
+fn logical_replication() {
+    let _ = env_logger::try_init();
+    let mut runtime = Runtime::new().unwrap();
+
+    let (mut client, connection) = runtime
+        .block_on(tokio_postgres::connect(
+            "postgres://postgres@localhost:5433".parse().unwrap(),
+            TlsMode::None,
+        )).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.handle().spawn(connection).unwrap();
+
+    runtime
+        .block_on(client.batch_execute(
+            "DROP TABLE IF EXISTS replication_foo;
+             CREATE TABLE replication_foo (id SERIAL PRIMARY KEY, name TEXT);
+             DROP PUBLICATION IF EXISTS replication_pub;
+             CREATE PUBLICATION replication_pub FOR TABLE replication_foo;",
+        )).unwrap();
+
+    let (mut repl_client, connection) = runtime
+        .block_on(tokio_postgres::connect(
+            "postgres://postgres@localhost:5433/postgres?replication=database"
+                .parse()
+                .unwrap(),
+            TlsMode::None,
+        )).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.handle().spawn(connection).unwrap();
+
+    let system = runtime.block_on(repl_client.identify_system()).unwrap();
+    assert_eq!(system.dbname(), Some("postgres"));
+
+    let slot = runtime
+        .block_on(repl_client.create_replication_slot(
+            "replication_slot",
+            true,
+            &SlotKind::Logical("pgoutput".to_string()),
+        )).unwrap();
+    assert_eq!(slot.slot_name(), "replication_slot");
+    assert_eq!(slot.output_plugin(), Some("pgoutput"));
+
+    runtime
+        .block_on(client.batch_execute("INSERT INTO replication_foo (name) VALUES ('steven')"))
+        .unwrap();
+
+    let stream = repl_client.start_logical_replication(
+        "replication_slot",
+        slot.consistent_point(),
+        &[
+            ("proto_version", postgres_pgoutput::PROTOCOL_VERSION),
+            ("publication_names", "replication_pub"),
+        ],
+    );
+    let messages = stream
+        .filter_map(|m| match m {
+            ReplicationMessage::XLogData(data) => {
+                Some(LogicalReplicationMessage::parse(data.data()).unwrap())
+            }
+            _ => None,
+        }).take_while(|m| match *m {
+            LogicalReplicationMessage::Commit(_) => future::ok(false),
+            _ => future::ok(true),
+        }).collect();
+    let messages = runtime.block_on(messages).unwrap();
+
+    match messages[0] {
+        LogicalReplicationMessage::Begin(_) => {}
+        ref m => panic!("unexpected message {:?}", m),
+    }
+    match messages[1] {
+        LogicalReplicationMessage::Relation(ref relation) => {
+            assert_eq!(relation.name(), "replication_foo");
+            assert_eq!(relation.columns().len(), 2);
+        }
+        ref m => panic!("unexpected message {:?}", m),
+    }
+    match messages[2] {
+        LogicalReplicationMessage::Insert(ref insert) => {
+            assert_eq!(insert.tuple().data()[1].as_str(), Some("steven"));
+        }
+        ref m => panic!("unexpected message {:?}", m),
+    }
+}
+
+#[test]
+fn physical_replication() {
+    let _ = env_logger::try_init();
+    let mut runtime = Runtime::new().unwrap();
+
+    let (mut client, connection) = runtime
+        .block_on(tokio_postgres::connect(
+            "postgres://postgres@localhost:5433?replication=true"
+                .parse()
+                .unwrap(),
+            TlsMode::None,
+        )).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.handle().spawn(connection).unwrap();
+
+    let system = runtime.block_on(client.identify_system()).unwrap();
+    assert_eq!(system.dbname(), None);
+
+    let mut stream = client.start_physical_replication(None, system.xlogpos(), None);
+    let message = runtime
+        .block_on(future::poll_fn(|| stream.poll()))
+        .unwrap()
+        .unwrap();
+    match message {
+        ReplicationMessage::XLogData(data) => assert!(data.wal_start() >= system.xlogpos()),
+        ReplicationMessage::PrimaryKeepAlive(keepalive) => {
+            assert!(keepalive.wal_end() >= system.xlogpos())
+        }
+        _ => panic!("unexpected message"),
+    }
+
+    stream.stop();
+    runtime.block_on(stream.for_each(|_| Ok(()))).unwrap();
+}
+
+#[test]
+fn replication_status_interval() {
+    let _ = env_logger::try_init();
+    let mut runtime = Runtime::new().unwrap();
+
+    let (mut client, connection) = runtime
+        .block_on(tokio_postgres::connect(
+            "postgres://postgres@localhost:5433".parse().unwrap(),
+            TlsMode::None,
+        )).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.handle().spawn(connection).unwrap();
+
+    let (mut repl_client, connection) = runtime
+        .block_on(tokio_postgres::connect(
+            "postgres://postgres@localhost:5433?replication=true&application_name=status_interval"
+                .parse()
+                .unwrap(),
+            TlsMode::None,
+        )).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.handle().spawn(connection).unwrap();
+
+    let system = runtime.block_on(repl_client.identify_system()).unwrap();
+    let mut stream = repl_client.start_physical_replication(None, system.xlogpos(), None);
+    stream.status_interval(Some(Duration::from_millis(100)));
+
+    // poll the stream for a while without ever calling standby_status_update
+    let mut delay = Delay::new(Instant::now() + Duration::from_secs(1));
+    runtime
+        .block_on(future::poll_fn(|| loop {
+            if delay.poll().unwrap().is_ready() {
+                return Ok::<_, tokio_postgres::error::Error>(Async::Ready(()));
+            }
+            match stream.poll()? {
+                Async::Ready(Some(_)) => {}
+                Async::Ready(None) => panic!("unexpected end of stream"),
+                Async::NotReady => return Ok(Async::NotReady),
+            }
+        })).unwrap();
+
+    let statement = runtime
+        .block_on(client.prepare(
+            "SELECT write_lsn::TEXT FROM pg_stat_replication \
+             WHERE application_name = 'status_interval'",
+        )).unwrap();
+    let rows = runtime
+        .block_on(client.query(&statement, &[]).collect())
+        .unwrap();
+    assert_eq!(rows.len(), 1);
+    let write_lsn: Option<String> = rows[0].get(0);
+    assert!(write_lsn.is_some());
+
+    stream.stop();
+    runtime.block_on(stream.for_each(|_| Ok(()))).unwrap();
+}
+
+#[test]
+fn base_backup() {
+    let _ = env_logger::try_init();
+    let mut runtime = Runtime::new().unwrap();
+
+    let (mut client, connection) = runtime
+        .block_on(tokio_postgres::connect(
+            "postgres://postgres@localhost:5433?replication=true"
+                .parse()
+                .unwrap(),
+            TlsMode::None,
+        )).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.handle().spawn(connection).unwrap();
+
+    let backup = client.base_backup(BaseBackupOptions::new().label("test").fast(true));
+    let messages = runtime.block_on(backup.collect()).unwrap();
+
+    let start = match messages[0] {
+        BaseBackupMessage::Start { lsn, .. } => lsn,
+        ref m => panic!("unexpected message {:?}", m),
+    };
+    let tablespaces = match messages[1] {
+        BaseBackupMessage::Tablespaces(ref tablespaces) => tablespaces.len(),
+        ref m => panic!("unexpected message {:?}", m),
+    };
+    let archives = messages
+        .iter()
+        .filter(|m| match **m {
+            BaseBackupMessage::ArchiveStart => true,
+            _ => false,
+        }).count();
+    assert_eq!(archives, tablespaces);
+    match messages[messages.len() - 1] {
+        BaseBackupMessage::End { lsn, .. } => assert!(lsn >= start),
+        ref m => panic!("unexpected message {:?}", m),
+    }
+}
+
 #[test]
 fn transaction_commit() {
     let _ = env_logger::try_init();
@@ -578,6 +971,383 @@ fn transaction_abort() {
     assert_eq!(rows.len(), 0);
 }
 
+#[test]
+fn prepare_transaction() {
+    let _ = env_logger::try_init();
+    let mut runtime = Runtime::new().unwrap();
+
+    let (mut client, connection) = runtime
+        .block_on(tokio_postgres::connect(
+            "postgres://postgres@localhost:5433".parse().unwrap(),
+            TlsMode::None,
+        )).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.handle().spawn(connection).unwrap();
+
+    // temporary tables can't be used in prepared transactions
+    runtime
+        .block_on(client.batch_execute(
+            "DROP TABLE IF EXISTS tokio_prepare_transaction_test;
+             CREATE TABLE tokio_prepare_transaction_test (name TEXT)",
+        )).unwrap();
+
+    let f = client.batch_execute(
+        "INSERT INTO tokio_prepare_transaction_test (name) VALUES ('steven')",
+    );
+    runtime
+        .block_on(client.prepare_transaction(f, "tokio-postgres-commit"))
+        .unwrap();
+
+    let prepared = runtime.block_on(client.list_prepared()).unwrap();
+    assert!(prepared.iter().any(|p| p.gid == "tokio-postgres-commit"));
+
+    runtime
+        .block_on(client.commit_prepared("tokio-postgres-commit"))
+        .unwrap();
+
+    let rows = runtime
+        .block_on(
+            client
+                .prepare("SELECT name FROM tokio_prepare_transaction_test")
+                .and_then(|s| client.query(&s, &[]).collect()),
+        ).unwrap();
+
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0].get::<_, &str>(0), "steven");
+
+    runtime
+        .block_on(client.batch_execute("DROP TABLE tokio_prepare_transaction_test"))
+        .unwrap();
+}
+
+#[test]
+fn cursor() {
+    let _ = env_logger::try_init();
+    let mut runtime = Runtime::new().unwrap();
+
+    let (mut client, connection) = runtime
+        .block_on(tokio_postgres::connect(
+            "postgres://postgres@localhost:5433".parse().unwrap(),
+            TlsMode::None,
+        )).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.handle().spawn(connection).unwrap();
+
+    let mut config = cursor::Config::new();
+    config.scroll(true);
+    let statement = runtime
+        .block_on(client.prepare_cursor("SELECT * FROM generate_series(1, $1) AS id", &config))
+        .unwrap();
+    assert_eq!(statement.params(), &[Type::INT4]);
+
+    runtime.block_on(client.batch_execute("BEGIN")).unwrap();
+    let mut cursor = runtime
+        .block_on(client.declare_cursor(&statement, &[&10i32]))
+        .unwrap();
+
+    let ids = |stream: cursor::FetchCursor| stream.map(|r| r.get::<_, i32>(0)).collect();
+    let rows = runtime.block_on(ids(cursor.fetch(3))).unwrap();
+    assert_eq!(rows, vec![1, 2, 3]);
+    let rows = runtime.block_on(ids(cursor.fetch_backward(2))).unwrap();
+    assert_eq!(rows, vec![2, 1]);
+    assert!(runtime.block_on(cursor.move_absolute(8)).unwrap());
+    let rows = runtime.block_on(ids(cursor.fetch(5))).unwrap();
+    assert_eq!(rows, vec![9, 10]);
+    let rows = runtime.block_on(ids(cursor.fetch(1))).unwrap();
+    assert!(rows.is_empty());
+    assert!(!runtime.block_on(cursor.move_absolute(11)).unwrap());
+    runtime.block_on(cursor.close()).unwrap();
+    runtime.block_on(client.batch_execute("COMMIT")).unwrap();
+}
+
+#[test]
+fn cursor_with_hold() {
+    let _ = env_logger::try_init();
+    let mut runtime = Runtime::new().unwrap();
+
+    let (mut client, connection) = runtime
+        .block_on(tokio_postgres::connect(
+            "postgres://postgres@localhost:5433".parse().unwrap(),
+            TlsMode::None,
+        )).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.handle().spawn(connection).unwrap();
+
+    let mut config = cursor::Config::new();
+    config.hold(true);
+    let statement = runtime
+        .block_on(client.prepare_cursor("SELECT * FROM generate_series(1, 5)", &config))
+        .unwrap();
+    let mut cursor = runtime
+        .block_on(client.declare_cursor(&statement, &[]))
+        .unwrap();
+    let rows = runtime.block_on(cursor.fetch(2).collect()).unwrap();
+    assert_eq!(rows.len(), 2);
+    drop(cursor);
+
+    // the close is sent in the background, but queues up ahead of this query
+    let query = client
+        .prepare("SELECT 1 FROM pg_cursors WHERE name = $1")
+        .and_then(|s| client.query(&s, &[&statement.name()]).collect());
+    let rows = runtime.block_on(query).unwrap();
+    assert!(rows.is_empty());
+}
+
+#[test]
+fn large_object() {
+    let _ = env_logger::try_init();
+    let mut runtime = Runtime::new().unwrap();
+
+    let (mut client, connection) = runtime
+        .block_on(tokio_postgres::connect(
+            "postgres://postgres@localhost:5433".parse().unwrap(),
+            TlsMode::None,
+        )).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.handle().spawn(connection).unwrap();
+
+    runtime.block_on(client.batch_execute("BEGIN")).unwrap();
+    let oid = runtime.block_on(client.create_large_object()).unwrap();
+
+    let lo = runtime
+        .block_on(client.open_large_object(oid, Mode::ReadWrite))
+        .unwrap();
+    let (mut lo, _) = runtime
+        .block_on(tokio::io::write_all(lo, b"hello world"))
+        .unwrap();
+    let pos = runtime
+        .block_on(future::poll_fn(|| lo.poll_seek(SeekFrom::Start(6))))
+        .unwrap();
+    assert_eq!(pos, 6);
+    let (mut lo, buf) = runtime
+        .block_on(tokio::io::read_to_end(lo, vec![]))
+        .unwrap();
+    assert_eq!(buf, b"world");
+    runtime
+        .block_on(future::poll_fn(|| lo.poll_close()))
+        .unwrap();
+    assert!(lo.read(&mut [0; 1]).is_err());
+
+    runtime.block_on(client.unlink_large_object(oid)).unwrap();
+    runtime.block_on(client.batch_execute("COMMIT")).unwrap();
+}
+
+#[test]
+fn large_object_outside_transaction() {
+    let _ = env_logger::try_init();
+    let mut runtime = Runtime::new().unwrap();
+
+    let (mut client, connection) = runtime
+        .block_on(tokio_postgres::connect(
+            "postgres://postgres@localhost:5433".parse().unwrap(),
+            TlsMode::None,
+        )).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.handle().spawn(connection).unwrap();
+
+    let oid = runtime.block_on(client.create_large_object()).unwrap();
+    assert!(
+        runtime
+            .block_on(client.open_large_object(oid, Mode::Read))
+            .is_err()
+    );
+
+    // the statements prepared by the first open are reused by the second
+    runtime.block_on(client.batch_execute("BEGIN")).unwrap();
+    for _ in 0..2 {
+        let mut lo = runtime
+            .block_on(client.open_large_object(oid, Mode::Read))
+            .unwrap();
+        runtime
+            .block_on(future::poll_fn(|| lo.poll_close()))
+            .unwrap();
+    }
+    runtime.block_on(client.batch_execute("ROLLBACK")).unwrap();
+
+    runtime.block_on(client.unlink_large_object(oid)).unwrap();
+}
+
+#[test]
+fn advisory_lock() {
+    let _ = env_logger::try_init();
+    let mut runtime = Runtime::new().unwrap();
+
+    let (mut client, connection) = runtime
+        .block_on(tokio_postgres::connect(
+            "postgres://postgres@localhost:5433".parse().unwrap(),
+            TlsMode::None,
+        )).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.handle().spawn(connection).unwrap();
+
+    let (mut client2, connection) = runtime
+        .block_on(tokio_postgres::connect(
+            "postgres://postgres@localhost:5433".parse().unwrap(),
+            TlsMode::None,
+        )).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.handle().spawn(connection).unwrap();
+
+    let lock = runtime
+        .block_on(client.advisory_lock(7_340_003i64, advisory_lock::Mode::Exclusive))
+        .unwrap();
+    let lock2 = runtime
+        .block_on(client2.try_advisory_lock(7_340_003i64, advisory_lock::Mode::Exclusive))
+        .unwrap();
+    assert!(lock2.is_none());
+    runtime.block_on(lock.unlock()).unwrap();
+
+    let lock2 = runtime
+        .block_on(client2.try_advisory_lock(7_340_003i64, advisory_lock::Mode::Exclusive))
+        .unwrap();
+    assert!(lock2.is_some());
+    drop(lock2);
+
+    // the unlock is sent in the background, so wait for it
+    let lock = runtime
+        .block_on(client.advisory_lock(7_340_003i64, advisory_lock::Mode::Exclusive))
+        .unwrap();
+    assert_eq!(lock.key(), advisory_lock::Key::Single(7_340_003));
+}
+
+#[test]
+fn advisory_lock_acquire_dropped() {
+    let _ = env_logger::try_init();
+    let mut runtime = Runtime::new().unwrap();
+
+    let (mut client, connection) = runtime
+        .block_on(tokio_postgres::connect(
+            "postgres://postgres@localhost:5433".parse().unwrap(),
+            TlsMode::None,
+        )).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.handle().spawn(connection).unwrap();
+
+    let (mut client2, connection) = runtime
+        .block_on(tokio_postgres::connect(
+            "postgres://postgres@localhost:5433".parse().unwrap(),
+            TlsMode::None,
+        )).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.handle().spawn(connection).unwrap();
+
+    let lock2 = runtime
+        .block_on(client2.advisory_lock(7_340_004i64, advisory_lock::Mode::Exclusive))
+        .unwrap();
+
+    // send the request, which blocks on the server, and then give up on it
+    let mut acquire = client.advisory_lock(7_340_004i64, advisory_lock::Mode::Exclusive);
+    runtime
+        .block_on(future::lazy(|| {
+            assert!(acquire.poll().unwrap().is_not_ready());
+            Ok::<_, ()>(())
+        })).unwrap();
+    drop(acquire);
+
+    // the server grants the lock once it's released, and the unlock queued behind the request
+    // releases it again before this query runs
+    runtime.block_on(lock2.unlock()).unwrap();
+    runtime.block_on(client.batch_execute("SELECT 1")).unwrap();
+
+    let lock2 = runtime
+        .block_on(client2.try_advisory_lock(7_340_004i64, advisory_lock::Mode::Exclusive))
+        .unwrap();
+    assert!(lock2.is_some());
+}
+
+#[test]
+fn text_result_format() {
+    let _ = env_logger::try_init();
+    let mut runtime = Runtime::new().unwrap();
+
+    let (mut client, connection) = runtime
+        .block_on(tokio_postgres::connect(
+            "postgres://postgres@localhost:5433".parse().unwrap(),
+            TlsMode::None,
+        )).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.handle().spawn(connection).unwrap();
+
+    let mut stmt = runtime
+        .block_on(client.prepare("SELECT 1::INT, 'a fat cat'::TSVECTOR, NULL::MONEY"))
+        .unwrap();
+    assert!(stmt.set_result_formats(&[Format::Text, Format::Text]).is_err());
+    stmt.set_result_formats(&[Format::Binary, Format::Text, Format::Text])
+        .unwrap();
+    let rows = runtime
+        .block_on(client.query(&stmt, &[]).collect())
+        .unwrap();
+
+    assert_eq!(rows[0].get::<_, i32>(0), 1);
+    assert_eq!(rows[0].get_text(1), Some("'a' 'cat' 'fat'"));
+    assert_eq!(rows[0].get::<_, String>(1), "'a' 'cat' 'fat'");
+    assert_eq!(rows[0].get_text(2), None);
+    assert!(rows[0].try_get_text(0).is_err());
+    assert_eq!(rows[0].try_get_text(1).unwrap(), Some("'a' 'cat' 'fat'"));
+}
+
+#[test]
+fn raw_value() {
+    let _ = env_logger::try_init();
+    let mut runtime = Runtime::new().unwrap();
+
+    let (mut client, connection) = runtime
+        .block_on(tokio_postgres::connect(
+            "postgres://postgres@localhost:5433".parse().unwrap(),
+            TlsMode::None,
+        )).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.handle().spawn(connection).unwrap();
+
+    let stmt = runtime
+        .block_on(client.prepare("SELECT 1::INT, NULL::TEXT, 'a fat cat'::TSVECTOR"))
+        .unwrap();
+    let rows = runtime
+        .block_on(client.query(&stmt, &[]).collect())
+        .unwrap();
+    assert_eq!(rows[0].get_raw(0), Some(&[0, 0, 0, 1][..]));
+    assert_eq!(rows[0].get_raw(1), None);
+    let value: RawValue = rows[0].get(2);
+
+    let stmt = runtime
+        .block_on(client.prepare("SELECT $1::TSVECTOR::TEXT"))
+        .unwrap();
+    let rows = runtime
+        .block_on(client.query(&stmt, &[&value]).collect())
+        .unwrap();
+    assert_eq!(rows[0].get::<_, String>(0), "'a' 'cat' 'fat'");
+}
+
+#[test]
+fn column_metadata() {
+    let _ = env_logger::try_init();
+    let mut runtime = Runtime::new().unwrap();
+
+    let (mut client, connection) = runtime
+        .block_on(tokio_postgres::connect(
+            "postgres://postgres@localhost:5433".parse().unwrap(),
+            TlsMode::None,
+        )).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.handle().spawn(connection).unwrap();
+
+    runtime
+        .block_on(client.batch_execute(
+            "CREATE TEMPORARY TABLE foo (name VARCHAR(10), price NUMERIC(10, 2))",
+        )).unwrap();
+
+    let stmt = runtime
+        .block_on(client.prepare("SELECT name, price, 1 FROM foo"))
+        .unwrap();
+    let columns = stmt.columns();
+    assert!(columns[0].table_oid().is_some());
+    assert_eq!(columns[0].column_id(), Some(1));
+    assert_eq!(columns[0].character_maximum_length(), Some(10));
+    assert_eq!(columns[1].numeric_precision(), Some(10));
+    assert_eq!(columns[1].numeric_scale(), Some(2));
+    assert_eq!(columns[2].table_oid(), None);
+}
+
 #[test]
 fn copy_in() {
     let _ = env_logger::try_init();
@@ -662,6 +1432,105 @@ fn copy_in_error() {
     assert_eq!(rows.len(), 0);
 }
 
+#[test]
+fn binary_copy() {
+    let _ = env_logger::try_init();
+    let mut runtime = Runtime::new().unwrap();
+
+    let (mut client, connection) = runtime
+        .block_on(tokio_postgres::connect(
+            "postgres://postgres@localhost:5433".parse().unwrap(),
+            TlsMode::None,
+        )).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.handle().spawn(connection).unwrap();
+
+    runtime
+        .block_on(client.batch_execute(
+            "CREATE TEMPORARY TABLE foo (
+                id INTEGER,
+                name TEXT
+             )",
+        )).unwrap();
+
+    let types = [Type::INT4, Type::TEXT];
+    let rows: Vec<Vec<Box<ToSql + Send>>> = vec![
+        vec![Box::new(1i32), Box::new("jim".to_string())],
+        vec![Box::new(2i32), Box::new(None::<String>)],
+    ];
+    let stream = BinaryCopyWriter::new(stream::iter_ok::<_, String>(rows), &types).unwrap();
+    let count = runtime
+        .block_on(
+            client
+                .prepare("COPY foo (id, name) FROM STDIN (FORMAT binary)")
+                .and_then(|s| client.copy_in(&s, &[], stream)),
+        ).unwrap();
+    assert_eq!(count, 2);
+
+    let rows = runtime
+        .block_on(
+            client
+                .prepare("COPY (SELECT id, name FROM foo ORDER BY id) TO STDOUT (FORMAT binary)")
+                .and_then(|s| BinaryCopyReader::new(client.copy_out(&s, &[]), &types).collect()),
+        ).unwrap();
+    assert_eq!(rows.len(), 2);
+    assert_eq!(rows[0].get::<i32>(0), 1);
+    assert_eq!(rows[0].get::<Option<&str>>(1), Some("jim"));
+    assert_eq!(rows[1].get::<i32>(0), 2);
+    assert_eq!(rows[1].get::<Option<&str>>(1), None);
+}
+
+#[test]
+fn text_copy() {
+    let _ = env_logger::try_init();
+    let mut runtime = Runtime::new().unwrap();
+
+    let (mut client, connection) = runtime
+        .block_on(tokio_postgres::connect(
+            "postgres://postgres@localhost:5433".parse().unwrap(),
+            TlsMode::None,
+        )).unwrap();
+    let connection = connection.map_err(|e| panic!("{}", e));
+    runtime.handle().spawn(connection).unwrap();
+
+    runtime
+        .block_on(client.batch_execute(
+            "CREATE TEMPORARY TABLE foo (
+                id INTEGER,
+                name TEXT
+             )",
+        )).unwrap();
+
+    let options = TextCopyOptions::text();
+    let rows = vec![
+        vec![Some("1"), Some("tab\there\\")],
+        vec![Some("2"), None],
+    ];
+    let stream = TextCopyWriter::new(stream::iter_ok::<_, String>(rows), &options);
+    let count = runtime
+        .block_on(
+            client
+                .prepare("COPY foo (id, name) FROM STDIN")
+                .and_then(|s| client.copy_in(&s, &[], stream)),
+        ).unwrap();
+    assert_eq!(count, 2);
+
+    let options = TextCopyOptions::csv();
+    let rows = runtime
+        .block_on(
+            client
+                .prepare("COPY (SELECT id, name FROM foo ORDER BY id) TO STDOUT (FORMAT csv)")
+                .and_then(|s| TextCopyReader::new(client.copy_out(&s, &[]), &options).collect()),
+        ).unwrap();
+    assert_eq!(
+        rows,
+        vec![
+            vec![Some("1".to_string()), Some("tab\there\\".to_string())],
+            vec![Some("2".to_string()), None],
+        ]
+    );
+}
+
 #[test]
 fn copy_out() {
     let _ = env_logger::try_init();